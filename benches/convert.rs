@@ -0,0 +1,32 @@
+//! A simple benchmark of conversion functions.
+//!
+//! Run with `cargo bench`.
+use std::hint::black_box;
+use std::time::Instant;
+
+use kelp::{h2z, hira2kata, kata2hira, z2h, ConvOption};
+
+/// Repeat mixed-width strings up to about 1MB
+fn mixed_text() -> String {
+    let unit = "ＡＢＣabc１２３123アイウｱｲｳガｶﾞあいう漢字、。\n";
+    unit.repeat(1024 * 1024 / unit.len())
+}
+
+fn bench(name: &str, f: impl Fn() -> String) {
+    let times = 10;
+    let start = Instant::now();
+    for _ in 0..times {
+        black_box(f());
+    }
+    println!("{:<10} {:?}/iter", name, start.elapsed() / times);
+}
+
+fn main() {
+    let text = mixed_text();
+    let option = ConvOption::all();
+
+    bench("h2z", || h2z(black_box(&text), option));
+    bench("z2h", || z2h(black_box(&text), option));
+    bench("hira2kata", || hira2kata(black_box(&text), option));
+    bench("kata2hira", || kata2hira(black_box(&text), option));
+}
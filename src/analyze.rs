@@ -0,0 +1,157 @@
+//! Detect script composition of strings.
+use crate::detect::{classify, CharKind};
+
+/// Counts of characters by script
+///
+/// Each character is classified by `classify`, so these counts agree with `segment`
+/// and `dominant`. Characters shared by hiragana and katakana except `ー`(e.g. `。`)
+/// are counted as other characters.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct ScriptStats {
+    /// Hiragana
+    pub hiragana: usize,
+    /// Katakana(full-width), including prolonged sound mark
+    pub full_kana: usize,
+    /// Katakana(half-width), including voiced consonant marks and punctuation
+    pub half_kana: usize,
+    /// Kanji
+    pub kanji: usize,
+    /// Ascii(full-width) except digits, including ideographic space
+    pub full_ascii: usize,
+    /// Ascii(half-width) except digits, including space
+    pub half_ascii: usize,
+    /// Digits(full-width)
+    pub full_digit: usize,
+    /// Digits(half-width)
+    pub half_digit: usize,
+    /// Other characters
+    pub other: usize,
+}
+
+impl ScriptStats {
+    /// Count of characters of the kind
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::{analyze, CharKind};
+    ///
+    /// assert_eq!(2, analyze("漢字かな").get(CharKind::Kanji));
+    /// ```
+    pub fn get(&self, kind: CharKind) -> usize {
+        match kind {
+            CharKind::Hiragana => self.hiragana,
+            CharKind::Katakana => self.full_kana,
+            CharKind::HalfKana => self.half_kana,
+            CharKind::Kanji => self.kanji,
+            CharKind::FullAscii => self.full_ascii,
+            CharKind::HalfAscii => self.half_ascii,
+            CharKind::Digit => self.half_digit,
+            CharKind::FullDigit => self.full_digit,
+            CharKind::Other => self.other,
+        }
+    }
+
+    /// Total count of characters
+    pub fn total(&self) -> usize {
+        CharKind::ALL.iter().map(|kind| self.get(*kind)).sum()
+    }
+
+    fn get_mut(&mut self, kind: CharKind) -> &mut usize {
+        match kind {
+            CharKind::Hiragana => &mut self.hiragana,
+            CharKind::Katakana => &mut self.full_kana,
+            CharKind::HalfKana => &mut self.half_kana,
+            CharKind::Kanji => &mut self.kanji,
+            CharKind::FullAscii => &mut self.full_ascii,
+            CharKind::HalfAscii => &mut self.half_ascii,
+            CharKind::Digit => &mut self.half_digit,
+            CharKind::FullDigit => &mut self.full_digit,
+            CharKind::Other => &mut self.other,
+        }
+    }
+}
+
+/// Count characters by script in a single pass
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::analyze;
+///
+/// let stats = analyze("ｱｲｳあい漢字ABC１２３");
+/// assert_eq!(3, stats.half_kana);
+/// assert_eq!(2, stats.hiragana);
+/// assert_eq!(2, stats.kanji);
+/// assert_eq!(3, stats.half_ascii);
+/// assert_eq!(3, stats.full_digit);
+/// assert_eq!(0, stats.full_kana);
+/// assert_eq!(13, stats.total());
+/// ```
+pub fn analyze(text: &str) -> ScriptStats {
+    let mut stats = ScriptStats::default();
+    for c in text.chars() {
+        *stats.get_mut(classify(c)) += 1;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_mixed() {
+        let text = "ｱｲｳｴｵ あいうえお ＡＢＣ abc １２３ 123 カﾞ、漢字ー〇";
+        let stats = analyze(text);
+        assert_eq!(
+            stats,
+            ScriptStats {
+                hiragana: 5,
+                full_kana: 2,
+                half_kana: 6,
+                kanji: 3,
+                full_ascii: 3,
+                half_ascii: 9,
+                full_digit: 3,
+                half_digit: 3,
+                other: 1,
+            }
+        );
+        assert_eq!(35, stats.total());
+        for c in text.chars() {
+            assert_eq!(analyze(c.encode_utf8(&mut [0; 4])).get(classify(c)), 1);
+        }
+    }
+
+    #[test]
+    fn test_analyze_kana_punct() {
+        let stats = analyze("。「ー」");
+        assert_eq!((stats.full_kana, stats.other), (1, 3));
+    }
+
+    #[test]
+    fn test_analyze_empty() {
+        assert_eq!(analyze(""), ScriptStats::default());
+    }
+
+    #[test]
+    fn test_analyze_ideographic_space() {
+        let stats = analyze("　");
+        assert_eq!(1, stats.full_ascii);
+    }
+
+    #[test]
+    fn test_analyze_agrees_with_conversion() {
+        use crate::{z2h, ConvOption};
+
+        let text = "アイウ漢字ａｂｃ";
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(analyze(text).full_kana, 3);
+        assert_eq!(analyze(&z2h(text, option)).full_kana, 0);
+        assert_eq!(analyze(&z2h(text, option)).half_kana, 3);
+    }
+}
@@ -33,31 +33,111 @@
 //! Option | Pattern
 //! ------ | -------
 //! h2z | half-width to full-width
-//! h2hk | half-width to half-width(katakana)
-//! h2k | half-width to full-width(katakana)
-//! k2h | full-width(katakana) to half-width(katakana)
+//! h2hk, hira2hkata | hiragana to half-width(katakana)
+//! h2k, hira2kata | hiragana to full-width(katakana)
+//! k2h, kata2hira | full-width(katakana) to hiragana
 //! z2h | full-width to half-width
 //!
+//! An unknown pattern is reported as an error.
+//!
 //! - -a, --ascii
 //!     - Convert with ascii if specified
 //! - -d, --digit
 //!     - Convert with digit if specified
 //! - -k, --kana
 //!     - Convert with kana if specified
+//! - --ainu-kana
+//!     - Convert small katakana of Ainu(ㇰ, ㇱ and so on) as large ones if specified
+//! - --kana-fallback
+//!     - Convert katakana without half-width form(ヮ, ヰ, ヱ, ヵ and ヶ) to the nearest ones if specified
+//! - --kana-punct
+//!     - Convert with Japanese punctuation(「」。、・) if specified
+//! - --keep-kana-punct
+//!     - Keep Japanese punctuation(「」。、・) by hira2kata, hira2hkata and kata2hira if specified
+//! - --prolonged-mark
+//!     - Convert with prolonged sound mark(ー) if specified
+//! - --keep-prolonged-mark
+//!     - Keep prolonged sound mark(ー and ｰ) even if kana is specified
+//! - -l, --letter
+//!     - Convert with latin letters if specified
+//! - -p, --punct
+//!     - Convert with ascii symbols if specified
+//! - --currency
+//!     - Convert with currency symbols(￥, ＄ and so on) if specified
+//! - -e, --enclosed
+//!     - Convert enclosed numbers(①, ⑴ and so on) into digits if specified
+//! - -w, --wide-symbol
+//!     - Convert with symbols which have another width form(e.g. `￥`, `←`) if specified
+//! - --modern-kana
+//!     - Convert obsolete kana(ゐ, ゑ, ヰ and ヱ) to modern ones if specified
+//! - --numeral
+//!     - Convert roman numerals(Ⅻ, ⅻ and so on) into ascii letters if specified
+//! - --small-kana
+//!     - Convert with small katakana(ァ, ッ and so on) if specified
+//! - --exclude
+//!     - Specified a group which is not converted even if enabled, which can be repeated
+//!     - e.g. `-a --exclude letter`
+//!     - Groups are ascii, digit, kana, punct, letter, symbol, kana-punct, enclosed,
+//!       square, numeral, currency, prolonged-mark, space and small-kana
+//! - --space
+//!     - Convert space and ideographic space even if ascii is not specified
+//! - --square
+//!     - Expand squared characters(㌔, ㎝ and so on) into their spelled-out forms if specified
+//! - --voiced-base
+//!     - Convert voiced kana(ガ, パ and so on) to half-width katakana without voiced marks if specified
+//! - --keep-voiced
+//!     - Keep voiced kana(ガ, パ and so on) as they are by half-width conversions if specified
+//! - --middle-dot
+//!     - Fold middle dots(·, •, ･ and so on) into katakana middle dot(・) if specified
+//! - --keep-middle-dot
+//!     - Keep katakana middle dots(・ and ･) as they are if specified
+//! - --strip-middle-dot
+//!     - Remove katakana middle dots(・ and ･) if specified
+//! - --wave-dash
+//!     - Fold wave dash(〜) into full-width tilde(～) if specified
+//! - --fallback
+//!     - Specified how hiragana and katakana without mapping(ヷ, ゟ and so on) are handled
+//!     - `keep`(default), `drop`, `error` or a replacement character
+//!     - e.g. `--fallback ?`
 //! - -i, --ignore
 //!     - Specified ignore characters
 //!     - e.g. `-i A1ｱ`
+//! - --ignore-range
+//!     - Specified an inclusive range of ignore characters, which can be repeated
+//!     - e.g. `--ignore-range ァ-ヶ`
+//! - -o, --only
+//!     - Specified characters which are only converted
+//!     - e.g. `-o ０１２`
+//! - -s, --preserve-space
+//!     - Keep ascii space and ideographic space if specified
+//! - --compose-mark
+//!     - Compose kana and a following voiced consonant mark(゛, ゜ or combining ones) if specified
+//! - --long-vowel
+//!     - Replace prolonged sound marks with vowels if specified(h2k and k2h only)
+//! - -f, --file
+//!     - Convert contents of the file instead of the text argument
+//!     - e.g. `-f input.txt`
+//! - --json
+//!     - Print input, output, conversion and the number of converted characters as JSON
+//!     - e.g. `{"conversion":"z2h","converted_chars":3,"input":"ＡＢＣ","output":"ABC"}`
 //!
 extern crate clap;
 extern crate kelp;
+extern crate serde_json;
+
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process;
 
 use clap::Parser;
-use kelp::h2z;
-use kelp::hira2hkata;
-use kelp::hira2kata;
-use kelp::kata2hira;
-use kelp::z2h;
 use kelp::ConvOption;
+use kelp::Conversion;
+use kelp::Fallback;
+use kelp::MiddleDotPolicy;
+use kelp::RomanizationSystem;
+use kelp::Target;
+use kelp::VoicedKanaPolicy;
 
 /// A conversion tool of Japanese
 #[derive(Debug, Parser)]
@@ -65,7 +145,7 @@ use kelp::ConvOption;
 struct Args {
     /// Specified conversion pattern
     #[arg(short, long)]
-    conv: String,
+    conv: Conversion,
 
     /// Convert with ascii if specified
     #[arg(short, long)]
@@ -79,13 +159,182 @@ struct Args {
     #[arg(short, long)]
     kana: bool,
 
+    /// Convert small katakana of Ainu as large ones if specified
+    #[arg(long)]
+    ainu_kana: bool,
+
+    /// Convert katakana without half-width form to the nearest ones if specified
+    #[arg(long)]
+    kana_fallback: bool,
+
+    /// Convert with Japanese punctuation if specified
+    #[arg(long)]
+    kana_punct: bool,
+
+    /// Keep Japanese punctuation between hiragana and katakana if specified
+    #[arg(long)]
+    keep_kana_punct: bool,
+
+    /// Convert with prolonged sound mark if specified
+    #[arg(long)]
+    prolonged_mark: bool,
+
+    /// Keep prolonged sound mark even if kana is specified
+    #[arg(long)]
+    keep_prolonged_mark: bool,
+
+    /// Convert with latin letters if specified
+    #[arg(short, long)]
+    letter: bool,
+
+    /// Convert with ascii symbols if specified
+    #[arg(short, long)]
+    punct: bool,
+
+    /// Convert obsolete kana to modern ones if specified
+    #[arg(long)]
+    modern_kana: bool,
+
+    /// Convert roman numerals into ascii letters if specified
+    #[arg(long)]
+    numeral: bool,
+
+    /// Convert with small katakana if specified
+    #[arg(long)]
+    small_kana: bool,
+
+    /// Specified a group which is not converted even if enabled(e.g. letter)
+    #[arg(long, value_parser = parse_target)]
+    exclude: Vec<Target>,
+
+    /// Convert space and ideographic space even if ascii is not specified
+    #[arg(long)]
+    space: bool,
+
+    /// Expand squared characters into their spelled-out forms if specified
+    #[arg(long)]
+    square: bool,
+
+    /// Convert voiced kana to half-width katakana without voiced marks if specified
+    #[arg(long, conflicts_with = "keep_voiced")]
+    voiced_base: bool,
+
+    /// Keep voiced kana as they are by half-width conversions if specified
+    #[arg(long)]
+    keep_voiced: bool,
+
+    /// Fold middle dots into katakana middle dot if specified
+    #[arg(long)]
+    middle_dot: bool,
+
+    /// Keep katakana middle dots as they are if specified
+    #[arg(long, conflicts_with = "strip_middle_dot")]
+    keep_middle_dot: bool,
+
+    /// Remove katakana middle dots if specified
+    #[arg(long)]
+    strip_middle_dot: bool,
+
+    /// Fold wave dash into full-width tilde if specified
+    #[arg(long)]
+    wave_dash: bool,
+
+    /// Convert with currency symbols if specified
+    #[arg(long)]
+    currency: bool,
+
+    /// Convert enclosed numbers into digits if specified
+    #[arg(short, long)]
+    enclosed: bool,
+
+    /// Convert with symbols which have another width form if specified
+    #[arg(short, long)]
+    wide_symbol: bool,
+
+    /// Keep ascii space and ideographic space if specified
+    #[arg(short = 's', long)]
+    preserve_space: bool,
+
+    /// Compose kana and a following voiced consonant mark if specified
+    #[arg(long)]
+    compose_mark: bool,
+
+    /// Replace prolonged sound marks with vowels if specified
+    #[arg(long)]
+    long_vowel: bool,
+
+    /// Specified how kana without mapping are handled(keep, drop, error or a character)
+    #[arg(long, value_parser = parse_fallback, default_value = "keep")]
+    fallback: Fallback,
+
     /// Specified ignore charcters
     #[arg(short, long)]
     ignore: Option<String>,
 
+    /// Specified an inclusive range of ignore characters(e.g. ァ-ヶ)
+    #[arg(long, value_parser = parse_range)]
+    ignore_range: Vec<RangeInclusive<char>>,
+
+    /// Specified characters which are only converted
+    #[arg(short, long)]
+    only: Option<String>,
+
+    /// Convert contents of the file
+    #[arg(short, long, conflicts_with = "text")]
+    file: Option<PathBuf>,
+
+    /// Print the result as JSON
+    #[arg(long)]
+    json: bool,
+
     text: Option<String>,
 }
 
+/// Parse an inclusive range of characters like `ァ-ヶ`
+fn parse_range(s: &str) -> Result<RangeInclusive<char>, String> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next(), chars.next(), chars.next()) {
+        (Some(start), Some('-'), Some(end), None) if start <= end => Ok(start..=end),
+        _ => Err(format!("invalid range `{}`, expected like `ァ-ヶ`", s)),
+    }
+}
+
+/// Parse a name of character groups like `small-kana`
+fn parse_target(s: &str) -> Result<Target, String> {
+    match s {
+        "ascii" => Ok(Target::ASCII),
+        "digit" => Ok(Target::DIGIT),
+        "kana" => Ok(Target::KANA),
+        "punct" => Ok(Target::PUNCT),
+        "letter" => Ok(Target::LETTER),
+        "symbol" => Ok(Target::SYMBOL),
+        "kana-punct" => Ok(Target::KANA_PUNCT),
+        "enclosed" => Ok(Target::ENCLOSED),
+        "square" => Ok(Target::SQUARE),
+        "numeral" => Ok(Target::NUMERAL),
+        "currency" => Ok(Target::CURRENCY),
+        "prolonged-mark" => Ok(Target::PROLONGED_MARK),
+        "space" => Ok(Target::SPACE),
+        "small-kana" => Ok(Target::SMALL_KANA),
+        _ => Err(format!("unknown group `{}`", s)),
+    }
+}
+
+/// Parse a fallback policy like `drop` or a replacement character
+fn parse_fallback(s: &str) -> Result<Fallback, String> {
+    let mut chars = s.chars();
+    match (s, chars.next(), chars.next()) {
+        ("keep", _, _) => Ok(Fallback::Keep),
+        ("drop", _, _) => Ok(Fallback::Drop),
+        ("error", _, _) => Ok(Fallback::Error),
+        (_, Some(c), None) => Ok(Fallback::Replace(c)),
+        _ => Err(format!(
+            "invalid fallback `{}`, expected keep, drop, error or a character",
+            s
+        )),
+    }
+}
+
 fn main() {
     let args = Args::parse();
     let ignore: &str = match args.ignore.as_deref() {
@@ -93,21 +342,90 @@ fn main() {
         Some(s) => Box::leak(s.to_string().into_boxed_str()),
     };
     let option = ConvOption {
+        ainu_kana: args.ainu_kana,
         ascii: args.ascii,
+        compose_mark: args.compose_mark,
+        currency: args.currency,
         digit: args.digit,
-        ignore: ignore,
+        enclosed: args.enclosed,
+        fallback: args.fallback,
+        exclude: args
+            .exclude
+            .iter()
+            .fold(Target::NONE, |acc, target| acc | *target)
+            | if args.keep_prolonged_mark {
+                Target::PROLONGED_MARK
+            } else {
+                Target::NONE
+            },
+        ignore,
+        ignore_chars: &[],
+        ignore_if: None,
+        #[cfg(feature = "regex")]
+        ignore_pattern: None,
+        ignore_ranges: &args.ignore_range,
         kana: args.kana,
+        kana_fallback: args.kana_fallback,
+        kana_punct: args.kana_punct,
+        keep_kana_punct: args.keep_kana_punct,
+        large_kana: false,
+        letter: args.letter,
+        long_vowel: args.long_vowel,
+        middle_dot: args.middle_dot,
+        middle_dot_policy: if args.keep_middle_dot {
+            MiddleDotPolicy::Keep
+        } else if args.strip_middle_dot {
+            MiddleDotPolicy::Strip
+        } else {
+            MiddleDotPolicy::Convert
+        },
+        modern_kana: args.modern_kana,
+        numeral: args.numeral,
+        only: args.only.as_deref().unwrap_or(""),
+        preserve_space: args.preserve_space,
+        prolonged_mark: args.prolonged_mark,
+        punct: args.punct,
+        roman: RomanizationSystem::Hepburn,
+        small_kana: args.small_kana,
+        space: args.space,
+        square: args.square,
+        voiced_kana: if args.voiced_base {
+            VoicedKanaPolicy::Base
+        } else if args.keep_voiced {
+            VoicedKanaPolicy::Keep
+        } else {
+            VoicedKanaPolicy::Split
+        },
+        wave_dash: args.wave_dash,
+        wide_symbol: args.wide_symbol,
     };
-    let text = args.text.as_deref().unwrap_or("");
-
-    let converted = match args.conv.as_str() {
-        "h2z" => h2z(text, option),
-        "h2hk" => hira2hkata(text, option),
-        "h2k" => hira2kata(text, option),
-        "k2h" => kata2hira(text, option),
-        "z2h" => z2h(text, option),
-        _ => text.to_string(),
+
+    let text = match args.file {
+        Some(ref path) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("error: failed to read '{}': {}", path.display(), e);
+            process::exit(1);
+        }),
+        None => args.text.clone().unwrap_or_default(),
     };
+    let result = args
+        .conv
+        .try_apply_counted(&text, option)
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
 
-    println!("{}", converted);
+    if args.json {
+        let json = serde_json::json!({
+            "input": text,
+            "output": result.output,
+            "conversion": args.conv.name(),
+            "converted_chars": result.converted,
+        });
+        println!("{}", json);
+    } else if args.file.is_some() {
+        print!("{}", result.output);
+    } else {
+        println!("{}", result.output);
+    }
 }
@@ -0,0 +1,1508 @@
+//! Options of converting
+//!
+//! `ConvOption` is the one struct which has flags of all conversions. It is re-exported at
+//! the crate root, so `kelp::ConvOption` and `kelp::conv_option::ConvOption` are the same
+//! type, and an option built with `ConvOptionBuilder` equals one written as a struct literal.
+//!
+//! `WidthOption`(for `h2z` and `z2h`) and `KanaOption`(for conversions between hiragana and
+//! katakana) are typed wrappers of `ConvOption`, which only have flags used by their
+//! conversions and convert into `ConvOption` with `From`.
+//!
+//! ```rust
+//! use kelp::conv_option::ConvOptionBuilder;
+//!
+//! let built: kelp::ConvOption = ConvOptionBuilder::new().enable_kana().finalize();
+//! let literal = kelp::conv_option::ConvOption {
+//!     kana: true,
+//!     ..Default::default()
+//! };
+//! assert_eq!(built, literal);
+//! ```
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::ops::{Range, RangeInclusive};
+
+use crate::{Conversion, RomanizationSystem, Target};
+
+/// A set of characters
+///
+/// This is a `BTreeSet`, so an empty set can be created in const contexts.
+pub type CharSet = BTreeSet<char>;
+
+/// Convert options
+///
+/// An option can be built with a struct literal or `ConvOptionBuilder`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::conv_option::ConvOption;
+///
+/// let option = ConvOption::build().enable_ascii().ignore("Ａ").finalize();
+/// let literal = ConvOption {
+///     ascii: true,
+///     ignore: "Ａ",
+///     ..Default::default()
+/// };
+/// assert_eq!(option, literal);
+/// ```
+///
+/// `ConvOption::default()` disables all flags.
+///
+/// `ConvOption` has flags of all conversions. `WidthOption` and `KanaOption` only have
+/// flags used by `h2z`/`z2h` and conversions between hiragana and katakana respectively,
+/// and are recommended for new code: each of them is rejected by the other kind of
+/// conversions. `ConvOption` is still accepted by all conversions for compatibility.
+///
+/// ```rust
+/// use kelp::{hira2kata, ConvOption};
+///
+/// assert_eq!("アイウ", hira2kata("あいう", ConvOption::default()));
+/// ```
+///
+/// # Spaces
+///
+/// Ascii space and ideographic space(U+3000) are converted by `h2z`/`z2h` if either `ascii`
+/// or `space` is enabled, unless `preserve_space` is enabled. `preserve_space` takes
+/// precedence over both, so it works as a `convert_space: false` flag for `ascii`, and enabling
+/// it together with `space` is rejected by `validate`.
+///
+/// ```rust
+/// use kelp::{h2z, ConvOption};
+///
+/// let ascii = ConvOption {
+///     ascii: true,
+///     ..Default::default()
+/// };
+/// assert_eq!("Ａ　Ｂ", h2z("A B", &ascii));
+/// let preserved = ConvOption {
+///     preserve_space: true,
+///     ..ascii
+/// };
+/// assert_eq!("Ａ Ｂ", h2z("A B", &preserved));
+/// ```
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct ConvOption<'a> {
+    /// Convert small katakana of Ainu(`ㇰ` to `ㇿ`), which have neither hiragana nor
+    /// half-width forms, as their large katakana(e.g. `ㇰ` to `く` by `kata2hira`
+    /// and `ｸ` by `z2h` with `kana`)
+    pub ainu_kana: bool,
+    /// Convert ascii, which is the same as enabling both `letter` and `punct`
+    pub ascii: bool,
+    /// Compose kana and a following voiced consonant mark(゛ or ゜) before conversion
+    ///
+    /// Combining voiced consonant marks(U+3099 and U+309A) of decomposed(NFD) strings
+    /// are composed too.
+    pub compose_mark: bool,
+    /// Convert currency symbols(`＄`, `￠`, `￡`, `￥` and `￦`)
+    pub currency: bool,
+    /// Convert digits
+    pub digit: bool,
+    /// Convert enclosed numbers(e.g. `①`, `⑴`, `⒈`, `㊿`) into digits, only by `z2h`
+    pub enclosed: bool,
+    /// Character groups which are not converted by any conversion, even if other flags
+    /// enable them(e.g. `Target::LETTER` with `ascii` converts only symbols and spaces)
+    pub exclude: Target,
+    /// How hiragana and katakana which have no mapping are handled
+    pub fallback: Fallback,
+    /// Characters which are not converted
+    pub ignore: &'a str,
+    /// Characters which are not converted, in addition to `ignore`
+    pub ignore_chars: &'a [char],
+    /// A predicate of characters which are not converted
+    pub ignore_if: Option<CharPredicate<'a>>,
+    /// A pattern of regions which are not converted
+    ///
+    /// This is used by conversions of `Conversion` and functions built on them,
+    /// not by ones which check characters one by one(e.g. `kana2roman`).
+    #[cfg(feature = "regex")]
+    pub ignore_pattern: Option<Pattern<'a>>,
+    /// Ranges of characters which are not converted
+    pub ignore_ranges: &'a [RangeInclusive<char>],
+    /// Convert katakana
+    pub kana: bool,
+    /// Convert katakana which have no half-width form to the nearest half-width katakana
+    /// (`ヮ` to `ﾜ`, `ヰ` to `ｲ`, `ヱ` to `ｴ`, `ヵ` to `ｶ` and `ヶ` to `ｹ`) by `z2h` and `hira2hkata`
+    pub kana_fallback: bool,
+    /// Convert Japanese punctuation(`「」。、・` and `｢｣｡､･`) even if `kana` is disabled
+    pub kana_punct: bool,
+    /// Keep Japanese punctuation(`「」。、・`) as they are in `hira2kata`, `hira2hkata`
+    /// and `kata2hira`
+    pub keep_kana_punct: bool,
+    /// Convert small kana into large kana by `normalize`
+    pub large_kana: bool,
+    /// Convert latin letters(`A-Za-z`) even if `ascii` is disabled
+    pub letter: bool,
+    /// Fold middle dots(`·`, `•`, `‧`, `∙`, `⋅` and `･`) into katakana middle dot(`・`),
+    /// which is converted to `･` by `z2h` with `kana`
+    pub middle_dot: bool,
+    /// How katakana middle dots(`・` and `･`) are handled
+    pub middle_dot_policy: MiddleDotPolicy,
+    /// Convert roman numerals(e.g. `Ⅻ`, `ⅻ`) into ascii letters, only by `z2h`
+    pub numeral: bool,
+    /// Convert obsolete kana(`ゐ`, `ゑ`, `ヰ` and `ヱ`) to modern ones(`い`, `え`, `イ` and `エ`)
+    /// by conversions between hiragana and katakana, and `normalize`
+    pub modern_kana: bool,
+    /// Characters which are only converted if not empty
+    ///
+    /// Characters in `ignore`, `ignore_chars` or `ignore_ranges`, or satisfying `ignore_if`
+    /// are not converted even if they are in `only`.
+    pub only: &'a str,
+    /// Replace prolonged sound marks(ー) with vowels in `hira2kata` and `kata2hira`
+    pub long_vowel: bool,
+    /// Convert prolonged sound mark(`ー` and `ｰ`) even if `kana` is disabled
+    pub prolonged_mark: bool,
+    /// Keep ascii space and ideographic space(U+3000) as they are
+    ///
+    /// See [Spaces](#spaces) for precedence over `ascii` and `space`.
+    pub preserve_space: bool,
+    /// Convert ascii symbols(`` !"#$%&'()*+,-./:;<=>?@[\]^_`{|}~ ``) even if `ascii` is disabled
+    pub punct: bool,
+    /// Romanization system of `kana2roman`
+    pub roman: RomanizationSystem,
+    /// Convert small katakana(`ァィゥェォッャュョ` and `ｧｨｩｪｫｯｬｭｮ`) even if `kana` is disabled
+    pub small_kana: bool,
+    /// Convert space and ideographic space(U+3000) even if `ascii` is disabled
+    ///
+    /// See [Spaces](#spaces) for precedence over `ascii` and `preserve_space`.
+    pub space: bool,
+    /// Expand squared characters(e.g. `㌔`, `㎝`) into their spelled-out forms, only by `z2h`
+    pub square: bool,
+    /// How voiced katakana(e.g. `ガ`, `パ`) are converted by `z2h` and `hira2hkata`
+    pub voiced_kana: VoicedKanaPolicy,
+    /// Fold wave dash(`〜`, U+301C) into full-width tilde(`～`, U+FF5E),
+    /// which is converted to `~` by `z2h` with `ascii`
+    pub wave_dash: bool,
+    /// Convert symbols which have another width form(e.g. `￥`, `←`, and `−`(U+2212) to `-`)
+    pub wide_symbol: bool,
+}
+
+/// A predicate of characters
+///
+/// Two predicates are equal if they refer the same function of the same type. Predicates
+/// which behave the same but are different closures are not equal.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{z2h, CharPredicate, ConvOption};
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     ignore_if: Some(CharPredicate(&|c| !c.is_alphanumeric())),
+///     ..Default::default()
+/// };
+/// assert_eq!("ABC！？", z2h("ＡＢＣ！？", option));
+/// ```
+#[derive(Clone, Copy)]
+pub struct CharPredicate<'a>(pub &'a (dyn Fn(char) -> bool + Sync));
+
+impl CharPredicate<'_> {
+    /// Whether the character satisfies this predicate
+    pub fn test(&self, c: char) -> bool {
+        (self.0)(c)
+    }
+}
+
+impl fmt::Debug for CharPredicate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CharPredicate(..)")
+    }
+}
+
+impl PartialEq for CharPredicate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        // Closures without captures are zero-sized and may share an address,
+        // so vtables are compared too
+        core::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for CharPredicate<'_> {}
+
+/// Policies of voiced kana which are converted to half-width katakana
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum VoicedKanaPolicy {
+    /// Convert to half-width katakana and a voiced consonant mark(e.g. `ガ` to `ｶﾞ`)
+    #[default]
+    Split,
+    /// Convert to half-width katakana without a voiced consonant mark(e.g. `ガ` to `ｶ`)
+    Base,
+    /// Keep voiced kana as they are
+    Keep,
+}
+
+/// Policies of hiragana and katakana which have no mapping by a conversion
+///
+/// Characters which are checked are hiragana letters by `hira2kata` and `hira2hkata`,
+/// and katakana letters by `kata2hira` and `z2h` with `kana`(e.g. `ヷ`, `ゟ`).
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum Fallback {
+    /// Keep them as they are
+    #[default]
+    Keep,
+    /// Replace them with the character(e.g. `'\u{fffd}'`)
+    Replace(char),
+    /// Remove them
+    Drop,
+    /// Fail `try_` functions(e.g. `try_kata2hira`, `Conversion::try_apply`), and keep them
+    /// as they are by the other functions
+    Error,
+}
+
+/// A regular expression which compares equal to one of the same source
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{z2h, ConvOption, Pattern};
+/// use regex::Regex;
+///
+/// let url = Regex::new(r"ｈｔｔｐｓ?://\S+").unwrap();
+/// let option = ConvOption {
+///     ascii: true,
+///     ignore_pattern: Some(Pattern(&url)),
+///     ..Default::default()
+/// };
+/// assert_eq!("ABC ｈｔｔｐ://ｅｘ", z2h("ＡＢＣ ｈｔｔｐ://ｅｘ", option));
+/// ```
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, Copy)]
+pub struct Pattern<'a>(pub &'a regex::Regex);
+
+#[cfg(feature = "regex")]
+impl PartialEq for Pattern<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Eq for Pattern<'_> {}
+
+/// Policies of katakana middle dots(`・` and `･`)
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum MiddleDotPolicy {
+    /// Convert between `・` and `･` with `kana` or `kana_punct`
+    #[default]
+    Convert,
+    /// Keep `・` and `･` as they are, even if `middle_dot` is enabled
+    Keep,
+    /// Remove `・` and `･`, and other middle dots if `middle_dot` is enabled
+    Strip,
+}
+
+impl<'a> ConvOption<'a> {
+    /// Returns a builder of `ConvOption`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOption;
+    ///
+    /// let option = ConvOption::build().finalize();
+    /// assert_eq!(option, ConvOption::default());
+    /// ```
+    pub fn build() -> ConvOptionBuilder<'a> {
+        ConvOptionBuilder::new()
+    }
+
+    /// Returns an option which disables all flags
+    ///
+    /// This is the same as `ConvOption::default()`, and can be used in const contexts
+    /// like the other presets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::{z2h, ConvOption};
+    ///
+    /// static OPTION: ConvOption = ConvOption::kana_only();
+    /// const NONE: ConvOption = ConvOption::new();
+    ///
+    /// assert_eq!("ＡＢｱｲ", z2h("ＡＢアイ", &OPTION));
+    /// assert_eq!(NONE, ConvOption::default());
+    /// ```
+    pub const fn new() -> ConvOption<'a> {
+        ConvOption {
+            ainu_kana: false,
+            ascii: false,
+            compose_mark: false,
+            currency: false,
+            digit: false,
+            enclosed: false,
+            exclude: Target::NONE,
+            fallback: Fallback::Keep,
+            ignore: "",
+            ignore_chars: &[],
+            ignore_if: None,
+            #[cfg(feature = "regex")]
+            ignore_pattern: None,
+            ignore_ranges: &[],
+            kana: false,
+            kana_fallback: false,
+            kana_punct: false,
+            keep_kana_punct: false,
+            large_kana: false,
+            letter: false,
+            long_vowel: false,
+            middle_dot: false,
+            middle_dot_policy: MiddleDotPolicy::Convert,
+            modern_kana: false,
+            numeral: false,
+            only: "",
+            preserve_space: false,
+            prolonged_mark: false,
+            punct: false,
+            roman: RomanizationSystem::Hepburn,
+            small_kana: false,
+            space: false,
+            square: false,
+            voiced_kana: VoicedKanaPolicy::Split,
+            wave_dash: false,
+            wide_symbol: false,
+        }
+    }
+
+    /// Returns an option which enables all of ascii, digit and kana
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::ConvOption;
+    /// use kelp::h2z;
+    ///
+    /// let converted = h2z("ABCｱ012", ConvOption::all());
+    /// assert_eq!("ＡＢＣア０１２", converted);
+    /// ```
+    pub const fn all() -> ConvOption<'a> {
+        let mut option = ConvOption::new();
+        option.ascii = true;
+        option.digit = true;
+        option.kana = true;
+        option
+    }
+
+    /// Returns an option which enables only kana
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::ConvOption;
+    /// use kelp::z2h;
+    ///
+    /// let converted = z2h("ＡＢＣアイウ０１２", ConvOption::kana_only());
+    /// assert_eq!("ＡＢＣｱｲｳ０１２", converted);
+    /// ```
+    pub const fn kana_only() -> ConvOption<'a> {
+        let mut option = ConvOption::new();
+        option.kana = true;
+        option
+    }
+
+    /// Returns an option which enables only ascii
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::ConvOption;
+    /// use kelp::z2h;
+    ///
+    /// let converted = z2h("ＡＢＣ！アイウ０１２", ConvOption::ascii_only());
+    /// assert_eq!("ABC!アイウ０１２", converted);
+    /// ```
+    pub const fn ascii_only() -> ConvOption<'a> {
+        let mut option = ConvOption::new();
+        option.ascii = true;
+        option
+    }
+
+    /// Returns an option which enables ascii and digit
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::ConvOption;
+    /// use kelp::z2h;
+    ///
+    /// let converted = z2h("ＡＢＣアイウ０１２", ConvOption::ascii_digits());
+    /// assert_eq!("ABCアイウ012", converted);
+    /// ```
+    pub const fn ascii_digits() -> ConvOption<'a> {
+        let mut option = ConvOption::new();
+        option.ascii = true;
+        option.digit = true;
+        option
+    }
+
+    /// Returns an option which enables letters and digits, but keeps symbols and spaces
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::ConvOption;
+    /// use kelp::z2h;
+    ///
+    /// let converted = z2h("ＡＢＣ（１２３）　アイウ", ConvOption::alphanumeric());
+    /// assert_eq!("ABC（123）　アイウ", converted);
+    /// ```
+    pub const fn alphanumeric() -> ConvOption<'a> {
+        let mut option = ConvOption::new();
+        option.letter = true;
+        option.digit = true;
+        option
+    }
+
+    /// Returns an option with no flags set
+    ///
+    /// This is the same as `ConvOption::default()`, so conversion functions still convert kana
+    /// because no target falls back to `kana`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::ConvOption;
+    /// use kelp::z2h;
+    ///
+    /// assert_eq!(ConvOption::none(), ConvOption::default());
+    /// assert_eq!("ｱｲＡ", z2h("アイＡ", ConvOption::none()));
+    /// ```
+    pub const fn none() -> ConvOption<'a> {
+        ConvOption::new()
+    }
+
+    /// Whether the character is in `ignore`, `ignore_chars` or `ignore_ranges`,
+    /// satisfies `ignore_if`, or not in non-empty `only`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::ConvOption;
+    ///
+    /// let option = ConvOption::build().ignore("あ").ignore_ranges(&['ア'..='オ']).finalize();
+    /// assert!(option.is_ignored('あ'));
+    /// assert!(option.is_ignored('イ'));
+    /// assert!(!option.is_ignored('カ'));
+    ///
+    /// let option = ConvOption::build().only("カキ").ignore("キ").finalize();
+    /// assert!(!option.is_ignored('カ'));
+    /// assert!(option.is_ignored('キ'));
+    /// assert!(option.is_ignored('ク'));
+    /// ```
+    pub fn is_ignored(&self, c: char) -> bool {
+        self.ignore.contains(c)
+            || self.ignore_chars.contains(&c)
+            || self.is_ignored_by_rules(c)
+            || (!self.only.is_empty() && !self.only.contains(c))
+    }
+
+    /// Characters of `ignore`, `ignore_chars` and `only` as sets, which are built once
+    /// to check many characters with `is_ignored_in`
+    pub(crate) fn ignore_set(&self) -> IgnoreSet {
+        IgnoreSet {
+            ignore: self
+                .ignore
+                .chars()
+                .chain(self.ignore_chars.iter().copied())
+                .collect(),
+            only: self.only.chars().collect(),
+        }
+    }
+
+    /// Same as `is_ignored`, with `ignore_set` built from this option
+    pub(crate) fn is_ignored_in(&self, set: &IgnoreSet, c: char) -> bool {
+        set.ignore.contains(&c)
+            || self.is_ignored_by_rules(c)
+            || (!set.only.is_empty() && !set.only.contains(&c))
+    }
+
+    /// Whether the character is ignored by `ignore_if` or `ignore_ranges`
+    fn is_ignored_by_rules(&self, c: char) -> bool {
+        self.ignore_if.is_some_and(|p| p.test(c))
+            || self.ignore_ranges.iter().any(|r| r.contains(&c))
+    }
+
+    /// Character groups which are converted between full-width and half-width
+    ///
+    /// Katakana is converted when no groups are enabled.
+    pub fn targets(&self) -> Target {
+        Target::from(self)
+    }
+
+    /// Check whether settings of this option make sense
+    ///
+    /// This returns the first problem of the following.
+    ///
+    /// - An ignore range whose start is greater than its end, which ignores nothing.
+    /// - Flags which cancel each other(`space` and `preserve_space`).
+    /// - An ignore character in `ignore` or `ignore_chars` which no conversion with this
+    ///   option converts, e.g. `Ａ` without `ascii` and `letter`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::{ConvOption, OptionError};
+    ///
+    /// let option = ConvOption::build().enable_kana().ignore("ア").finalize();
+    /// assert_eq!(Ok(()), option.validate());
+    ///
+    /// let option = ConvOption::build().enable_kana().ignore("Ａ").finalize();
+    /// assert_eq!(Err(OptionError::UnusedIgnore('Ａ')), option.validate());
+    /// ```
+    pub fn validate(&self) -> Result<(), OptionError> {
+        if let Some(range) = self.ignore_ranges.iter().find(|r| r.is_empty()) {
+            return Err(OptionError::EmptyIgnoreRange(range.clone()));
+        }
+        if self.space && self.preserve_space {
+            return Err(OptionError::ConflictingFlags("space", "preserve_space"));
+        }
+
+        let tables = Conversion::ALL
+            .iter()
+            .map(|c| c.table(self))
+            .collect::<Vec<_>>();
+        let is_used = |c: char| {
+            matches!(c, '゛' | '゜' | '\u{3099}' | '\u{309a}' | 'ﾞ' | 'ﾟ')
+                || (c == 'ー' && self.long_vowel)
+                || tables.iter().any(|t| t.contains_key(&(c as u32)))
+        };
+        match self
+            .ignore
+            .chars()
+            .chain(self.ignore_chars.iter().copied())
+            .find(|c| !is_used(*c))
+        {
+            Some(c) => Err(OptionError::UnusedIgnore(c)),
+            None => Ok(()),
+        }
+    }
+
+    /// Byte ranges of `text` which match `ignore_pattern`
+    #[cfg(feature = "regex")]
+    pub(crate) fn ignored_regions(&self, text: &str) -> Vec<Range<usize>> {
+        match self.ignore_pattern {
+            Some(pattern) => pattern.0.find_iter(text).map(|m| m.range()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Byte ranges of `text` which match `ignore_pattern`
+    #[cfg(not(feature = "regex"))]
+    pub(crate) fn ignored_regions(&self, _text: &str) -> Vec<Range<usize>> {
+        Vec::new()
+    }
+}
+
+/// Sets of ignore characters of an option
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IgnoreSet {
+    /// Characters of `ignore` and `ignore_chars`
+    ignore: CharSet,
+    /// Characters of `only`
+    only: CharSet,
+}
+
+impl<'a> From<ConvOptionBuilder<'a>> for ConvOption<'a> {
+    fn from(builder: ConvOptionBuilder<'a>) -> ConvOption<'a> {
+        builder.finalize()
+    }
+}
+
+/// A problem of option settings which is returned by `ConvOption::validate`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OptionError {
+    /// An ignore character which no conversion converts
+    UnusedIgnore(char),
+    /// An ignore range which contains no characters
+    EmptyIgnoreRange(RangeInclusive<char>),
+    /// A pair of flags which cancel each other
+    ConflictingFlags(&'static str, &'static str),
+}
+
+impl fmt::Display for OptionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptionError::UnusedIgnore(c) => {
+                write!(
+                    f,
+                    "ignore character `{}` is not converted by any conversion",
+                    c
+                )
+            }
+            OptionError::EmptyIgnoreRange(r) => {
+                write!(f, "ignore range `{}..={}` is empty", r.start(), r.end())
+            }
+            OptionError::ConflictingFlags(a, b) => {
+                write!(f, "`{}` and `{}` cannot be enabled together", a, b)
+            }
+        }
+    }
+}
+
+impl Error for OptionError {}
+
+/// Builder of ConvOption
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConvOptionBuilder<'a> {
+    option: ConvOption<'a>,
+}
+
+impl<'a> ConvOptionBuilder<'a> {
+    /// Returns a `ConvOptionBuilder` with default options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().finalize();
+    /// assert!(!option.ascii);
+    /// assert!(!option.digit);
+    /// assert!(!option.kana);
+    /// assert_eq!("", option.ignore);
+    /// ```
+    pub const fn new() -> Self {
+        ConvOptionBuilder {
+            option: ConvOption::new(),
+        }
+    }
+
+    /// Set a flag of ascii.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().ascii(true).finalize();
+    /// assert!(option.ascii);
+    /// ```
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.option.ascii = ascii;
+        self
+    }
+
+    /// Set a flag of digit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().digit(true).finalize();
+    /// assert!(option.digit);
+    /// ```
+    pub fn digit(mut self, digit: bool) -> Self {
+        self.option.digit = digit;
+        self
+    }
+
+    /// Set a flag of kana.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().kana(true).finalize();
+    /// assert!(option.kana);
+    /// ```
+    pub fn kana(mut self, kana: bool) -> Self {
+        self.option.kana = kana;
+        self
+    }
+
+    /// Set a flag of Japanese punctuation.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().kana_punct(true).finalize();
+    /// assert!(option.kana_punct);
+    /// ```
+    pub fn kana_punct(mut self, kana_punct: bool) -> Self {
+        self.option.kana_punct = kana_punct;
+        self
+    }
+
+    /// Set a flag of latin letters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().letter(true).finalize();
+    /// assert!(option.letter);
+    /// ```
+    ///
+    /// Letters can be converted while symbols keep their width.
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::z2h;
+    ///
+    /// let option = ConvOptionBuilder::new().letter(true).enable_digit().finalize();
+    /// assert_eq!("ABC123（！）", z2h("ＡＢＣ１２３（！）", option));
+    /// ```
+    pub fn letter(mut self, letter: bool) -> Self {
+        self.option.letter = letter;
+        self
+    }
+
+    /// Set a flag of ascii symbols.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().punct(true).finalize();
+    /// assert!(option.punct);
+    /// ```
+    pub fn punct(mut self, punct: bool) -> Self {
+        self.option.punct = punct;
+        self
+    }
+
+    /// Set whether enclosed numbers are converted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().enclosed(true).finalize();
+    /// assert!(option.enclosed);
+    /// ```
+    pub fn enclosed(mut self, enclosed: bool) -> Self {
+        self.option.enclosed = enclosed;
+        self
+    }
+
+    /// Set whether squared characters are expanded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().square(true).finalize();
+    /// assert!(option.square);
+    /// ```
+    pub fn square(mut self, square: bool) -> Self {
+        self.option.square = square;
+        self
+    }
+
+    /// Set whether roman numerals are converted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().numeral(true).finalize();
+    /// assert!(option.numeral);
+    /// ```
+    pub fn numeral(mut self, numeral: bool) -> Self {
+        self.option.numeral = numeral;
+        self
+    }
+
+    /// Set whether currency symbols are converted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().currency(true).finalize();
+    /// assert!(option.currency);
+    /// ```
+    pub fn currency(mut self, currency: bool) -> Self {
+        self.option.currency = currency;
+        self
+    }
+
+    /// Set whether small kana are converted into large kana by `normalize`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().large_kana(true).finalize();
+    /// assert!(option.large_kana);
+    /// ```
+    pub fn large_kana(mut self, large_kana: bool) -> Self {
+        self.option.large_kana = large_kana;
+        self
+    }
+
+    /// Set whether obsolete kana are converted to modern ones.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().modern_kana(true).finalize();
+    /// assert!(option.modern_kana);
+    /// ```
+    pub fn modern_kana(mut self, modern_kana: bool) -> Self {
+        self.option.modern_kana = modern_kana;
+        self
+    }
+
+    /// Set a flag of prolonged sound mark.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::z2h;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .kana_punct(true)
+    ///     .prolonged_mark(true)
+    ///     .finalize();
+    /// assert_eq!("｢ア｣ｰ", z2h("「ア」ー", option));
+    /// ```
+    pub fn prolonged_mark(mut self, prolonged_mark: bool) -> Self {
+        self.option.prolonged_mark = prolonged_mark;
+        self
+    }
+
+    /// Set a flag of small katakana.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::z2h;
+    ///
+    /// let option = ConvOptionBuilder::new().small_kana(true).finalize();
+    /// assert_eq!("ｧアｯ", z2h("ァアッ", option));
+    /// ```
+    pub fn small_kana(mut self, small_kana: bool) -> Self {
+        self.option.small_kana = small_kana;
+        self
+    }
+
+    /// Set character groups which are not converted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::{kata2hira, z2h, Target};
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .enable_ascii()
+    ///     .enable_kana()
+    ///     .exclude(Target::LETTER | Target::SMALL_KANA)
+    ///     .finalize();
+    /// assert_eq!("ＡＢ(ｷャッﾄ)", z2h("ＡＢ（キャット）", &option));
+    /// assert_eq!("きャッと", kata2hira("キャット", &option));
+    /// ```
+    pub fn exclude(mut self, exclude: Target) -> Self {
+        self.option.exclude = exclude;
+        self
+    }
+
+    /// Set a flag of small katakana of Ainu.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::{kata2hira, z2h};
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .enable_kana()
+    ///     .ainu_kana(true)
+    ///     .finalize();
+    /// assert_eq!("ｱｲﾇ ｶﾑｲ ｸ", z2h("アイヌ カムイ ㇰ", &option));
+    /// assert_eq!("ぷく", kata2hira("プㇰ", &option));
+    /// ```
+    pub fn ainu_kana(mut self, ainu_kana: bool) -> Self {
+        self.option.ainu_kana = ainu_kana;
+        self
+    }
+
+    /// Set how hiragana and katakana which have no mapping are handled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::{kata2hira, Fallback};
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .fallback(Fallback::Replace('?'))
+    ///     .finalize();
+    /// assert_eq!("あ?い", kata2hira("アヷイ", option));
+    /// ```
+    pub fn fallback(mut self, fallback: Fallback) -> Self {
+        self.option.fallback = fallback;
+        self
+    }
+
+    /// Set a flag of approximate half-width katakana.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::z2h;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .enable_kana()
+    ///     .kana_fallback(true)
+    ///     .finalize();
+    /// assert_eq!("ｹｰｽ", z2h("ヶース", option));
+    /// ```
+    pub fn kana_fallback(mut self, kana_fallback: bool) -> Self {
+        self.option.kana_fallback = kana_fallback;
+        self
+    }
+
+    /// Set a flag of spaces.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::z2h;
+    ///
+    /// let option = ConvOptionBuilder::new().space(true).finalize();
+    /// assert_eq!("Ａ Ｂ", z2h("Ａ　Ｂ", option));
+    /// ```
+    pub fn space(mut self, space: bool) -> Self {
+        self.option.space = space;
+        self
+    }
+
+    /// Set a flag of keeping Japanese punctuation between hiragana and katakana.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::hira2hkata;
+    ///
+    /// let option = ConvOptionBuilder::new().keep_kana_punct(true).finalize();
+    /// assert_eq!("「ｱｲ」。", hira2hkata("「あい」。", option));
+    /// ```
+    pub fn keep_kana_punct(mut self, keep_kana_punct: bool) -> Self {
+        self.option.keep_kana_punct = keep_kana_punct;
+        self
+    }
+
+    /// Set how voiced katakana are converted to half-width katakana.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::{ConvOptionBuilder, VoicedKanaPolicy};
+    /// use kelp::hira2hkata;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .voiced_kana(VoicedKanaPolicy::Base)
+    ///     .finalize();
+    /// assert_eq!("ｶｷ", hira2hkata("がぎ", option));
+    /// ```
+    pub fn voiced_kana(mut self, policy: VoicedKanaPolicy) -> Self {
+        self.option.voiced_kana = policy;
+        self
+    }
+
+    /// Set a flag of symbols which have another width form.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().wide_symbol(true).finalize();
+    /// assert!(option.wide_symbol);
+    /// ```
+    pub fn wide_symbol(mut self, wide_symbol: bool) -> Self {
+        self.option.wide_symbol = wide_symbol;
+        self
+    }
+
+    /// Set a flag of folding wave dash into full-width tilde.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().wave_dash(true).finalize();
+    /// assert!(option.wave_dash);
+    /// ```
+    pub fn wave_dash(mut self, wave_dash: bool) -> Self {
+        self.option.wave_dash = wave_dash;
+        self
+    }
+
+    /// Set a flag of composing voiced consonant marks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().compose_mark(true).finalize();
+    /// assert!(option.compose_mark);
+    /// ```
+    pub fn compose_mark(mut self, compose_mark: bool) -> Self {
+        self.option.compose_mark = compose_mark;
+        self
+    }
+
+    /// Set a flag of replacing prolonged sound marks with vowels.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().long_vowel(true).finalize();
+    /// assert!(option.long_vowel);
+    /// ```
+    pub fn long_vowel(mut self, long_vowel: bool) -> Self {
+        self.option.long_vowel = long_vowel;
+        self
+    }
+
+    /// Set a flag of folding middle dots into katakana middle dot.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().middle_dot(true).finalize();
+    /// assert!(option.middle_dot);
+    /// ```
+    pub fn middle_dot(mut self, middle_dot: bool) -> Self {
+        self.option.middle_dot = middle_dot;
+        self
+    }
+
+    /// Set a policy of katakana middle dots.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::{ConvOptionBuilder, MiddleDotPolicy};
+    /// use kelp::z2h;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .enable_kana()
+    ///     .middle_dot_policy(MiddleDotPolicy::Keep)
+    ///     .finalize();
+    /// assert_eq!("ﾃﾞｰﾀ・ｾｯﾄ", z2h("データ・セット", option));
+    /// ```
+    pub fn middle_dot_policy(mut self, policy: MiddleDotPolicy) -> Self {
+        self.option.middle_dot_policy = policy;
+        self
+    }
+
+    /// Set a flag of keeping spaces.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().preserve_space(true).finalize();
+    /// assert!(option.preserve_space);
+    /// ```
+    pub fn preserve_space(mut self, preserve_space: bool) -> Self {
+        self.option.preserve_space = preserve_space;
+        self
+    }
+
+    /// Set a romanization system.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::{kana2roman, RomanizationSystem};
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .roman(RomanizationSystem::Kunrei)
+    ///     .finalize();
+    /// assert_eq!("sinbun", kana2roman("しんぶん", option));
+    /// ```
+    pub fn roman(mut self, roman: RomanizationSystem) -> Self {
+        self.option.roman = roman;
+        self
+    }
+
+    /// Set ignore characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new().ignore("あいう").finalize();
+    /// assert_eq!("あいう", option.ignore);
+    /// ```
+    pub fn ignore(mut self, ignore: &'a str) -> Self {
+        self.option.ignore = ignore;
+        self
+    }
+
+    /// Set ignore characters in addition to `ignore`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::hira2kata;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .ignore("さ")
+    ///     .ignore_chars(&['か', 'こ'])
+    ///     .finalize();
+    /// assert_eq!("かキクケこさシ", hira2kata("かきくけこさし", option));
+    /// ```
+    pub fn ignore_chars(mut self, chars: &'a [char]) -> Self {
+        self.option.ignore_chars = chars;
+        self
+    }
+
+    /// Set a predicate of ignore characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::h2z;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .enable_ascii()
+    ///     .ignore_if(&|c| c.is_ascii_punctuation())
+    ///     .finalize();
+    /// assert_eq!("ＡＢＣ!?", h2z("ABC!?", option));
+    /// ```
+    pub fn ignore_if(mut self, predicate: &'a (dyn Fn(char) -> bool + Sync)) -> Self {
+        self.option.ignore_if = Some(CharPredicate(predicate));
+        self
+    }
+
+    /// Set a pattern of ignore regions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::h2z;
+    /// use regex::Regex;
+    ///
+    /// let mail = Regex::new(r"\w+@\w+\.com").unwrap();
+    /// let option = ConvOptionBuilder::new()
+    ///     .enable_ascii()
+    ///     .ignore_pattern(&mail)
+    ///     .finalize();
+    /// assert_eq!("ｍａｉｌ：\u{3000}a@b.com", h2z("mail: a@b.com", option));
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn ignore_pattern(mut self, pattern: &'a regex::Regex) -> Self {
+        self.option.ignore_pattern = Some(Pattern(pattern));
+        self
+    }
+
+    /// Set inclusive ranges of ignore characters.
+    ///
+    /// Ranges may overlap.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::kata2hira;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .ignore_ranges(&['ァ'..='オ', 'ラ'..='ロ'])
+    ///     .finalize();
+    /// assert_eq!("アかリ", kata2hira("アカリ", option));
+    /// ```
+    pub fn ignore_ranges(mut self, ranges: &'a [RangeInclusive<char>]) -> Self {
+        self.option.ignore_ranges = ranges;
+        self
+    }
+
+    /// Set characters which are only converted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::z2h;
+    ///
+    /// let option = ConvOptionBuilder::new().enable_digit().only("０１２").finalize();
+    /// assert_eq!("012３４", z2h("０１２３４", option));
+    /// ```
+    pub fn only(mut self, only: &'a str) -> Self {
+        self.option.only = only;
+        self
+    }
+
+    /// Set flags of character groups which are converted between full-width and half-width.
+    ///
+    /// Flags of groups which are not in `targets` are disabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::Target;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .targets(Target::ASCII | Target::KANA)
+    ///     .finalize();
+    /// assert!(option.ascii && option.kana && !option.digit);
+    /// ```
+    pub fn targets(mut self, targets: Target) -> Self {
+        let option = &mut self.option;
+        option.ascii = targets.contains(Target::ASCII);
+        option.digit = targets.contains(Target::DIGIT);
+        option.kana = targets.contains(Target::KANA);
+        option.punct = targets.contains(Target::PUNCT);
+        option.letter = targets.contains(Target::LETTER);
+        option.wide_symbol = targets.contains(Target::SYMBOL);
+        option.kana_punct = targets.contains(Target::KANA_PUNCT);
+        option.enclosed = targets.contains(Target::ENCLOSED);
+        option.square = targets.contains(Target::SQUARE);
+        option.numeral = targets.contains(Target::NUMERAL);
+        option.currency = targets.contains(Target::CURRENCY);
+        option.prolonged_mark = targets.contains(Target::PROLONGED_MARK);
+        option.space = targets.contains(Target::SPACE);
+        option.small_kana = targets.contains(Target::SMALL_KANA);
+        self
+    }
+
+    /// Disabled convert with ascii.
+    pub fn disable_ascii(self) -> Self {
+        self.ascii(false)
+    }
+
+    /// Disabled convert with digit.
+    pub fn disable_digit(self) -> Self {
+        self.digit(false)
+    }
+
+    /// Disabled convert with kana.
+    pub fn disable_kana(self) -> Self {
+        self.kana(false)
+    }
+
+    /// Enabled convert with ascii.
+    pub fn enable_ascii(self) -> Self {
+        self.ascii(true)
+    }
+
+    /// Enabled convert with digit.
+    pub fn enable_digit(self) -> Self {
+        self.digit(true)
+    }
+
+    /// Enabled convert with kana.
+    pub fn enable_kana(self) -> Self {
+        self.kana(true)
+    }
+
+    /// Build a `ConvOption`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    ///
+    /// let option = ConvOptionBuilder::new()
+    ///     .ascii(true)
+    ///     .digit(true)
+    ///     .ignore("あいう")
+    ///     .kana(true)
+    ///     .finalize();
+    /// assert!(option.ascii);
+    /// assert!(option.digit);
+    /// assert!(option.kana);
+    /// assert_eq!("あいう", option.ignore);
+    /// ```
+    pub fn finalize(self) -> ConvOption<'a> {
+        self.option
+    }
+
+    /// Build a `ConvOption` and check its settings with `ConvOption::validate`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::conv_option::ConvOptionBuilder;
+    /// use kelp::OptionError;
+    ///
+    /// let result = ConvOptionBuilder::new().space(true).preserve_space(true).try_finalize();
+    /// assert_eq!(
+    ///     Err(OptionError::ConflictingFlags("space", "preserve_space")),
+    ///     result
+    /// );
+    /// ```
+    pub fn try_finalize(self) -> Result<ConvOption<'a>, OptionError> {
+        self.option.validate().map(|_| self.option)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(ConvOption::default(), ConvOption::build().finalize());
+        assert_eq!(
+            ConvOptionBuilder::default().finalize(),
+            ConvOptionBuilder::new().finalize()
+        );
+    }
+
+    #[test]
+    fn test_from_builder() {
+        let builder = ConvOption::build().enable_kana().ignore("ア");
+        assert_eq!(ConvOption::from(builder), builder.finalize());
+    }
+
+    #[test]
+    fn test_copy() {
+        use crate::{hira2hkata, hira2kata};
+
+        let ranges = ['う'..='え'];
+        let option = ConvOption::build()
+            .ignore_chars(&['あ'])
+            .ignore_ranges(&ranges)
+            .only("あいうお")
+            .finalize();
+        assert_eq!(hira2kata("あいうえお", option), "あイうえオ");
+        assert_eq!(hira2hkata("あいうえお", option), "あｲうえｵ");
+    }
+
+    #[test]
+    fn test_targets_round_trip() {
+        for bits in 1..=Target::ALL.bits() {
+            let targets = Target::from_bits_truncate(bits);
+            let option = ConvOption::build().targets(targets).finalize();
+            assert_eq!(option.targets(), targets);
+        }
+        assert_eq!(ConvOption::default().targets(), Target::KANA);
+        assert_eq!(Target::from_bits_truncate(u16::MAX), Target::ALL);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(ConvOption::default().validate(), Ok(()));
+        assert_eq!(ConvOption::all().validate(), Ok(()));
+
+        let option = ConvOption::build().ignore("あ").finalize();
+        assert_eq!(option.validate(), Ok(()));
+        let option = ConvOption::build().ignore("ﾞ").finalize();
+        assert_eq!(option.validate(), Ok(()));
+        let option = ConvOption::build().ignore_chars(&['漢']).finalize();
+        assert_eq!(option.validate(), Err(OptionError::UnusedIgnore('漢')));
+        let option = ConvOption::build().ignore_ranges(&['オ'..='ア']).finalize();
+        assert_eq!(
+            option.validate(),
+            Err(OptionError::EmptyIgnoreRange('オ'..='ア'))
+        );
+        assert_eq!(
+            OptionError::UnusedIgnore('漢').to_string(),
+            "ignore character `漢` is not converted by any conversion"
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        let kana = ConvOption::kana_only();
+        assert!(kana.kana && !kana.ascii && !kana.digit);
+        let ascii = ConvOption::ascii_only();
+        assert!(ascii.ascii && !ascii.digit && !ascii.kana);
+        let ascii_digits = ConvOption::ascii_digits();
+        assert!(ascii_digits.ascii && ascii_digits.digit && !ascii_digits.kana);
+        let alphanumeric = ConvOption::alphanumeric();
+        assert!(alphanumeric.letter && alphanumeric.digit && !alphanumeric.ascii);
+        assert_eq!(ConvOption::none(), ConvOption::default());
+    }
+
+    #[test]
+    fn test_ignore_if() {
+        let punct = |c: char| c.is_ascii_punctuation();
+        let option = ConvOption::build().ignore_if(&punct).finalize();
+        assert!(option.is_ignored('!'));
+        assert!(!option.is_ignored('a'));
+        assert_eq!(option, ConvOption::build().ignore_if(&punct).finalize());
+        assert_ne!(option, ConvOption::default());
+    }
+
+    #[test]
+    fn test_ignore_if_eq_different_closures() {
+        use crate::z2h;
+
+        let a = ConvOption {
+            ascii: true,
+            ignore_if: Some(CharPredicate(&|c| c == 'Ａ')),
+            ..Default::default()
+        };
+        let b = ConvOption {
+            ascii: true,
+            ignore_if: Some(CharPredicate(&|c| c == 'Ｂ')),
+            ..Default::default()
+        };
+        assert_ne!(z2h("ＡＢ", a), z2h("ＡＢ", b));
+        assert_ne!(a, b);
+        let copied = a;
+        assert_eq!(a, copied);
+    }
+
+    #[test]
+    fn test_ignore_set() {
+        let option = ConvOption::build()
+            .ignore("あい")
+            .ignore_chars(&['う'])
+            .ignore_ranges(&['ア'..='ウ'])
+            .only("あいうえアイウエカ")
+            .finalize();
+        let ignore = option.ignore_set();
+        for c in "あいうえおアイウエオカ".chars() {
+            assert_eq!(
+                option.is_ignored_in(&ignore, c),
+                option.is_ignored(c),
+                "{}",
+                c
+            );
+        }
+        assert!(!option.is_ignored('え') && !option.is_ignored('カ'));
+        assert!(option.is_ignored('お'));
+    }
+
+    #[test]
+    fn test_ignore_chars() {
+        let chars = ['あ', 'い', 'う'];
+        let option = ConvOption::build().ignore_chars(&chars).finalize();
+        assert_eq!(option.ignore_chars.len(), 3);
+        assert!(option.is_ignored('あ'));
+        assert!(option.is_ignored('う'));
+        assert!(!option.is_ignored('え'));
+    }
+}
@@ -1,10 +1,12 @@
 //! Tables for conversion
-use std::collections::HashMap;
-use std::vec::Vec;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use core::ops::{BitAnd, BitOr, BitOrAssign, RangeInclusive};
 
 use self::Method::*;
-use self::Target::*;
-use crate::ConvOption;
+use crate::{CharSet, ConvOption};
 
 /// ASCII(full-width)
 pub(crate) const FULL_ASCII: [&str; 85] = [
@@ -25,6 +27,33 @@ pub(crate) const HALF_ASCII: [&str; 85] = [
     "w", "x", "y", "z", "{", "|", "}", "~", " ",
 ];
 
+/// Ascii symbols(full-width)
+pub(crate) const FULL_PUNCT: [&str; 32] = [
+    "！", "＂", "＃", "＄", "％", "＆", "＇", "（", "）", "＊", "＋", "，", "－", "．", "／", "：",
+    "；", "＜", "＝", "＞", "？", "＠", "［", "＼", "］", "＾", "＿", "｀", "｛", "｜", "｝", "～",
+];
+
+/// Ascii symbols(half-width)
+pub(crate) const HALF_PUNCT: [&str; 32] = [
+    "!", "\"", "#", "$", "%", "&", "'", "(", ")", "*", "+", ",", "-", ".", "/", ":", ";", "<", "=",
+    ">", "?", "@", "[", "\\", "]", "^", "_", "`", "{", "|", "}", "~",
+];
+
+/// Latin letters(full-width)
+pub(crate) const FULL_ALPHA: [&str; 52] = [
+    "Ａ", "Ｂ", "Ｃ", "Ｄ", "Ｅ", "Ｆ", "Ｇ", "Ｈ", "Ｉ", "Ｊ", "Ｋ", "Ｌ", "Ｍ", "Ｎ", "Ｏ", "Ｐ",
+    "Ｑ", "Ｒ", "Ｓ", "Ｔ", "Ｕ", "Ｖ", "Ｗ", "Ｘ", "Ｙ", "Ｚ", "ａ", "ｂ", "ｃ", "ｄ", "ｅ", "ｆ",
+    "ｇ", "ｈ", "ｉ", "ｊ", "ｋ", "ｌ", "ｍ", "ｎ", "ｏ", "ｐ", "ｑ", "ｒ", "ｓ", "ｔ", "ｕ", "ｖ",
+    "ｗ", "ｘ", "ｙ", "ｚ",
+];
+
+/// Latin letters(half-width)
+pub(crate) const HALF_ALPHA: [&str; 52] = [
+    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+    "T", "U", "V", "W", "X", "Y", "Z", "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l",
+    "m", "n", "o", "p", "q", "r", "s", "t", "u", "v", "w", "x", "y", "z",
+];
+
 /// Digits(full-width)
 pub(crate) const FULL_DIGIT: [&str; 10] =
     ["０", "１", "２", "３", "４", "５", "６", "７", "８", "９"];
@@ -32,6 +61,314 @@ pub(crate) const FULL_DIGIT: [&str; 10] =
 /// Digits(half-width)
 pub(crate) const HALF_DIGIT: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
 
+/// Symbols which have a half-width form(full-width)
+///
+/// Ambiguous symbols like `×` or `※` are not included.
+pub(crate) const FULL_SYMBOL: [&str; 13] = [
+    "￠", "￡", "￢", "￣", "￤", "￥", "←", "↑", "→", "↓", "│", "■", "○",
+];
+
+/// Symbols which have a full-width form(half-width)
+pub(crate) const HALF_SYMBOL: [&str; 13] = [
+    "¢", "£", "¬", "¯", "¦", "¥", "￩", "￪", "￫", "￬", "￨", "￭", "￮",
+];
+
+/// Currency symbols(full-width)
+///
+/// `＄` is also ascii, and `￠`, `￡` and `￥` are also symbols.
+pub(crate) const FULL_CURRENCY: [&str; 5] = ["＄", "￠", "￡", "￥", "￦"];
+
+/// Currency symbols(half-width)
+pub(crate) const HALF_CURRENCY: [&str; 5] = ["$", "¢", "£", "¥", "₩"];
+
+/// Math minus sign(U+2212) which is converted only to half-width,
+/// because `-` is converted to `－`(U+FF0D) as ascii
+pub(crate) const FULL_MINUS: [&str; 1] = ["−"];
+
+/// Hyphen-minus of `FULL_MINUS`
+pub(crate) const HALF_MINUS: [&str; 1] = ["-"];
+
+/// Enclosed numbers(circled, parenthesized, with full stop and negative circled)
+/// which are converted only to half-width
+pub(crate) const FULL_ENCLOSED: [&str; 141] = [
+    "⓪", "①", "②", "③", "④", "⑤", "⑥", "⑦", "⑧", "⑨", "⑩", "⑪", "⑫", "⑬", "⑭", "⑮", "⑯", "⑰", "⑱",
+    "⑲", "⑳", "⑴", "⑵", "⑶", "⑷", "⑸", "⑹", "⑺", "⑻", "⑼", "⑽", "⑾", "⑿", "⒀", "⒁", "⒂", "⒃", "⒄",
+    "⒅", "⒆", "⒇", "⒈", "⒉", "⒊", "⒋", "⒌", "⒍", "⒎", "⒏", "⒐", "⒑", "⒒", "⒓", "⒔", "⒕", "⒖", "⒗",
+    "⒘", "⒙", "⒚", "⒛", "㉑", "㉒", "㉓", "㉔", "㉕", "㉖", "㉗", "㉘", "㉙", "㉚", "㉛", "㉜",
+    "㉝", "㉞", "㉟", "㊱", "㊲", "㊳", "㊴", "㊵", "㊶", "㊷", "㊸", "㊹", "㊺", "㊻", "㊼", "㊽",
+    "㊾", "㊿", "⓫", "⓬", "⓭", "⓮", "⓯", "⓰", "⓱", "⓲", "⓳", "⓴", "⓵", "⓶", "⓷", "⓸", "⓹", "⓺",
+    "⓻", "⓼", "⓽", "⓾", "❶", "❷", "❸", "❹", "❺", "❻", "❼", "❽", "❾", "❿", "➀", "➁", "➂", "➃", "➄",
+    "➅", "➆", "➇", "➈", "➉", "➊", "➋", "➌", "➍", "➎", "➏", "➐", "➑", "➒", "➓",
+];
+
+/// Digits of `FULL_ENCLOSED`
+pub(crate) const HALF_ENCLOSED: [&str; 141] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14", "15", "16",
+    "17", "18", "19", "20", "(1)", "(2)", "(3)", "(4)", "(5)", "(6)", "(7)", "(8)", "(9)", "(10)",
+    "(11)", "(12)", "(13)", "(14)", "(15)", "(16)", "(17)", "(18)", "(19)", "(20)", "1.", "2.",
+    "3.", "4.", "5.", "6.", "7.", "8.", "9.", "10.", "11.", "12.", "13.", "14.", "15.", "16.",
+    "17.", "18.", "19.", "20.", "21", "22", "23", "24", "25", "26", "27", "28", "29", "30", "31",
+    "32", "33", "34", "35", "36", "37", "38", "39", "40", "41", "42", "43", "44", "45", "46", "47",
+    "48", "49", "50", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "1", "2", "3",
+    "4", "5", "6", "7", "8", "9", "10", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "1",
+    "2", "3", "4", "5", "6", "7", "8", "9", "10", "1", "2", "3", "4", "5", "6", "7", "8", "9",
+    "10",
+];
+
+/// Roman numerals which are converted only to half-width
+pub(crate) const FULL_NUMERAL: [&str; 32] = [
+    "Ⅰ", "Ⅱ", "Ⅲ", "Ⅳ", "Ⅴ", "Ⅵ", "Ⅶ", "Ⅷ", "Ⅸ", "Ⅹ", "Ⅺ", "Ⅻ", "Ⅼ", "Ⅽ", "Ⅾ", "Ⅿ", "ⅰ", "ⅱ", "ⅲ",
+    "ⅳ", "ⅴ", "ⅵ", "ⅶ", "ⅷ", "ⅸ", "ⅹ", "ⅺ", "ⅻ", "ⅼ", "ⅽ", "ⅾ", "ⅿ",
+];
+
+/// Ascii letters of `FULL_NUMERAL`
+pub(crate) const HALF_NUMERAL: [&str; 32] = [
+    "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII", "L", "C", "D", "M",
+    "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x", "xi", "xii", "l", "c", "d", "m",
+];
+
+/// Squared katakana, units and era names which are expanded only by `z2h`
+pub(crate) const FULL_SQUARE: [&str; 201] = [
+    "㌀", "㌁", "㌂", "㌃", "㌄", "㌅", "㌆", "㌇", "㌈", "㌉", "㌊", "㌋", "㌌", "㌍", "㌎", "㌏",
+    "㌐", "㌑", "㌒", "㌓", "㌔", "㌕", "㌖", "㌗", "㌘", "㌙", "㌚", "㌛", "㌜", "㌝", "㌞", "㌟",
+    "㌠", "㌡", "㌢", "㌣", "㌤", "㌥", "㌦", "㌧", "㌨", "㌩", "㌪", "㌫", "㌬", "㌭", "㌮", "㌯",
+    "㌰", "㌱", "㌲", "㌳", "㌴", "㌵", "㌶", "㌷", "㌸", "㌹", "㌺", "㌻", "㌼", "㌽", "㌾", "㌿",
+    "㍀", "㍁", "㍂", "㍃", "㍄", "㍅", "㍆", "㍇", "㍈", "㍉", "㍊", "㍋", "㍌", "㍍", "㍎", "㍏",
+    "㍐", "㍑", "㍒", "㍓", "㍔", "㍕", "㍖", "㍗", "㍱", "㍲", "㍳", "㍴", "㍵", "㍶", "㍷", "㍸",
+    "㍹", "㍺", "㍻", "㍼", "㍽", "㍾", "㍿", "㎀", "㎁", "㎂", "㎃", "㎄", "㎅", "㎆", "㎇", "㎈",
+    "㎉", "㎊", "㎋", "㎌", "㎍", "㎎", "㎏", "㎐", "㎑", "㎒", "㎓", "㎔", "㎕", "㎖", "㎗", "㎘",
+    "㎙", "㎚", "㎛", "㎜", "㎝", "㎞", "㎟", "㎠", "㎡", "㎢", "㎣", "㎤", "㎥", "㎦", "㎧", "㎨",
+    "㎩", "㎪", "㎫", "㎬", "㎭", "㎮", "㎯", "㎰", "㎱", "㎲", "㎳", "㎴", "㎵", "㎶", "㎷", "㎸",
+    "㎹", "㎺", "㎻", "㎼", "㎽", "㎾", "㎿", "㏀", "㏁", "㏂", "㏃", "㏄", "㏅", "㏆", "㏇", "㏈",
+    "㏉", "㏊", "㏋", "㏌", "㏍", "㏎", "㏏", "㏐", "㏑", "㏒", "㏓", "㏔", "㏕", "㏖", "㏗", "㏘",
+    "㏙", "㏚", "㏛", "㏜", "㏝", "㏞", "㏟", "㏿", "㋿",
+];
+
+/// Spelled-out forms of `FULL_SQUARE`
+pub(crate) const HALF_SQUARE: [&str; 201] = [
+    "アパート",
+    "アルファ",
+    "アンペア",
+    "アール",
+    "イニング",
+    "インチ",
+    "ウォン",
+    "エスクード",
+    "エーカー",
+    "オンス",
+    "オーム",
+    "カイリ",
+    "カラット",
+    "カロリー",
+    "ガロン",
+    "ガンマ",
+    "ギガ",
+    "ギニー",
+    "キュリー",
+    "ギルダー",
+    "キロ",
+    "キログラム",
+    "キロメートル",
+    "キロワット",
+    "グラム",
+    "グラムトン",
+    "クルゼイロ",
+    "クローネ",
+    "ケース",
+    "コルナ",
+    "コーポ",
+    "サイクル",
+    "サンチーム",
+    "シリング",
+    "センチ",
+    "セント",
+    "ダース",
+    "デシ",
+    "ドル",
+    "トン",
+    "ナノ",
+    "ノット",
+    "ハイツ",
+    "パーセント",
+    "パーツ",
+    "バーレル",
+    "ピアストル",
+    "ピクル",
+    "ピコ",
+    "ビル",
+    "ファラッド",
+    "フィート",
+    "ブッシェル",
+    "フラン",
+    "ヘクタール",
+    "ペソ",
+    "ペニヒ",
+    "ヘルツ",
+    "ペンス",
+    "ページ",
+    "ベータ",
+    "ポイント",
+    "ボルト",
+    "ホン",
+    "ポンド",
+    "ホール",
+    "ホーン",
+    "マイクロ",
+    "マイル",
+    "マッハ",
+    "マルク",
+    "マンション",
+    "ミクロン",
+    "ミリ",
+    "ミリバール",
+    "メガ",
+    "メガトン",
+    "メートル",
+    "ヤード",
+    "ヤール",
+    "ユアン",
+    "リットル",
+    "リラ",
+    "ルピー",
+    "ルーブル",
+    "レム",
+    "レントゲン",
+    "ワット",
+    "hPa",
+    "da",
+    "AU",
+    "bar",
+    "oV",
+    "pc",
+    "dm",
+    "dm2",
+    "dm3",
+    "IU",
+    "平成",
+    "昭和",
+    "大正",
+    "明治",
+    "株式会社",
+    "pA",
+    "nA",
+    "μA",
+    "mA",
+    "kA",
+    "KB",
+    "MB",
+    "GB",
+    "cal",
+    "kcal",
+    "pF",
+    "nF",
+    "μF",
+    "μg",
+    "mg",
+    "kg",
+    "Hz",
+    "kHz",
+    "MHz",
+    "GHz",
+    "THz",
+    "μl",
+    "ml",
+    "dl",
+    "kl",
+    "fm",
+    "nm",
+    "μm",
+    "mm",
+    "cm",
+    "km",
+    "mm2",
+    "cm2",
+    "m2",
+    "km2",
+    "mm3",
+    "cm3",
+    "m3",
+    "km3",
+    "m/s",
+    "m/s2",
+    "Pa",
+    "kPa",
+    "MPa",
+    "GPa",
+    "rad",
+    "rad/s",
+    "rad/s2",
+    "ps",
+    "ns",
+    "μs",
+    "ms",
+    "pV",
+    "nV",
+    "μV",
+    "mV",
+    "kV",
+    "MV",
+    "pW",
+    "nW",
+    "μW",
+    "mW",
+    "kW",
+    "MW",
+    "kΩ",
+    "MΩ",
+    "a.m.",
+    "Bq",
+    "cc",
+    "cd",
+    "C/kg",
+    "Co.",
+    "dB",
+    "Gy",
+    "ha",
+    "HP",
+    "in",
+    "KK",
+    "KM",
+    "kt",
+    "lm",
+    "ln",
+    "log",
+    "lx",
+    "mb",
+    "mil",
+    "mol",
+    "PH",
+    "p.m.",
+    "PPM",
+    "PR",
+    "sr",
+    "Sv",
+    "Wb",
+    "V/m",
+    "A/m",
+    "gal",
+    "令和",
+];
+
+/// Hiragana letters, which are expected to be converted to katakana
+pub(crate) const HIRAGANA_LETTERS: [RangeInclusive<char>; 2] =
+    ['\u{3041}'..='\u{3096}', '\u{309d}'..='\u{309f}'];
+
+/// Katakana letters, which are expected to be converted to hiragana and half-width
+pub(crate) const KATAKANA_LETTERS: [RangeInclusive<char>; 2] =
+    ['\u{30a1}'..='\u{30fa}', '\u{30fd}'..='\u{30ff}'];
+
+/// Kanji, which are CJK unified ideographs(including extensions), compatibility
+/// ideographs, iteration mark(`々`) and ideographic number zero(`〇`)
+pub(crate) const KANJI: [RangeInclusive<char>; 7] = [
+    '\u{3005}'..='\u{3005}',
+    '\u{3007}'..='\u{3007}',
+    '\u{3400}'..='\u{4dbf}',
+    '\u{4e00}'..='\u{9fff}',
+    '\u{f900}'..='\u{faff}',
+    '\u{20000}'..='\u{2fa1f}',
+    '\u{30000}'..='\u{323af}',
+];
+
 /// Hiragana(only full-width)
 pub(crate) const HIRAGANA: [&str; 94] = [
     "ぁ", "あ", "ぃ", "い", "ぅ", "う", "ぇ", "え", "ぉ", "お", "か", "が", "き", "ぎ", "く", "ぐ",
@@ -62,6 +399,420 @@ pub(crate) const HALF_KANA: [&str; 94] = [
     "ヽ", "ヾ", "･", "｢", "｣", "｡", "､",
 ];
 
+/// Japanese punctuation(full-width)
+pub(crate) const FULL_KANA_PUNCT: [&str; 5] = ["「", "」", "。", "、", "・"];
+
+/// Japanese punctuation(half-width)
+pub(crate) const HALF_KANA_PUNCT: [&str; 5] = ["｢", "｣", "｡", "､", "･"];
+
+/// Prolonged sound mark(full-width)
+pub(crate) const FULL_PROLONGED_MARK: [&str; 1] = ["ー"];
+
+/// Prolonged sound mark(half-width)
+pub(crate) const HALF_PROLONGED_MARK: [&str; 1] = ["ｰ"];
+
+/// Small katakana(full-width)
+pub(crate) const FULL_SMALL_KANA: [&str; 9] =
+    ["ァ", "ィ", "ゥ", "ェ", "ォ", "ッ", "ャ", "ュ", "ョ"];
+
+/// Small katakana(half-width)
+pub(crate) const HALF_SMALL_KANA: [&str; 9] = ["ｧ", "ｨ", "ｩ", "ｪ", "ｫ", "ｯ", "ｬ", "ｭ", "ｮ"];
+
+/// Ideographic space(full-width)
+pub(crate) const FULL_SPACE: [&str; 1] = ["　"];
+
+/// Space(half-width)
+pub(crate) const HALF_SPACE: [&str; 1] = [" "];
+
+/// Zero-width characters which are skipped between kana and a following voiced consonant mark
+///
+/// Zero-width space(U+200B), zero-width non-joiner(U+200C), zero-width joiner(U+200D),
+/// word joiner(U+2060) and zero-width no-break space(U+FEFF)
+pub(crate) const ZERO_WIDTH: [char; 5] =
+    ['\u{200b}', '\u{200c}', '\u{200d}', '\u{2060}', '\u{feff}'];
+
+/// Middle dots which are folded into katakana middle dot(`・`, U+30FB)
+///
+/// Latin middle dot(U+00B7), bullet(U+2022), hyphenation point(U+2027),
+/// bullet operator(U+2219), dot operator(U+22C5) and half-width katakana middle dot(U+FF65)
+pub(crate) const MIDDLE_DOTS: [char; 6] = ['·', '•', '‧', '∙', '⋅', '･'];
+
+/// Lookalikes of a hyphen which `normalize` converts to `-`
+pub(crate) const HYPHENS: [char; 11] = ['˗', '֊', '‐', '‑', '‒', '–', '―', '⁃', '⁻', '₋', '−'];
+
+/// Wave dashes and dashes which `normalize` converts to `ー`
+pub(crate) const LONG_VOWEL_MARKS: [char; 7] = ['〜', '～', '﹣', '－', '—', '━', '─'];
+
+/// Quotation marks which `normalize` converts to ascii ones
+pub(crate) const QUOTES: [(char, &str); 3] = [('’', "'"), ('”', "\""), ('“', "``")];
+
+/// Lookalikes of a long vowel mark which `unify_prolonged_marks` converts to `ー`
+pub(crate) const PROLONGED_MARKS: [char; 14] = [
+    '-', '~', '‐', '–', '—', '―', '−', '─', '━', '〜', '﹣', '－', '～', 'ｰ',
+];
+
+/// Kanji numerals from zero to nine
+pub(crate) const KANJI_DIGIT: [char; 10] =
+    ['〇', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// Daiji(formal kanji numerals) from zero to nine
+pub(crate) const DAIJI_DIGIT: [char; 10] =
+    ['零', '壱', '弐', '参', '四', '五', '六', '七', '八', '九'];
+
+/// Daiji of positions below ten thousand
+pub(crate) const DAIJI_SMALL_UNIT: [(char, u64); 3] = [('拾', 10), ('百', 100), ('千', 1000)];
+
+/// Kanji numerals of positions below ten thousand
+pub(crate) const KANJI_SMALL_UNIT: [(char, u64); 3] = [('十', 10), ('百', 100), ('千', 1000)];
+
+/// Kanji numerals of positions of every four digits
+pub(crate) const KANJI_LARGE_UNIT: [(char, u64); 4] = [
+    ('万', 10_000),
+    ('億', 100_000_000),
+    ('兆', 1_000_000_000_000),
+    ('京', 10_000_000_000_000_000),
+];
+
+/// Obsolete kana and their modern equivalents
+pub(crate) const OBSOLETE_KANA: [(char, char); 4] =
+    [('ゐ', 'い'), ('ゑ', 'え'), ('ヰ', 'イ'), ('ヱ', 'エ')];
+
+/// Hentaigana(and archaic ye) and their standard hiragana
+///
+/// A hentaigana which has several readings(e.g. `N-MU-MO`) is mapped to the first one.
+#[cfg(feature = "hentaigana")]
+pub(crate) const HENTAIGANA: [(char, char); 286] = [
+    ('\u{1b001}', 'え'),
+    ('\u{1b002}', 'あ'),
+    ('\u{1b003}', 'あ'),
+    ('\u{1b004}', 'あ'),
+    ('\u{1b005}', 'あ'),
+    ('\u{1b006}', 'い'),
+    ('\u{1b007}', 'い'),
+    ('\u{1b008}', 'い'),
+    ('\u{1b009}', 'い'),
+    ('\u{1b00a}', 'う'),
+    ('\u{1b00b}', 'う'),
+    ('\u{1b00c}', 'う'),
+    ('\u{1b00d}', 'う'),
+    ('\u{1b00e}', 'う'),
+    ('\u{1b00f}', 'え'),
+    ('\u{1b010}', 'え'),
+    ('\u{1b011}', 'え'),
+    ('\u{1b012}', 'え'),
+    ('\u{1b013}', 'え'),
+    ('\u{1b014}', 'お'),
+    ('\u{1b015}', 'お'),
+    ('\u{1b016}', 'お'),
+    ('\u{1b017}', 'か'),
+    ('\u{1b018}', 'か'),
+    ('\u{1b019}', 'か'),
+    ('\u{1b01a}', 'か'),
+    ('\u{1b01b}', 'か'),
+    ('\u{1b01c}', 'か'),
+    ('\u{1b01d}', 'か'),
+    ('\u{1b01e}', 'か'),
+    ('\u{1b01f}', 'か'),
+    ('\u{1b020}', 'か'),
+    ('\u{1b021}', 'か'),
+    ('\u{1b022}', 'か'),
+    ('\u{1b023}', 'き'),
+    ('\u{1b024}', 'き'),
+    ('\u{1b025}', 'き'),
+    ('\u{1b026}', 'き'),
+    ('\u{1b027}', 'き'),
+    ('\u{1b028}', 'き'),
+    ('\u{1b029}', 'き'),
+    ('\u{1b02a}', 'き'),
+    ('\u{1b02b}', 'く'),
+    ('\u{1b02c}', 'く'),
+    ('\u{1b02d}', 'く'),
+    ('\u{1b02e}', 'く'),
+    ('\u{1b02f}', 'く'),
+    ('\u{1b030}', 'く'),
+    ('\u{1b031}', 'く'),
+    ('\u{1b032}', 'け'),
+    ('\u{1b033}', 'け'),
+    ('\u{1b034}', 'け'),
+    ('\u{1b035}', 'け'),
+    ('\u{1b036}', 'け'),
+    ('\u{1b037}', 'け'),
+    ('\u{1b038}', 'こ'),
+    ('\u{1b039}', 'こ'),
+    ('\u{1b03a}', 'こ'),
+    ('\u{1b03b}', 'こ'),
+    ('\u{1b03c}', 'さ'),
+    ('\u{1b03d}', 'さ'),
+    ('\u{1b03e}', 'さ'),
+    ('\u{1b03f}', 'さ'),
+    ('\u{1b040}', 'さ'),
+    ('\u{1b041}', 'さ'),
+    ('\u{1b042}', 'さ'),
+    ('\u{1b043}', 'さ'),
+    ('\u{1b044}', 'し'),
+    ('\u{1b045}', 'し'),
+    ('\u{1b046}', 'し'),
+    ('\u{1b047}', 'し'),
+    ('\u{1b048}', 'し'),
+    ('\u{1b049}', 'し'),
+    ('\u{1b04a}', 'す'),
+    ('\u{1b04b}', 'す'),
+    ('\u{1b04c}', 'す'),
+    ('\u{1b04d}', 'す'),
+    ('\u{1b04e}', 'す'),
+    ('\u{1b04f}', 'す'),
+    ('\u{1b050}', 'す'),
+    ('\u{1b051}', 'す'),
+    ('\u{1b052}', 'せ'),
+    ('\u{1b053}', 'せ'),
+    ('\u{1b054}', 'せ'),
+    ('\u{1b055}', 'せ'),
+    ('\u{1b056}', 'せ'),
+    ('\u{1b057}', 'そ'),
+    ('\u{1b058}', 'そ'),
+    ('\u{1b059}', 'そ'),
+    ('\u{1b05a}', 'そ'),
+    ('\u{1b05b}', 'そ'),
+    ('\u{1b05c}', 'そ'),
+    ('\u{1b05d}', 'そ'),
+    ('\u{1b05e}', 'た'),
+    ('\u{1b05f}', 'た'),
+    ('\u{1b060}', 'た'),
+    ('\u{1b061}', 'た'),
+    ('\u{1b062}', 'ち'),
+    ('\u{1b063}', 'ち'),
+    ('\u{1b064}', 'ち'),
+    ('\u{1b065}', 'ち'),
+    ('\u{1b066}', 'ち'),
+    ('\u{1b067}', 'ち'),
+    ('\u{1b068}', 'ち'),
+    ('\u{1b069}', 'つ'),
+    ('\u{1b06a}', 'つ'),
+    ('\u{1b06b}', 'つ'),
+    ('\u{1b06c}', 'つ'),
+    ('\u{1b06d}', 'つ'),
+    ('\u{1b06e}', 'て'),
+    ('\u{1b06f}', 'て'),
+    ('\u{1b070}', 'て'),
+    ('\u{1b071}', 'て'),
+    ('\u{1b072}', 'て'),
+    ('\u{1b073}', 'て'),
+    ('\u{1b074}', 'て'),
+    ('\u{1b075}', 'て'),
+    ('\u{1b076}', 'て'),
+    ('\u{1b077}', 'と'),
+    ('\u{1b078}', 'と'),
+    ('\u{1b079}', 'と'),
+    ('\u{1b07a}', 'と'),
+    ('\u{1b07b}', 'と'),
+    ('\u{1b07c}', 'と'),
+    ('\u{1b07d}', 'と'),
+    ('\u{1b07e}', 'な'),
+    ('\u{1b07f}', 'な'),
+    ('\u{1b080}', 'な'),
+    ('\u{1b081}', 'な'),
+    ('\u{1b082}', 'な'),
+    ('\u{1b083}', 'な'),
+    ('\u{1b084}', 'な'),
+    ('\u{1b085}', 'な'),
+    ('\u{1b086}', 'な'),
+    ('\u{1b087}', 'に'),
+    ('\u{1b088}', 'に'),
+    ('\u{1b089}', 'に'),
+    ('\u{1b08a}', 'に'),
+    ('\u{1b08b}', 'に'),
+    ('\u{1b08c}', 'に'),
+    ('\u{1b08d}', 'に'),
+    ('\u{1b08e}', 'に'),
+    ('\u{1b08f}', 'ぬ'),
+    ('\u{1b090}', 'ぬ'),
+    ('\u{1b091}', 'ぬ'),
+    ('\u{1b092}', 'ね'),
+    ('\u{1b093}', 'ね'),
+    ('\u{1b094}', 'ね'),
+    ('\u{1b095}', 'ね'),
+    ('\u{1b096}', 'ね'),
+    ('\u{1b097}', 'ね'),
+    ('\u{1b098}', 'ね'),
+    ('\u{1b099}', 'の'),
+    ('\u{1b09a}', 'の'),
+    ('\u{1b09b}', 'の'),
+    ('\u{1b09c}', 'の'),
+    ('\u{1b09d}', 'の'),
+    ('\u{1b09e}', 'は'),
+    ('\u{1b09f}', 'は'),
+    ('\u{1b0a0}', 'は'),
+    ('\u{1b0a1}', 'は'),
+    ('\u{1b0a2}', 'は'),
+    ('\u{1b0a3}', 'は'),
+    ('\u{1b0a4}', 'は'),
+    ('\u{1b0a5}', 'は'),
+    ('\u{1b0a6}', 'は'),
+    ('\u{1b0a7}', 'は'),
+    ('\u{1b0a8}', 'は'),
+    ('\u{1b0a9}', 'ひ'),
+    ('\u{1b0aa}', 'ひ'),
+    ('\u{1b0ab}', 'ひ'),
+    ('\u{1b0ac}', 'ひ'),
+    ('\u{1b0ad}', 'ひ'),
+    ('\u{1b0ae}', 'ひ'),
+    ('\u{1b0af}', 'ひ'),
+    ('\u{1b0b0}', 'ふ'),
+    ('\u{1b0b1}', 'ふ'),
+    ('\u{1b0b2}', 'ふ'),
+    ('\u{1b0b3}', 'へ'),
+    ('\u{1b0b4}', 'へ'),
+    ('\u{1b0b5}', 'へ'),
+    ('\u{1b0b6}', 'へ'),
+    ('\u{1b0b7}', 'へ'),
+    ('\u{1b0b8}', 'へ'),
+    ('\u{1b0b9}', 'へ'),
+    ('\u{1b0ba}', 'ほ'),
+    ('\u{1b0bb}', 'ほ'),
+    ('\u{1b0bc}', 'ほ'),
+    ('\u{1b0bd}', 'ほ'),
+    ('\u{1b0be}', 'ほ'),
+    ('\u{1b0bf}', 'ほ'),
+    ('\u{1b0c0}', 'ほ'),
+    ('\u{1b0c1}', 'ほ'),
+    ('\u{1b0c2}', 'ま'),
+    ('\u{1b0c3}', 'ま'),
+    ('\u{1b0c4}', 'ま'),
+    ('\u{1b0c5}', 'ま'),
+    ('\u{1b0c6}', 'ま'),
+    ('\u{1b0c7}', 'ま'),
+    ('\u{1b0c8}', 'ま'),
+    ('\u{1b0c9}', 'み'),
+    ('\u{1b0ca}', 'み'),
+    ('\u{1b0cb}', 'み'),
+    ('\u{1b0cc}', 'み'),
+    ('\u{1b0cd}', 'み'),
+    ('\u{1b0ce}', 'み'),
+    ('\u{1b0cf}', 'み'),
+    ('\u{1b0d0}', 'む'),
+    ('\u{1b0d1}', 'む'),
+    ('\u{1b0d2}', 'む'),
+    ('\u{1b0d3}', 'む'),
+    ('\u{1b0d4}', 'め'),
+    ('\u{1b0d5}', 'め'),
+    ('\u{1b0d6}', 'め'),
+    ('\u{1b0d7}', 'も'),
+    ('\u{1b0d8}', 'も'),
+    ('\u{1b0d9}', 'も'),
+    ('\u{1b0da}', 'も'),
+    ('\u{1b0db}', 'も'),
+    ('\u{1b0dc}', 'も'),
+    ('\u{1b0dd}', 'や'),
+    ('\u{1b0de}', 'や'),
+    ('\u{1b0df}', 'や'),
+    ('\u{1b0e0}', 'や'),
+    ('\u{1b0e1}', 'や'),
+    ('\u{1b0e2}', 'や'),
+    ('\u{1b0e3}', 'ゆ'),
+    ('\u{1b0e4}', 'ゆ'),
+    ('\u{1b0e5}', 'ゆ'),
+    ('\u{1b0e6}', 'ゆ'),
+    ('\u{1b0e7}', 'よ'),
+    ('\u{1b0e8}', 'よ'),
+    ('\u{1b0e9}', 'よ'),
+    ('\u{1b0ea}', 'よ'),
+    ('\u{1b0eb}', 'よ'),
+    ('\u{1b0ec}', 'よ'),
+    ('\u{1b0ed}', 'ら'),
+    ('\u{1b0ee}', 'ら'),
+    ('\u{1b0ef}', 'ら'),
+    ('\u{1b0f0}', 'ら'),
+    ('\u{1b0f1}', 'り'),
+    ('\u{1b0f2}', 'り'),
+    ('\u{1b0f3}', 'り'),
+    ('\u{1b0f4}', 'り'),
+    ('\u{1b0f5}', 'り'),
+    ('\u{1b0f6}', 'り'),
+    ('\u{1b0f7}', 'り'),
+    ('\u{1b0f8}', 'る'),
+    ('\u{1b0f9}', 'る'),
+    ('\u{1b0fa}', 'る'),
+    ('\u{1b0fb}', 'る'),
+    ('\u{1b0fc}', 'る'),
+    ('\u{1b0fd}', 'る'),
+    ('\u{1b0fe}', 'れ'),
+    ('\u{1b0ff}', 'れ'),
+    ('\u{1b100}', 'れ'),
+    ('\u{1b101}', 'れ'),
+    ('\u{1b102}', 'ろ'),
+    ('\u{1b103}', 'ろ'),
+    ('\u{1b104}', 'ろ'),
+    ('\u{1b105}', 'ろ'),
+    ('\u{1b106}', 'ろ'),
+    ('\u{1b107}', 'ろ'),
+    ('\u{1b108}', 'わ'),
+    ('\u{1b109}', 'わ'),
+    ('\u{1b10a}', 'わ'),
+    ('\u{1b10b}', 'わ'),
+    ('\u{1b10c}', 'わ'),
+    ('\u{1b10d}', 'ゐ'),
+    ('\u{1b10e}', 'ゐ'),
+    ('\u{1b10f}', 'ゐ'),
+    ('\u{1b110}', 'ゐ'),
+    ('\u{1b111}', 'ゐ'),
+    ('\u{1b112}', 'ゑ'),
+    ('\u{1b113}', 'ゑ'),
+    ('\u{1b114}', 'ゑ'),
+    ('\u{1b115}', 'ゑ'),
+    ('\u{1b116}', 'を'),
+    ('\u{1b117}', 'を'),
+    ('\u{1b118}', 'を'),
+    ('\u{1b119}', 'を'),
+    ('\u{1b11a}', 'を'),
+    ('\u{1b11b}', 'を'),
+    ('\u{1b11c}', 'を'),
+    ('\u{1b11d}', 'ん'),
+    ('\u{1b11e}', 'ん'),
+];
+
+/// Small kana(hiragana, full-width and half-width katakana)
+pub(crate) const SMALL_KANA: [&str; 49] = [
+    "ぁ", "ぃ", "ぅ", "ぇ", "ぉ", "っ", "ゃ", "ゅ", "ょ", "ゎ", "ゕ", "ゖ", "ァ", "ィ", "ゥ", "ェ",
+    "ォ", "ッ", "ャ", "ュ", "ョ", "ヮ", "ヵ", "ヶ", "ㇰ", "ㇱ", "ㇲ", "ㇳ", "ㇴ", "ㇵ", "ㇶ", "ㇷ",
+    "ㇸ", "ㇹ", "ㇺ", "ㇻ", "ㇼ", "ㇽ", "ㇾ", "ㇿ", "ｧ", "ｨ", "ｩ", "ｪ", "ｫ", "ｯ", "ｬ", "ｭ", "ｮ",
+];
+
+/// Large kana of `SMALL_KANA`
+pub(crate) const LARGE_KANA: [&str; 49] = [
+    "あ", "い", "う", "え", "お", "つ", "や", "ゆ", "よ", "わ", "か", "け", "ア", "イ", "ウ", "エ",
+    "オ", "ツ", "ヤ", "ユ", "ヨ", "ワ", "カ", "ケ", "ク", "シ", "ス", "ト", "ヌ", "ハ", "ヒ", "フ",
+    "ヘ", "ホ", "ム", "ラ", "リ", "ル", "レ", "ロ", "ｱ", "ｲ", "ｳ", "ｴ", "ｵ", "ﾂ", "ﾔ", "ﾕ", "ﾖ",
+];
+
+/// Nearest half-width katakana of katakana which have no half-width form
+pub(crate) const HALF_KANA_FALLBACK: [(char, &str); 5] = [
+    ('ヮ', "ﾜ"),
+    ('ヰ', "ｲ"),
+    ('ヱ', "ｴ"),
+    ('ヵ', "ｶ"),
+    ('ヶ', "ｹ"),
+];
+
+/// Small katakana of Ainu(Katakana Phonetic Extensions) and their large katakana
+pub(crate) const AINU_KANA: [(char, char); 16] = [
+    ('ㇰ', 'ク'),
+    ('ㇱ', 'シ'),
+    ('ㇲ', 'ス'),
+    ('ㇳ', 'ト'),
+    ('ㇴ', 'ヌ'),
+    ('ㇵ', 'ハ'),
+    ('ㇶ', 'ヒ'),
+    ('ㇷ', 'フ'),
+    ('ㇸ', 'ヘ'),
+    ('ㇹ', 'ホ'),
+    ('ㇺ', 'ム'),
+    ('ㇻ', 'ラ'),
+    ('ㇼ', 'リ'),
+    ('ㇽ', 'ル'),
+    ('ㇾ', 'レ'),
+    ('ㇿ', 'ロ'),
+];
+
 /// Katakana(full-width, no voiced consonant marks)
 pub(crate) const FULL_KANA_SEION: [&str; 68] = [
     "ァ", "ア", "ィ", "イ", "ゥ", "ウ", "ェ", "エ", "ォ", "オ", "カ", "キ", "ク", "ケ", "コ", "サ",
@@ -110,6 +861,469 @@ pub(crate) const MAP_KANA: [(&str, &str); 26] = [
     ("ｳﾞ", "ヴ"),
 ];
 
+/// A table that convert from kana to kana with a voiced consonant mark(゛)
+pub(crate) const MAP_VOICED: [(char, char); 48] = [
+    ('カ', 'ガ'),
+    ('キ', 'ギ'),
+    ('ク', 'グ'),
+    ('ケ', 'ゲ'),
+    ('コ', 'ゴ'),
+    ('サ', 'ザ'),
+    ('シ', 'ジ'),
+    ('ス', 'ズ'),
+    ('セ', 'ゼ'),
+    ('ソ', 'ゾ'),
+    ('タ', 'ダ'),
+    ('チ', 'ヂ'),
+    ('ツ', 'ヅ'),
+    ('テ', 'デ'),
+    ('ト', 'ド'),
+    ('ハ', 'バ'),
+    ('ヒ', 'ビ'),
+    ('フ', 'ブ'),
+    ('ヘ', 'ベ'),
+    ('ホ', 'ボ'),
+    ('ウ', 'ヴ'),
+    ('ワ', 'ヷ'),
+    ('ヰ', 'ヸ'),
+    ('ヱ', 'ヹ'),
+    ('ヲ', 'ヺ'),
+    ('ヽ', 'ヾ'),
+    ('か', 'が'),
+    ('き', 'ぎ'),
+    ('く', 'ぐ'),
+    ('け', 'げ'),
+    ('こ', 'ご'),
+    ('さ', 'ざ'),
+    ('し', 'じ'),
+    ('す', 'ず'),
+    ('せ', 'ぜ'),
+    ('そ', 'ぞ'),
+    ('た', 'だ'),
+    ('ち', 'ぢ'),
+    ('つ', 'づ'),
+    ('て', 'で'),
+    ('と', 'ど'),
+    ('は', 'ば'),
+    ('ひ', 'び'),
+    ('ふ', 'ぶ'),
+    ('へ', 'べ'),
+    ('ほ', 'ぼ'),
+    ('う', 'ゔ'),
+    ('ゝ', 'ゞ'),
+];
+
+/// A table that convert from kana to kana with a semi-voiced consonant mark(゜)
+pub(crate) const MAP_SEMI_VOICED: [(char, char); 10] = [
+    ('ハ', 'パ'),
+    ('ヒ', 'ピ'),
+    ('フ', 'プ'),
+    ('ヘ', 'ペ'),
+    ('ホ', 'ポ'),
+    ('は', 'ぱ'),
+    ('ひ', 'ぴ'),
+    ('ふ', 'ぷ'),
+    ('へ', 'ぺ'),
+    ('ほ', 'ぽ'),
+];
+
+/// Kana grouped by their vowels, with the vowel in hiragana and katakana
+pub(crate) const KANA_VOWELS: [(&str, char, char); 5] = [
+    (
+        "ぁあかがさざただなはばぱまゃやらゎわァアカガサザタダナハバパマャヤラヮワヵヷゕ",
+        'あ',
+        'ア',
+    ),
+    (
+        "ぃいきぎしじちぢにひびぴみりゐィイキギシジチヂニヒビピミリヰヸ",
+        'い',
+        'イ',
+    ),
+    (
+        "ぅうくぐすずつづぬふぶぷむゅゆるゔゥウクグスズツヅヌフブプムュユルヴ",
+        'う',
+        'ウ',
+    ),
+    (
+        "ぇえけげせぜてでねへべぺめれゑェエケゲセゼテデネヘベペメレヱヶヹゖ",
+        'え',
+        'エ',
+    ),
+    (
+        "ぉおこごそぞとどのほぼぽもょよろをォオコゴソゾトドノホボポモョヨロヲヺ",
+        'お',
+        'オ',
+    ),
+];
+
+/// A table that convert from hiragana to romaji(Hepburn)
+///
+/// Two characters keys are digraphs of a kana and a following small kana(e.g. `きゃ`).
+pub(crate) const ROMAN_HEPBURN: [(&str, &str); 143] = [
+    ("あ", "a"),
+    ("い", "i"),
+    ("う", "u"),
+    ("え", "e"),
+    ("お", "o"),
+    ("か", "ka"),
+    ("き", "ki"),
+    ("く", "ku"),
+    ("け", "ke"),
+    ("こ", "ko"),
+    ("が", "ga"),
+    ("ぎ", "gi"),
+    ("ぐ", "gu"),
+    ("げ", "ge"),
+    ("ご", "go"),
+    ("さ", "sa"),
+    ("し", "shi"),
+    ("す", "su"),
+    ("せ", "se"),
+    ("そ", "so"),
+    ("ざ", "za"),
+    ("じ", "ji"),
+    ("ず", "zu"),
+    ("ぜ", "ze"),
+    ("ぞ", "zo"),
+    ("た", "ta"),
+    ("ち", "chi"),
+    ("つ", "tsu"),
+    ("て", "te"),
+    ("と", "to"),
+    ("だ", "da"),
+    ("ぢ", "ji"),
+    ("づ", "zu"),
+    ("で", "de"),
+    ("ど", "do"),
+    ("な", "na"),
+    ("に", "ni"),
+    ("ぬ", "nu"),
+    ("ね", "ne"),
+    ("の", "no"),
+    ("は", "ha"),
+    ("ひ", "hi"),
+    ("ふ", "fu"),
+    ("へ", "he"),
+    ("ほ", "ho"),
+    ("ば", "ba"),
+    ("び", "bi"),
+    ("ぶ", "bu"),
+    ("べ", "be"),
+    ("ぼ", "bo"),
+    ("ぱ", "pa"),
+    ("ぴ", "pi"),
+    ("ぷ", "pu"),
+    ("ぺ", "pe"),
+    ("ぽ", "po"),
+    ("ま", "ma"),
+    ("み", "mi"),
+    ("む", "mu"),
+    ("め", "me"),
+    ("も", "mo"),
+    ("や", "ya"),
+    ("ゆ", "yu"),
+    ("よ", "yo"),
+    ("ら", "ra"),
+    ("り", "ri"),
+    ("る", "ru"),
+    ("れ", "re"),
+    ("ろ", "ro"),
+    ("わ", "wa"),
+    ("ゐ", "i"),
+    ("ゑ", "e"),
+    ("を", "o"),
+    ("ん", "n"),
+    ("ゔ", "vu"),
+    ("ぁ", "a"),
+    ("ぃ", "i"),
+    ("ぅ", "u"),
+    ("ぇ", "e"),
+    ("ぉ", "o"),
+    ("ゃ", "ya"),
+    ("ゅ", "yu"),
+    ("ょ", "yo"),
+    ("ゎ", "wa"),
+    ("ゕ", "ka"),
+    ("ゖ", "ke"),
+    ("きゃ", "kya"),
+    ("きゅ", "kyu"),
+    ("きょ", "kyo"),
+    ("ぎゃ", "gya"),
+    ("ぎゅ", "gyu"),
+    ("ぎょ", "gyo"),
+    ("しゃ", "sha"),
+    ("しゅ", "shu"),
+    ("しょ", "sho"),
+    ("じゃ", "ja"),
+    ("じゅ", "ju"),
+    ("じょ", "jo"),
+    ("ちゃ", "cha"),
+    ("ちゅ", "chu"),
+    ("ちょ", "cho"),
+    ("ぢゃ", "ja"),
+    ("ぢゅ", "ju"),
+    ("ぢょ", "jo"),
+    ("にゃ", "nya"),
+    ("にゅ", "nyu"),
+    ("にょ", "nyo"),
+    ("ひゃ", "hya"),
+    ("ひゅ", "hyu"),
+    ("ひょ", "hyo"),
+    ("びゃ", "bya"),
+    ("びゅ", "byu"),
+    ("びょ", "byo"),
+    ("ぴゃ", "pya"),
+    ("ぴゅ", "pyu"),
+    ("ぴょ", "pyo"),
+    ("みゃ", "mya"),
+    ("みゅ", "myu"),
+    ("みょ", "myo"),
+    ("りゃ", "rya"),
+    ("りゅ", "ryu"),
+    ("りょ", "ryo"),
+    ("ふぁ", "fa"),
+    ("ふぃ", "fi"),
+    ("ふぇ", "fe"),
+    ("ふぉ", "fo"),
+    ("ゔぁ", "va"),
+    ("ゔぃ", "vi"),
+    ("ゔぇ", "ve"),
+    ("ゔぉ", "vo"),
+    ("てぃ", "ti"),
+    ("でぃ", "di"),
+    ("とぅ", "tu"),
+    ("どぅ", "du"),
+    ("しぇ", "she"),
+    ("じぇ", "je"),
+    ("ちぇ", "che"),
+    ("うぃ", "wi"),
+    ("うぇ", "we"),
+    ("うぉ", "wo"),
+    ("つぁ", "tsa"),
+    ("つぃ", "tsi"),
+    ("つぇ", "tse"),
+    ("つぉ", "tso"),
+];
+
+/// Entries of Kunrei-shiki which differ from `ROMAN_HEPBURN`
+pub(crate) const ROMAN_KUNREI: [(&str, &str); 20] = [
+    ("し", "si"),
+    ("ち", "ti"),
+    ("つ", "tu"),
+    ("ふ", "hu"),
+    ("じ", "zi"),
+    ("ぢ", "zi"),
+    ("しゃ", "sya"),
+    ("しゅ", "syu"),
+    ("しょ", "syo"),
+    ("ちゃ", "tya"),
+    ("ちゅ", "tyu"),
+    ("ちょ", "tyo"),
+    ("じゃ", "zya"),
+    ("じゅ", "zyu"),
+    ("じょ", "zyo"),
+    ("ぢゃ", "zya"),
+    ("ぢゅ", "zyu"),
+    ("ぢょ", "zyo"),
+    ("しぇ", "sye"),
+    ("ちぇ", "tye"),
+];
+
+/// Entries of Nihon-shiki which differ from `ROMAN_KUNREI`
+pub(crate) const ROMAN_NIHON: [(&str, &str); 8] = [
+    ("ぢ", "di"),
+    ("づ", "du"),
+    ("ゐ", "wi"),
+    ("ゑ", "we"),
+    ("を", "wo"),
+    ("ぢゃ", "dya"),
+    ("ぢゅ", "dyu"),
+    ("ぢょ", "dyo"),
+];
+
+/// A table that convert from romaji to hiragana
+///
+/// Hepburn romaji and common alternatives(e.g. `si`, `tya` and `xtsu`) are included.
+/// `ti`, `tu`, `di` and `du` are loanword kana(e.g. `てぃ`), and `ぢ` and `づ` are spelled
+/// `dji`, `dzu` and `dya`.
+/// `n` and doubled consonants are handled by `roman2kana`.
+pub(crate) const ROMAN_INPUT: [(&str, &str); 169] = [
+    ("a", "あ"),
+    ("i", "い"),
+    ("u", "う"),
+    ("e", "え"),
+    ("o", "お"),
+    ("ka", "か"),
+    ("ki", "き"),
+    ("ku", "く"),
+    ("ke", "け"),
+    ("ko", "こ"),
+    ("ga", "が"),
+    ("gi", "ぎ"),
+    ("gu", "ぐ"),
+    ("ge", "げ"),
+    ("go", "ご"),
+    ("sa", "さ"),
+    ("shi", "し"),
+    ("su", "す"),
+    ("se", "せ"),
+    ("so", "そ"),
+    ("za", "ざ"),
+    ("ji", "じ"),
+    ("zu", "ず"),
+    ("ze", "ぜ"),
+    ("zo", "ぞ"),
+    ("ta", "た"),
+    ("chi", "ち"),
+    ("tsu", "つ"),
+    ("te", "て"),
+    ("to", "と"),
+    ("da", "だ"),
+    ("de", "で"),
+    ("do", "ど"),
+    ("na", "な"),
+    ("ni", "に"),
+    ("nu", "ぬ"),
+    ("ne", "ね"),
+    ("no", "の"),
+    ("ha", "は"),
+    ("hi", "ひ"),
+    ("fu", "ふ"),
+    ("he", "へ"),
+    ("ho", "ほ"),
+    ("ba", "ば"),
+    ("bi", "び"),
+    ("bu", "ぶ"),
+    ("be", "べ"),
+    ("bo", "ぼ"),
+    ("pa", "ぱ"),
+    ("pi", "ぴ"),
+    ("pu", "ぷ"),
+    ("pe", "ぺ"),
+    ("po", "ぽ"),
+    ("ma", "ま"),
+    ("mi", "み"),
+    ("mu", "む"),
+    ("me", "め"),
+    ("mo", "も"),
+    ("ya", "や"),
+    ("yu", "ゆ"),
+    ("yo", "よ"),
+    ("ra", "ら"),
+    ("ri", "り"),
+    ("ru", "る"),
+    ("re", "れ"),
+    ("ro", "ろ"),
+    ("wa", "わ"),
+    ("vu", "ゔ"),
+    ("kya", "きゃ"),
+    ("kyu", "きゅ"),
+    ("kyo", "きょ"),
+    ("gya", "ぎゃ"),
+    ("gyu", "ぎゅ"),
+    ("gyo", "ぎょ"),
+    ("sha", "しゃ"),
+    ("shu", "しゅ"),
+    ("sho", "しょ"),
+    ("ja", "じゃ"),
+    ("ju", "じゅ"),
+    ("jo", "じょ"),
+    ("cha", "ちゃ"),
+    ("chu", "ちゅ"),
+    ("cho", "ちょ"),
+    ("nya", "にゃ"),
+    ("nyu", "にゅ"),
+    ("nyo", "にょ"),
+    ("hya", "ひゃ"),
+    ("hyu", "ひゅ"),
+    ("hyo", "ひょ"),
+    ("bya", "びゃ"),
+    ("byu", "びゅ"),
+    ("byo", "びょ"),
+    ("pya", "ぴゃ"),
+    ("pyu", "ぴゅ"),
+    ("pyo", "ぴょ"),
+    ("mya", "みゃ"),
+    ("myu", "みゅ"),
+    ("myo", "みょ"),
+    ("rya", "りゃ"),
+    ("ryu", "りゅ"),
+    ("ryo", "りょ"),
+    ("fa", "ふぁ"),
+    ("fi", "ふぃ"),
+    ("fe", "ふぇ"),
+    ("fo", "ふぉ"),
+    ("va", "ゔぁ"),
+    ("vi", "ゔぃ"),
+    ("ve", "ゔぇ"),
+    ("vo", "ゔぉ"),
+    ("ti", "てぃ"),
+    ("di", "でぃ"),
+    ("tu", "とぅ"),
+    ("du", "どぅ"),
+    ("dji", "ぢ"),
+    ("dzu", "づ"),
+    ("dya", "ぢゃ"),
+    ("dyu", "ぢゅ"),
+    ("dyo", "ぢょ"),
+    ("she", "しぇ"),
+    ("je", "じぇ"),
+    ("che", "ちぇ"),
+    ("wi", "うぃ"),
+    ("we", "うぇ"),
+    ("wo", "を"),
+    ("tsa", "つぁ"),
+    ("tsi", "つぃ"),
+    ("tse", "つぇ"),
+    ("tso", "つぉ"),
+    ("si", "し"),
+    ("hu", "ふ"),
+    ("zi", "じ"),
+    ("sya", "しゃ"),
+    ("syu", "しゅ"),
+    ("syo", "しょ"),
+    ("tya", "ちゃ"),
+    ("tyu", "ちゅ"),
+    ("tyo", "ちょ"),
+    ("zya", "じゃ"),
+    ("zyu", "じゅ"),
+    ("zyo", "じょ"),
+    ("jya", "じゃ"),
+    ("jyu", "じゅ"),
+    ("jyo", "じょ"),
+    ("ye", "いぇ"),
+    ("xa", "ぁ"),
+    ("xi", "ぃ"),
+    ("xu", "ぅ"),
+    ("xe", "ぇ"),
+    ("xo", "ぉ"),
+    ("la", "ぁ"),
+    ("li", "ぃ"),
+    ("lu", "ぅ"),
+    ("le", "ぇ"),
+    ("lo", "ぉ"),
+    ("xya", "ゃ"),
+    ("xyu", "ゅ"),
+    ("xyo", "ょ"),
+    ("lya", "ゃ"),
+    ("lyu", "ゅ"),
+    ("lyo", "ょ"),
+    ("xwa", "ゎ"),
+    ("lwa", "ゎ"),
+    ("xka", "ゕ"),
+    ("xke", "ゖ"),
+    ("xtu", "っ"),
+    ("xtsu", "っ"),
+    ("ltu", "っ"),
+    ("ltsu", "っ"),
+    ("-", "ー"),
+];
+
+/// A conversion table from a code point to converted strings
+///
+/// This is a `BTreeMap` regardless of features, so its API does not change with `std`.
+pub type Table = BTreeMap<u32, String>;
+
 #[derive(Debug)]
 pub(crate) enum Method {
     /// From full-width to half-width
@@ -122,103 +1336,342 @@ pub(crate) enum Method {
     HiraToKana,
     /// From katakana(full-width) to hiragana
     KanaToHira,
+    /// From small kana to large kana
+    SmallToLarge,
+    /// From large kana to small kana(except small katakana for Ainu)
+    LargeToSmall,
+}
+
+/// An inconsistency of source and converted tables
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum TableError {
+    /// Source and converted tables have different lengths
+    LengthMismatch { keys: usize, values: usize },
+    /// A source entry is not a single character
+    InvalidKey(&'static str),
+    /// A source character appears more than once
+    DuplicateKey(char),
 }
 
 impl Method {
-    pub fn table(&self) -> HashMap<u32, String> {
-        let pair = match self {
-            FullToHalf(target) => match target {
-                All => (
-                    [&FULL_ASCII[..], &FULL_DIGIT[..], &FULL_KANA[..]].concat(),
-                    [&HALF_ASCII[..], &HALF_DIGIT[..], &HALF_KANA[..]].concat(),
-                ),
-                Ascii => ([&FULL_ASCII[..]].concat(), [&HALF_ASCII[..]].concat()),
-                AsciiAndDigits => (
-                    [&FULL_ASCII[..], &FULL_DIGIT[..]].concat(),
-                    [&HALF_ASCII[..], &HALF_DIGIT[..]].concat(),
-                ),
-                AsciiAndKana => (
-                    [&FULL_ASCII[..], &FULL_KANA[..]].concat(),
-                    [&HALF_ASCII[..], &HALF_KANA[..]].concat(),
-                ),
-                Digits => ([&FULL_DIGIT[..]].concat(), [&HALF_DIGIT[..]].concat()),
-                DigitsAndKana => (
-                    [&FULL_DIGIT[..], &FULL_KANA[..]].concat(),
-                    [&HALF_DIGIT[..], &HALF_KANA[..]].concat(),
-                ),
-                Kana => ([&FULL_KANA[..]].concat(), [&HALF_KANA[..]].concat()),
-            },
-            HalfToFull(target) => match target {
-                All => (
-                    [&HALF_ASCII[..], &HALF_DIGIT[..], &HALF_KANA_SEION[..]].concat(),
-                    [&FULL_ASCII[..], &FULL_DIGIT[..], &FULL_KANA_SEION[..]].concat(),
-                ),
-                Ascii => ([&HALF_ASCII[..]].concat(), [&FULL_ASCII[..]].concat()),
-                AsciiAndDigits => (
-                    [&HALF_ASCII[..], &HALF_DIGIT[..]].concat(),
-                    [&FULL_ASCII[..], &FULL_DIGIT[..]].concat(),
-                ),
-                AsciiAndKana => (
-                    [&HALF_ASCII[..], &HALF_KANA_SEION[..]].concat(),
-                    [&FULL_ASCII[..], &FULL_KANA_SEION[..]].concat(),
-                ),
-                Digits => ([&HALF_DIGIT[..]].concat(), [&FULL_DIGIT[..]].concat()),
-                DigitsAndKana => (
-                    [&HALF_DIGIT[..], &HALF_KANA_SEION[..]].concat(),
-                    [&FULL_DIGIT[..], &FULL_KANA_SEION[..]].concat(),
-                ),
-                Kana => (
-                    [&HALF_KANA_SEION[..]].concat(),
-                    [&FULL_KANA_SEION[..]].concat(),
-                ),
-            },
-            HiraToHalfKana => ([&HIRAGANA[..]].concat(), [&HALF_KANA[..]].concat()),
-            HiraToKana => ([&HIRAGANA[..]].concat(), [&FULL_KANA[..]].concat()),
-            KanaToHira => ([&FULL_KANA[..]].concat(), [&HIRAGANA[..]].concat()),
-        };
+    pub fn table(&self) -> Table {
+        let (keys, values) = self.pairs();
+        if let Err(e) = Method::verify(&keys, &values) {
+            panic!("invalid conversion table of {:?}: {:?}", self, e);
+        }
+
+        let mut table: Table = keys
+            .into_iter()
+            .zip(values)
+            .filter_map(|(k, v)| k.chars().next().map(|c| (c as u32, v.to_string())))
+            .collect();
+        if let FullToHalf(_) = self {
+            // Expanded entries(e.g. `㌔` to `キロ`) are converted by the other entries too
+            let expanded = table
+                .iter()
+                .filter(|(_, v)| v.chars().count() > 1)
+                .map(|(k, v)| {
+                    let v = v
+                        .chars()
+                        .map(|c| {
+                            table
+                                .get(&(c as u32))
+                                .cloned()
+                                .unwrap_or_else(|| c.to_string())
+                        })
+                        .collect::<String>();
+                    (*k, v)
+                })
+                .collect::<Vec<_>>();
+            table.extend(expanded);
+        }
+        table
+    }
+
+    /// Source and converted tables of this method
+    fn pairs(&self) -> (Vec<&'static str>, Vec<&'static str>) {
+        match self {
+            FullToHalf(target) => target.tables(true),
+            HalfToFull(target) => target.tables(false),
+            HiraToHalfKana => (HIRAGANA.to_vec(), HALF_KANA.to_vec()),
+            HiraToKana => (HIRAGANA.to_vec(), FULL_KANA.to_vec()),
+            KanaToHira => (FULL_KANA.to_vec(), HIRAGANA.to_vec()),
+            SmallToLarge => (SMALL_KANA.to_vec(), LARGE_KANA.to_vec()),
+            LargeToSmall => LARGE_KANA
+                .iter()
+                .zip(SMALL_KANA.iter())
+                .filter(|(_, s)| !s.starts_with(|c| ('ㇰ'..='ㇿ').contains(&c)))
+                .unzip(),
+        }
+    }
+
+    /// Check that every source entry is a unique single character
+    /// and has a converted entry
+    pub fn verify(keys: &[&'static str], values: &[&'static str]) -> Result<(), TableError> {
+        if keys.len() != values.len() {
+            return Err(TableError::LengthMismatch {
+                keys: keys.len(),
+                values: values.len(),
+            });
+        }
+
+        let mut chars = Vec::with_capacity(keys.len());
+        for key in keys {
+            let mut it = key.chars();
+            match (it.next(), it.next()) {
+                (Some(c), None) => chars.push(c),
+                _ => return Err(TableError::InvalidKey(key)),
+            }
+        }
+        chars.sort_unstable();
+        match chars.windows(2).find(|w| w[0] == w[1]) {
+            Some(w) => Err(TableError::DuplicateKey(w[0])),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Character groups which are converted between full-width and half-width
+///
+/// Groups can be combined with `|`, and set to an option with `ConvOptionBuilder::targets`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{z2h, ConvOption, Target};
+///
+/// let option = ConvOption::build()
+///     .targets(Target::LETTER | Target::DIGIT)
+///     .finalize();
+/// assert_eq!(Target::LETTER | Target::DIGIT, option.targets());
+/// assert_eq!("AB12（）", z2h("ＡＢ１２（）", option));
+/// ```
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Target(u16);
+
+impl Target {
+    /// No groups
+    pub const NONE: Target = Target(0);
+    /// All groups
+    pub const ALL: Target = Target((1 << 14) - 1);
+    /// Ascii(including symbols and space)
+    pub const ASCII: Target = Target(1);
+    /// Digits
+    pub const DIGIT: Target = Target(1 << 1);
+    /// Katakana
+    pub const KANA: Target = Target(1 << 2);
+    /// Ascii symbols(punctuation)
+    pub const PUNCT: Target = Target(1 << 3);
+    /// Latin letters
+    pub const LETTER: Target = Target(1 << 4);
+    /// Symbols which have another width form
+    pub const SYMBOL: Target = Target(1 << 5);
+    /// Japanese punctuation(brackets, full stop, comma and middle dot)
+    pub const KANA_PUNCT: Target = Target(1 << 6);
+    /// Enclosed numbers(only to half-width)
+    pub const ENCLOSED: Target = Target(1 << 7);
+    /// Squared characters(only to half-width)
+    pub const SQUARE: Target = Target(1 << 8);
+    /// Roman numerals(only to half-width)
+    pub const NUMERAL: Target = Target(1 << 9);
+    /// Currency symbols
+    pub const CURRENCY: Target = Target(1 << 10);
+    /// Prolonged sound mark
+    pub const PROLONGED_MARK: Target = Target(1 << 11);
+    /// Space
+    pub const SPACE: Target = Target(1 << 12);
+    /// Small katakana(e.g. `ァ`, `ッ`)
+    ///
+    /// As an excluded group, this also contains small hiragana and small katakana
+    /// which have no half-width form(e.g. `ぁ`, `ヵ`).
+    pub const SMALL_KANA: Target = Target(1 << 13);
+
+    /// Whether all groups of `other` are contained
+    pub const fn contains(&self, other: Target) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether no groups are contained
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Raw bits of contained groups
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// Groups of the raw bits, dropping bits which are not any group
+    pub const fn from_bits_truncate(bits: u16) -> Target {
+        Target(bits & Target::ALL.0)
+    }
+
+    /// Source and converted tables of contained groups
+    ///
+    /// A group which is a subset of another contained group is skipped
+    /// not to duplicate keys.
+    fn tables(&self, to_half: bool) -> (Vec<&'static str>, Vec<&'static str>) {
+        let mut pairs: Vec<(&[&str], &[&str])> = Vec::new();
+        if self.contains(Target::ASCII) {
+            pairs.push((&FULL_ASCII, &HALF_ASCII));
+        } else {
+            if self.contains(Target::PUNCT) {
+                pairs.push((&FULL_PUNCT, &HALF_PUNCT));
+            }
+            if self.contains(Target::LETTER) {
+                pairs.push((&FULL_ALPHA, &HALF_ALPHA));
+            }
+            if self.contains(Target::SPACE) {
+                pairs.push((&FULL_SPACE, &HALF_SPACE));
+            }
+        }
+        if self.contains(Target::DIGIT) {
+            pairs.push((&FULL_DIGIT, &HALF_DIGIT));
+        }
+        if self.contains(Target::SYMBOL) {
+            pairs.push((&FULL_SYMBOL, &HALF_SYMBOL));
+            if to_half {
+                pairs.push((&FULL_MINUS, &HALF_MINUS));
+            }
+        }
+        if self.contains(Target::CURRENCY) {
+            let ascii = self.contains(Target::ASCII) || self.contains(Target::PUNCT);
+            let symbol = self.contains(Target::SYMBOL);
+            for i in 0..FULL_CURRENCY.len() {
+                if (i == 0 && ascii) || ((1..4).contains(&i) && symbol) {
+                    continue;
+                }
+                pairs.push((&FULL_CURRENCY[i..=i], &HALF_CURRENCY[i..=i]));
+            }
+        }
+        if self.contains(Target::ENCLOSED) && to_half {
+            pairs.push((&FULL_ENCLOSED, &HALF_ENCLOSED));
+        }
+        if self.contains(Target::SQUARE) && to_half {
+            pairs.push((&FULL_SQUARE, &HALF_SQUARE));
+        }
+        if self.contains(Target::NUMERAL) && to_half {
+            pairs.push((&FULL_NUMERAL, &HALF_NUMERAL));
+        }
+        if self.contains(Target::KANA) {
+            if to_half {
+                pairs.push((&FULL_KANA, &HALF_KANA));
+            } else {
+                pairs.push((&FULL_KANA_SEION, &HALF_KANA_SEION));
+            }
+        } else {
+            if self.contains(Target::KANA_PUNCT) {
+                pairs.push((&FULL_KANA_PUNCT, &HALF_KANA_PUNCT));
+            }
+            if self.contains(Target::PROLONGED_MARK) {
+                pairs.push((&FULL_PROLONGED_MARK, &HALF_PROLONGED_MARK));
+            }
+            if self.contains(Target::SMALL_KANA) {
+                pairs.push((&FULL_SMALL_KANA, &HALF_SMALL_KANA));
+            }
+        }
 
-        assert!(pair.0.len() == pair.1.len());
-        let keys = pair
-            .0
-            .join("")
-            .chars()
-            .map(|c| c as u32)
-            .collect::<Vec<u32>>();
-        keys.into_iter()
-            .zip(pair.1.into_iter())
-            .map(|(k, v)| (k, v.to_string()))
-            .collect()
+        let full = pairs.iter().flat_map(|(f, _)| f.iter().copied()).collect();
+        let half = pairs.iter().flat_map(|(_, h)| h.iter().copied()).collect();
+        if to_half {
+            (full, half)
+        } else {
+            (half, full)
+        }
+    }
+
+    /// Full-width and half-width characters of contained groups
+    pub(crate) fn chars(&self) -> CharSet {
+        let (full, half) = self.tables(true);
+        let mut chars: CharSet = full
+            .into_iter()
+            .chain(half)
+            .filter_map(|s| {
+                let mut chars = s.chars();
+                chars.next().filter(|_| chars.next().is_none())
+            })
+            .collect();
+        if self.contains(Target::SMALL_KANA) {
+            chars.extend(SMALL_KANA.iter().flat_map(|s| s.chars()));
+        }
+        chars
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-pub(crate) enum Target {
-    /// Ascii, digits and katakana
-    All,
-    /// Only ascii
-    Ascii,
-    /// Ascii and digits
-    AsciiAndDigits,
-    /// Ascii and katakana
-    AsciiAndKana,
-    /// Only digits
-    Digits,
-    /// Digits and katakana
-    DigitsAndKana,
-    /// Only katakana
-    Kana,
+impl BitOr for Target {
+    type Output = Target;
+
+    fn bitor(self, rhs: Target) -> Target {
+        Target(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Target {
+    fn bitor_assign(&mut self, rhs: Target) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for Target {
+    type Output = Target;
+
+    fn bitand(self, rhs: Target) -> Target {
+        Target(self.0 & rhs.0)
+    }
 }
 
 impl From<&ConvOption<'_>> for Target {
+    /// Groups which are enabled by the option
+    ///
+    /// Katakana is converted when no groups are enabled.
     fn from(option: &ConvOption) -> Target {
-        match (option.ascii, option.digit, option.kana) {
-            (true, true, true) => All,
-            (true, true, false) => AsciiAndDigits,
-            (true, false, true) => AsciiAndKana,
-            (true, false, false) => Ascii,
-            (false, true, true) => DigitsAndKana,
-            (false, true, false) => Digits,
-            _ => Kana,
+        let mut target = Target::NONE;
+        if option.ascii {
+            target |= Target::ASCII;
+        }
+        if option.digit {
+            target |= Target::DIGIT;
+        }
+        if option.kana {
+            target |= Target::KANA;
+        }
+        if option.punct {
+            target |= Target::PUNCT;
+        }
+        if option.letter {
+            target |= Target::LETTER;
+        }
+        if option.wide_symbol {
+            target |= Target::SYMBOL;
+        }
+        if option.kana_punct {
+            target |= Target::KANA_PUNCT;
+        }
+        if option.enclosed {
+            target |= Target::ENCLOSED;
+        }
+        if option.square {
+            target |= Target::SQUARE;
+        }
+        if option.numeral {
+            target |= Target::NUMERAL;
+        }
+        if option.currency {
+            target |= Target::CURRENCY;
+        }
+        if option.prolonged_mark {
+            target |= Target::PROLONGED_MARK;
+        }
+        if option.space {
+            target |= Target::SPACE;
+        }
+        if option.small_kana {
+            target |= Target::SMALL_KANA;
+        }
+        if target.is_empty() {
+            Target::KANA
+        } else {
+            target
         }
     }
 }
@@ -227,6 +1680,7 @@ impl From<&ConvOption<'_>> for Target {
 mod tests {
     use super::*;
     use crate::ConvOption;
+    use alloc::vec;
 
     #[test]
     fn test_target_all() {
@@ -236,7 +1690,10 @@ mod tests {
             kana: true,
             ..Default::default()
         };
-        assert_eq!(Target::from(&option), Target::All);
+        assert_eq!(
+            Target::from(&option),
+            Target::ASCII | Target::DIGIT | Target::KANA
+        );
     }
 
     #[test]
@@ -245,7 +1702,7 @@ mod tests {
             ascii: true,
             ..Default::default()
         };
-        assert_eq!(Target::from(&option), Target::Ascii);
+        assert_eq!(Target::from(&option), Target::ASCII);
     }
 
     #[test]
@@ -255,7 +1712,7 @@ mod tests {
             digit: true,
             ..Default::default()
         };
-        assert_eq!(Target::from(&option), Target::AsciiAndDigits);
+        assert_eq!(Target::from(&option), Target::ASCII | Target::DIGIT);
     }
 
     #[test]
@@ -265,7 +1722,7 @@ mod tests {
             kana: true,
             ..Default::default()
         };
-        assert_eq!(Target::from(&option), Target::AsciiAndKana);
+        assert_eq!(Target::from(&option), Target::ASCII | Target::KANA);
     }
 
     #[test]
@@ -274,7 +1731,7 @@ mod tests {
             digit: true,
             ..Default::default()
         };
-        assert_eq!(Target::from(&option), Target::Digits);
+        assert_eq!(Target::from(&option), Target::DIGIT);
     }
 
     #[test]
@@ -284,7 +1741,7 @@ mod tests {
             kana: true,
             ..Default::default()
         };
-        assert_eq!(Target::from(&option), Target::DigitsAndKana);
+        assert_eq!(Target::from(&option), Target::DIGIT | Target::KANA);
     }
 
     #[test]
@@ -292,12 +1749,31 @@ mod tests {
         let option = ConvOption {
             ..Default::default()
         };
-        assert_eq!(Target::from(&option), Target::Kana);
+        assert_eq!(Target::from(&option), Target::KANA);
+    }
+
+    #[test]
+    fn test_target_punct_only() {
+        let option = ConvOption {
+            punct: true,
+            ..Default::default()
+        };
+        assert_eq!(Target::from(&option), Target::PUNCT);
+    }
+
+    #[test]
+    fn test_target_punct_and_kana() {
+        let option = ConvOption {
+            punct: true,
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(Target::from(&option), Target::PUNCT | Target::KANA);
     }
 
     #[test]
     fn test_full_to_half_all() {
-        let table = Method::FullToHalf(Target::All).table();
+        let table = Method::FullToHalf(Target::ASCII | Target::DIGIT | Target::KANA).table();
         assert_eq!(189, table.len());
         assert_eq!(table.get(&65313).unwrap(), "A");
         assert_eq!(table.get(&65296).unwrap(), "0");
@@ -306,7 +1782,7 @@ mod tests {
 
     #[test]
     fn test_full_to_half_ascii() {
-        let table = Method::FullToHalf(Target::Ascii).table();
+        let table = Method::FullToHalf(Target::ASCII).table();
         assert_eq!(85, table.len());
         assert_eq!(table.get(&65314).unwrap(), "B");
         assert_eq!(table.get(&65297), None);
@@ -315,7 +1791,7 @@ mod tests {
 
     #[test]
     fn test_full_to_half_ascii_and_digits() {
-        let table = Method::FullToHalf(Target::AsciiAndDigits).table();
+        let table = Method::FullToHalf(Target::ASCII | Target::DIGIT).table();
         assert_eq!(95, table.len());
         assert_eq!(table.get(&65315).unwrap(), "C");
         assert_eq!(table.get(&65298).unwrap(), "2");
@@ -324,7 +1800,7 @@ mod tests {
 
     #[test]
     fn test_full_to_half_ascii_and_kana() {
-        let table = Method::FullToHalf(Target::AsciiAndKana).table();
+        let table = Method::FullToHalf(Target::ASCII | Target::KANA).table();
         assert_eq!(179, table.len());
         assert_eq!(table.get(&65316).unwrap(), "D");
         assert_eq!(table.get(&65299), None);
@@ -333,7 +1809,7 @@ mod tests {
 
     #[test]
     fn test_full_to_half_digits() {
-        let table = Method::FullToHalf(Target::Digits).table();
+        let table = Method::FullToHalf(Target::DIGIT).table();
         assert_eq!(10, table.len());
         assert_eq!(table.get(&65317), None);
         assert_eq!(table.get(&65300).unwrap(), "4");
@@ -342,7 +1818,7 @@ mod tests {
 
     #[test]
     fn test_full_to_half_digits_and_kana() {
-        let table = Method::FullToHalf(Target::DigitsAndKana).table();
+        let table = Method::FullToHalf(Target::DIGIT | Target::KANA).table();
         assert_eq!(104, table.len());
         assert_eq!(table.get(&65318), None);
         assert_eq!(table.get(&65301).unwrap(), "5");
@@ -351,7 +1827,7 @@ mod tests {
 
     #[test]
     fn test_full_to_half_kana() {
-        let table = Method::FullToHalf(Target::Kana).table();
+        let table = Method::FullToHalf(Target::KANA).table();
         assert_eq!(94, table.len());
         assert_eq!(table.get(&65319), None);
         assert_eq!(table.get(&65302), None);
@@ -360,7 +1836,7 @@ mod tests {
 
     #[test]
     fn test_half_to_full_all() {
-        let table = Method::HalfToFull(Target::All).table();
+        let table = Method::HalfToFull(Target::ASCII | Target::DIGIT | Target::KANA).table();
         assert_eq!(163, table.len());
         assert_eq!(table.get(&97).unwrap(), "ａ");
         assert_eq!(table.get(&48).unwrap(), "０");
@@ -369,7 +1845,7 @@ mod tests {
 
     #[test]
     fn test_half_to_full_ascii() {
-        let table = Method::HalfToFull(Target::Ascii).table();
+        let table = Method::HalfToFull(Target::ASCII).table();
         assert_eq!(85, table.len());
         assert_eq!(table.get(&98).unwrap(), "ｂ");
         assert_eq!(table.get(&49), None);
@@ -378,7 +1854,7 @@ mod tests {
 
     #[test]
     fn test_half_to_full_ascii_and_digits() {
-        let table = Method::HalfToFull(Target::AsciiAndDigits).table();
+        let table = Method::HalfToFull(Target::ASCII | Target::DIGIT).table();
         assert_eq!(95, table.len());
         assert_eq!(table.get(&99).unwrap(), "ｃ");
         assert_eq!(table.get(&50).unwrap(), "２");
@@ -387,7 +1863,7 @@ mod tests {
 
     #[test]
     fn test_half_to_full_ascii_and_kana() {
-        let table = Method::HalfToFull(Target::AsciiAndKana).table();
+        let table = Method::HalfToFull(Target::ASCII | Target::KANA).table();
         assert_eq!(153, table.len());
         assert_eq!(table.get(&100).unwrap(), "ｄ");
         assert_eq!(table.get(&51), None);
@@ -396,7 +1872,7 @@ mod tests {
 
     #[test]
     fn test_half_to_full_digits() {
-        let table = Method::HalfToFull(Target::Digits).table();
+        let table = Method::HalfToFull(Target::DIGIT).table();
         assert_eq!(10, table.len());
         assert_eq!(table.get(&101), None);
         assert_eq!(table.get(&52).unwrap(), "４");
@@ -405,7 +1881,7 @@ mod tests {
 
     #[test]
     fn test_half_to_full_digits_and_kana() {
-        let table = Method::HalfToFull(Target::DigitsAndKana).table();
+        let table = Method::HalfToFull(Target::DIGIT | Target::KANA).table();
         assert_eq!(78, table.len());
         assert_eq!(table.get(&102), None);
         assert_eq!(table.get(&53).unwrap(), "５");
@@ -414,7 +1890,7 @@ mod tests {
 
     #[test]
     fn test_half_to_full_kana() {
-        let table = Method::HalfToFull(Target::Kana).table();
+        let table = Method::HalfToFull(Target::KANA).table();
         assert_eq!(68, table.len());
         assert_eq!(table.get(&103), None);
         assert_eq!(table.get(&54), None);
@@ -441,4 +1917,217 @@ mod tests {
         assert_eq!(94, table.len());
         assert_eq!(table.get(&12531).unwrap(), "ん");
     }
+
+    #[test]
+    fn test_full_to_half_punct() {
+        let table = Method::FullToHalf(Target::PUNCT).table();
+        assert_eq!(32, table.len());
+        assert_eq!(table.get(&65281).unwrap(), "!");
+        assert_eq!(table.get(&65313), None);
+        assert_eq!(table.get(&12288), None);
+    }
+
+    #[test]
+    fn test_full_to_half_ascii_and_punct() {
+        let table = Method::FullToHalf(Target::ASCII | Target::PUNCT).table();
+        assert_eq!(85, table.len());
+    }
+
+    #[test]
+    fn test_half_to_full_punct() {
+        let table = Method::HalfToFull(Target::PUNCT).table();
+        assert_eq!(32, table.len());
+        assert_eq!(table.get(&40).unwrap(), "（");
+        assert_eq!(table.get(&97), None);
+    }
+
+    #[test]
+    fn test_punct_subset_of_ascii() {
+        FULL_PUNCT.iter().zip(HALF_PUNCT.iter()).for_each(|(f, h)| {
+            let i = FULL_ASCII.iter().position(|a| a == f).unwrap();
+            assert_eq!(HALF_ASCII[i], *h);
+        });
+    }
+
+    #[test]
+    fn test_full_to_half_letter() {
+        let table = Method::FullToHalf(Target::LETTER).table();
+        assert_eq!(52, table.len());
+        assert_eq!(table.get(&65313).unwrap(), "A");
+        assert_eq!(table.get(&65345).unwrap(), "a");
+        assert_eq!(table.get(&65312), None);
+        assert_eq!(table.get(&65296), None);
+    }
+
+    #[test]
+    fn test_half_to_full_letter_and_punct() {
+        let table = Method::HalfToFull(Target::LETTER | Target::PUNCT).table();
+        assert_eq!(84, table.len());
+        assert_eq!(table.get(&32), None);
+    }
+
+    #[test]
+    fn test_letter_subset_of_ascii() {
+        FULL_ALPHA.iter().zip(HALF_ALPHA.iter()).for_each(|(f, h)| {
+            let i = FULL_ASCII.iter().position(|a| a == f).unwrap();
+            assert_eq!(HALF_ASCII[i], *h);
+        });
+    }
+
+    #[test]
+    fn test_small_to_large() {
+        let table = Method::SmallToLarge.table();
+        assert_eq!(49, table.len());
+        assert_eq!(table.get(&12387).unwrap(), "つ");
+        assert_eq!(table.get(&12540), None);
+    }
+
+    #[test]
+    fn test_large_to_small() {
+        let table = Method::LargeToSmall.table();
+        assert_eq!(33, table.len());
+        assert_eq!(table.get(&('ツ' as u32)).unwrap(), "ッ");
+        assert_eq!(table.get(&('カ' as u32)).unwrap(), "ヵ");
+        assert_eq!(table.get(&('ク' as u32)), None);
+    }
+
+    #[test]
+    fn test_full_to_half_kana_punct() {
+        let table = Method::FullToHalf(Target::KANA_PUNCT).table();
+        assert_eq!(5, table.len());
+        assert_eq!(table.get(&12300).unwrap(), "｢");
+        assert_eq!(table.get(&12450), None);
+    }
+
+    #[test]
+    fn test_kana_punct_subset_of_kana() {
+        FULL_KANA_PUNCT
+            .iter()
+            .zip(HALF_KANA_PUNCT.iter())
+            .for_each(|(f, h)| {
+                let i = FULL_KANA.iter().position(|k| k == f).unwrap();
+                assert_eq!(HALF_KANA[i], *h);
+                let i = FULL_KANA_SEION.iter().position(|k| k == f).unwrap();
+                assert_eq!(HALF_KANA_SEION[i], *h);
+            });
+    }
+
+    #[test]
+    fn test_verify_all_methods() {
+        let mut methods = vec![
+            HiraToHalfKana,
+            HiraToKana,
+            KanaToHira,
+            SmallToLarge,
+            LargeToSmall,
+        ];
+        for bits in 0..(1 << 14) {
+            methods.push(FullToHalf(Target(bits)));
+            methods.push(HalfToFull(Target(bits)));
+        }
+        for method in methods {
+            let (keys, values) = method.pairs();
+            assert_eq!(Method::verify(&keys, &values), Ok(()), "{:?}", method);
+            assert_eq!(method.table().len(), keys.len(), "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn test_verify_errors() {
+        assert_eq!(
+            Method::verify(&["ア", "イ"], &["ｱ"]),
+            Err(TableError::LengthMismatch { keys: 2, values: 1 })
+        );
+        assert_eq!(
+            Method::verify(&["ｶﾞ"], &["ガ"]),
+            Err(TableError::InvalidKey("ｶﾞ"))
+        );
+        assert_eq!(
+            Method::verify(&["ア", "イ", "ア"], &["ｱ", "ｲ", "ｱ"]),
+            Err(TableError::DuplicateKey('ア'))
+        );
+    }
+
+    #[test]
+    fn test_full_to_half_symbol() {
+        let table = Method::FullToHalf(Target::SYMBOL).table();
+        assert_eq!(14, table.len());
+        assert_eq!(table.get(&0x2212).unwrap(), "-");
+        assert_eq!(table.get(&0xffe5).unwrap(), "¥");
+        assert_eq!(table.get(&0x2192).unwrap(), "￫");
+        assert_eq!(table.get(&0x203b), None);
+    }
+
+    #[test]
+    fn test_half_to_full_symbol() {
+        let table = Method::HalfToFull(Target::SYMBOL).table();
+        assert_eq!(13, table.len());
+        assert_eq!(table.get(&0xa5).unwrap(), "￥");
+        assert_eq!(table.get(&0x2d), None);
+    }
+
+    #[test]
+    fn test_full_to_half_enclosed() {
+        let table = Method::FullToHalf(Target::ENCLOSED).table();
+        assert_eq!(141, table.len());
+        assert_eq!(table.get(&0x2460).unwrap(), "1");
+        assert_eq!(table.get(&0x2473).unwrap(), "20");
+        assert_eq!(table.get(&0x2474).unwrap(), "(1)");
+        assert_eq!(table.get(&0x249b).unwrap(), "20.");
+        assert_eq!(table.get(&0x32bf).unwrap(), "50");
+        assert!(Method::HalfToFull(Target::ENCLOSED).table().is_empty());
+    }
+
+    #[test]
+    fn test_full_to_half_square() {
+        let table = Method::FullToHalf(Target::SQUARE).table();
+        assert_eq!(201, table.len());
+        assert_eq!(table.get(&0x3314).unwrap(), "キロ");
+        assert_eq!(table.get(&0x334d).unwrap(), "メートル");
+        assert_eq!(table.get(&0x339d).unwrap(), "cm");
+        assert_eq!(table.get(&0x33a7).unwrap(), "m/s");
+        assert_eq!(table.get(&0x32ff).unwrap(), "令和");
+        assert!(Method::HalfToFull(Target::SQUARE).table().is_empty());
+    }
+
+    #[test]
+    fn test_full_to_half_square_and_kana() {
+        let table = Method::FullToHalf(Target::SQUARE | Target::KANA).table();
+        assert_eq!(table.get(&0x3314).unwrap(), "ｷﾛ");
+        assert_eq!(table.get(&0x3300).unwrap(), "ｱﾊﾟｰﾄ");
+        assert_eq!(table.get(&0x339d).unwrap(), "cm");
+    }
+
+    #[test]
+    fn test_full_to_half_numeral() {
+        let table = Method::FullToHalf(Target::NUMERAL).table();
+        assert_eq!(32, table.len());
+        assert_eq!(table.get(&0x2161).unwrap(), "II");
+        assert_eq!(table.get(&0x216b).unwrap(), "XII");
+        assert_eq!(table.get(&0x217b).unwrap(), "xii");
+        assert_eq!(table.get(&0x216f).unwrap(), "M");
+        assert!(Method::HalfToFull(Target::NUMERAL).table().is_empty());
+    }
+
+    #[test]
+    fn test_currency() {
+        let table = Method::FullToHalf(Target::CURRENCY).table();
+        assert_eq!(5, table.len());
+        assert_eq!(table.get(&0xff04).unwrap(), "$");
+        assert_eq!(table.get(&0xffe5).unwrap(), "¥");
+        let table = Method::HalfToFull(Target::CURRENCY).table();
+        assert_eq!(5, table.len());
+        assert_eq!(table.get(&0xa3).unwrap(), "￡");
+        assert_eq!(table.get(&0x20a9).unwrap(), "￦");
+
+        let table = Method::FullToHalf(Target::CURRENCY | Target::ASCII | Target::SYMBOL).table();
+        assert_eq!(85 + 14 + 1, table.len());
+    }
+
+    #[test]
+    fn test_half_to_full_symbol_and_ascii() {
+        let table = Method::HalfToFull(Target::ASCII | Target::SYMBOL).table();
+        assert_eq!(98, table.len());
+        assert_eq!(table.get(&0x2d).unwrap(), "－");
+    }
 }
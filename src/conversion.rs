@@ -0,0 +1,656 @@
+//! Kinds of conversion which can be selected at runtime.
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::error::Error;
+use core::fmt;
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+
+use crate::conv_option::{Fallback, MiddleDotPolicy, VoicedKanaPolicy};
+use crate::conv_table::{
+    Method, Table, Target, AINU_KANA, FULL_KANA_PUNCT, HALF_KANA_FALLBACK, HIRAGANA_LETTERS,
+    KATAKANA_LETTERS, MIDDLE_DOTS, OBSOLETE_KANA,
+};
+#[cfg(feature = "rayon")]
+use crate::convert::par_batch;
+use crate::convert::{batch, run_cow, run_into, run_spans, run_to, run_unknown, Buffer, Span};
+use crate::convert::{h2z, hira2hkata, hira2kata, kata2hira, z2h};
+use crate::convert::{
+    h2z_counted, hira2hkata_counted, hira2kata_counted, kata2hira_counted, z2h_counted, ConvResult,
+};
+use crate::ConvOption;
+
+/// Conversion kinds
+///
+/// Each kind has a short name (used by `kelp-cli`) and a long name
+/// (same as the function name), and both of them can be parsed.
+///
+/// Kind | Short | Long
+/// ---- | ----- | ----
+/// `H2z` | `h2z` | `h2z`
+/// `Z2h` | `z2h` | `z2h`
+/// `Hira2Kata` | `h2k` | `hira2kata`
+/// `Hira2HKata` | `h2hk` | `hira2hkata`
+/// `Kata2Hira` | `k2h` | `kata2hira`
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{ConvOption, Conversion};
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     ..Default::default()
+/// };
+/// let conversion = "z2h".parse::<Conversion>().unwrap();
+/// assert_eq!("ABC", conversion.apply("ＡＢＣ", option));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Conversion {
+    /// From half-width to full-width
+    H2z,
+    /// From full-width to half-width
+    Z2h,
+    /// From hiragana to full-width katakana
+    Hira2Kata,
+    /// From hiragana to half-width katakana
+    Hira2HKata,
+    /// From full-width katakana to hiragana
+    Kata2Hira,
+}
+
+impl Conversion {
+    /// All conversion kinds
+    pub const ALL: [Conversion; 5] = [
+        Conversion::H2z,
+        Conversion::Z2h,
+        Conversion::Hira2Kata,
+        Conversion::Hira2HKata,
+        Conversion::Kata2Hira,
+    ];
+
+    /// Convert strings with this conversion kind
+    pub fn apply<'a>(&self, text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+        let text = text.as_ref();
+        let option = option.borrow();
+        match self {
+            Conversion::H2z => h2z(text, option),
+            Conversion::Z2h => z2h(text, option),
+            Conversion::Hira2Kata => hira2kata(text, option),
+            Conversion::Hira2HKata => hira2hkata(text, option),
+            Conversion::Kata2Hira => kata2hira(text, option),
+        }
+    }
+
+    /// Convert strings with this conversion kind and count converted characters
+    pub fn apply_counted<'a>(
+        &self,
+        text: impl AsRef<str>,
+        option: impl Borrow<ConvOption<'a>>,
+    ) -> ConvResult {
+        let text = text.as_ref();
+        let option = option.borrow();
+        match self {
+            Conversion::H2z => h2z_counted(text, option),
+            Conversion::Z2h => z2h_counted(text, option),
+            Conversion::Hira2Kata => hira2kata_counted(text, option),
+            Conversion::Hira2HKata => hira2hkata_counted(text, option),
+            Conversion::Kata2Hira => kata2hira_counted(text, option),
+        }
+    }
+
+    /// Convert strings with this conversion kind, failing at a character which has no mapping
+    ///
+    /// When `option.fallback` is `Fallback::Error`, the first hiragana or katakana which has
+    /// no mapping(see `Fallback`) is returned as an error. Otherwise this is the same as `apply`.
+    /// `apply` and the other infallible functions keep such characters as they are.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::{ConvOption, Conversion, Fallback};
+    ///
+    /// let option = ConvOption {
+    ///     fallback: Fallback::Error,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(Ok("あい".to_string()), Conversion::Kata2Hira.try_apply("アイ", &option));
+    ///
+    /// let error = Conversion::Kata2Hira.try_apply("アヷ", &option).unwrap_err();
+    /// assert_eq!('ヷ', error.char());
+    /// ```
+    pub fn try_apply<'a>(
+        &self,
+        text: impl AsRef<str>,
+        option: impl Borrow<ConvOption<'a>>,
+    ) -> Result<String, UnmappedError> {
+        self.try_apply_counted(text, option)
+            .map(|result| result.output)
+    }
+
+    /// Convert strings with this conversion kind and count converted characters,
+    /// failing at a character which has no mapping
+    ///
+    /// See `try_apply` for details.
+    pub fn try_apply_counted<'a>(
+        &self,
+        text: impl AsRef<str>,
+        option: impl Borrow<ConvOption<'a>>,
+    ) -> Result<ConvResult, UnmappedError> {
+        let text = text.as_ref();
+        let option = option.borrow();
+        if option.fallback != Fallback::Error {
+            return Ok(self.apply_counted(text, option));
+        }
+        let unmapped = self.unmapped(option, &self.method(option).table());
+        let (result, unknown) = run_unknown(*self, text, option, |c| unmapped.contains(&c));
+        match unknown.first() {
+            Some(c) => Err(UnmappedError {
+                conversion: *self,
+                c: *c,
+            }),
+            None => Ok(result),
+        }
+    }
+
+    /// Convert strings with this conversion kind and append them to the buffer
+    ///
+    /// Use `Converter::convert_into` to reuse the conversion table too.
+    pub fn apply_into<'a, B: Buffer>(
+        &self,
+        buf: &mut B,
+        text: impl AsRef<str>,
+        option: impl Borrow<ConvOption<'a>>,
+    ) {
+        let option = option.borrow();
+        run_into(*self, &self.table(option), buf, text.as_ref(), option)
+    }
+
+    /// Convert strings with this conversion kind, and borrow `text` if nothing changes
+    ///
+    /// See `z2h_cow` for details.
+    pub fn apply_cow<'t, 'a>(
+        &self,
+        text: &'t str,
+        option: impl Borrow<ConvOption<'a>>,
+    ) -> Cow<'t, str> {
+        run_cow(*self, text, option.borrow())
+    }
+
+    /// Convert strings with this conversion kind and pair byte ranges
+    ///
+    /// See `z2h_spans` for details of spans.
+    pub fn apply_spans(&self, text: impl AsRef<str>, option: &ConvOption) -> (String, Vec<Span>) {
+        run_spans(*self, text.as_ref(), option)
+    }
+
+    /// Convert strings with this conversion kind into the writer
+    pub fn apply_to<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        text: impl AsRef<str>,
+        option: &ConvOption,
+    ) -> fmt::Result {
+        run_to(*self, out, text.as_ref(), option)
+    }
+
+    /// Convert many strings with this conversion kind
+    ///
+    /// The conversion table is built only once and reused for all strings.
+    pub fn apply_batch<'a, I>(&self, items: I, option: &ConvOption) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        batch(*self, items, option)
+    }
+
+    /// Convert many strings in parallel with this conversion kind
+    ///
+    /// This requires `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn par_apply_batch(&self, items: &[&str], option: &ConvOption) -> Vec<String> {
+        par_batch(*self, items, option)
+    }
+
+    /// Conversion table which is used by this conversion kind
+    ///
+    /// The table can be extended with `merge_table` and used with `convert_with`.
+    pub fn table(&self, option: &ConvOption) -> Table {
+        let mut table = self.method(option).table();
+        let fallback = match option.fallback {
+            Fallback::Keep | Fallback::Error => None,
+            Fallback::Replace(c) => Some(c.to_string()),
+            Fallback::Drop => Some(String::new()),
+        };
+        if let Some(fallback) = fallback {
+            for c in self.unmapped(option, &table) {
+                table.insert(c as u32, fallback.clone());
+            }
+        }
+        if option.preserve_space {
+            table.remove(&(' ' as u32));
+            table.remove(&('　' as u32));
+        }
+        if option.wave_dash {
+            fold(&mut table, &['〜'], '～');
+        }
+        if option.keep_kana_punct && !matches!(self, Conversion::H2z | Conversion::Z2h) {
+            for punct in FULL_KANA_PUNCT.iter().flat_map(|s| s.chars()) {
+                table.remove(&(punct as u32));
+            }
+        }
+        match option.middle_dot_policy {
+            MiddleDotPolicy::Convert => (),
+            MiddleDotPolicy::Keep => {
+                table.remove(&('・' as u32));
+                table.remove(&('･' as u32));
+            }
+            MiddleDotPolicy::Strip => {
+                table.insert('・' as u32, String::new());
+                table.insert('･' as u32, String::new());
+            }
+        }
+        if option.middle_dot {
+            // `･` is kept as it is with `MiddleDotPolicy::Keep`
+            let dots = MIDDLE_DOTS
+                .iter()
+                .copied()
+                .filter(|c| option.middle_dot_policy != MiddleDotPolicy::Keep || *c != '･')
+                .collect::<Vec<_>>();
+            fold(&mut table, &dots, '・');
+        }
+        if option.kana_fallback && matches!(self, Conversion::Z2h | Conversion::Hira2HKata) {
+            for value in table.values_mut() {
+                let fallback = HALF_KANA_FALLBACK
+                    .iter()
+                    .find(|(full, _)| value.chars().eq(Some(*full)));
+                if let Some((_, half)) = fallback {
+                    *value = half.to_string();
+                }
+            }
+        }
+        if matches!(self, Conversion::Z2h | Conversion::Hira2HKata) {
+            let is_voiced = |v: &str| {
+                let mut chars = v.chars();
+                matches!(
+                    (chars.next(), chars.next(), chars.next()),
+                    (Some(_), Some('ﾞ' | 'ﾟ'), None)
+                )
+            };
+            match option.voiced_kana {
+                VoicedKanaPolicy::Split => (),
+                VoicedKanaPolicy::Base => {
+                    for value in table.values_mut().filter(|v| is_voiced(v)) {
+                        value.truncate(value.len() - 'ﾞ'.len_utf8());
+                    }
+                }
+                VoicedKanaPolicy::Keep => table.retain(|_, v| !is_voiced(v)),
+            }
+        }
+        if option.modern_kana && !matches!(self, Conversion::H2z | Conversion::Z2h) {
+            for (obsolete, modern) in OBSOLETE_KANA {
+                fold(&mut table, &[obsolete], modern);
+            }
+        }
+        if option.ainu_kana {
+            for (small, large) in AINU_KANA {
+                if let Some(converted) = table.get(&(large as u32)).cloned() {
+                    table.insert(small as u32, converted);
+                }
+            }
+        }
+        if !option.exclude.is_empty() {
+            let excluded = option.exclude.chars();
+            let is_excluded = |s: &str| {
+                let mut chars = s.chars();
+                matches!((chars.next(), chars.next()), (Some(c), None) if excluded.contains(&c))
+            };
+            table.retain(|key, value| {
+                let key = char::from_u32(*key).unwrap_or_default();
+                !excluded.contains(&key) && !is_excluded(value)
+            });
+        }
+        table
+    }
+
+    /// Hiragana or katakana letters which have no entry in the table
+    fn unmapped(&self, option: &ConvOption, table: &Table) -> Vec<char> {
+        let kana =
+            Target::from(option).contains(Target::KANA) && !option.exclude.contains(Target::KANA);
+        let letters: &[RangeInclusive<char>] = match self {
+            Conversion::Hira2Kata | Conversion::Hira2HKata => &HIRAGANA_LETTERS,
+            Conversion::Kata2Hira => &KATAKANA_LETTERS,
+            Conversion::Z2h if kana => &KATAKANA_LETTERS,
+            _ => &[],
+        };
+        letters
+            .iter()
+            .cloned()
+            .flatten()
+            .filter(|c| !table.contains_key(&(*c as u32)))
+            .collect()
+    }
+
+    /// Conversion method of this conversion kind
+    pub(crate) fn method(&self, option: &ConvOption) -> Method {
+        match self {
+            Conversion::H2z => Method::HalfToFull(Target::from(option)),
+            Conversion::Z2h => Method::FullToHalf(Target::from(option)),
+            Conversion::Hira2Kata => Method::HiraToKana,
+            Conversion::Hira2HKata => Method::HiraToHalfKana,
+            Conversion::Kata2Hira => Method::KanaToHira,
+        }
+    }
+
+    /// Short name of this conversion kind
+    pub fn name(&self) -> &'static str {
+        match self {
+            Conversion::H2z => "h2z",
+            Conversion::Z2h => "z2h",
+            Conversion::Hira2Kata => "h2k",
+            Conversion::Hira2HKata => "h2hk",
+            Conversion::Kata2Hira => "k2h",
+        }
+    }
+
+    /// Long name of this conversion kind
+    fn long_name(&self) -> &'static str {
+        match self {
+            Conversion::H2z => "h2z",
+            Conversion::Z2h => "z2h",
+            Conversion::Hira2Kata => "hira2kata",
+            Conversion::Hira2HKata => "hira2hkata",
+            Conversion::Kata2Hira => "kata2hira",
+        }
+    }
+}
+
+/// Map characters of `from` to the same strings as `to`
+///
+/// `to` itself is used when the table has no entry of `to`.
+fn fold(table: &mut Table, from: &[char], to: char) {
+    let target = table
+        .get(&(to as u32))
+        .cloned()
+        .unwrap_or_else(|| to.to_string());
+    from.iter()
+        .filter(|c| target.chars().ne(Some(**c)))
+        .for_each(|c| {
+            table.insert(*c as u32, target.clone());
+        });
+}
+
+impl fmt::Display for Conversion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Conversion {
+    type Err = ParseConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Conversion::ALL
+            .iter()
+            .find(|c| c.name() == s || c.long_name() == s)
+            .copied()
+            .ok_or_else(|| ParseConversionError {
+                name: s.to_string(),
+            })
+    }
+}
+
+/// An error which is returned when parsing an unknown conversion kind
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseConversionError {
+    name: String,
+}
+
+impl fmt::Display for ParseConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names = Conversion::ALL
+            .iter()
+            .map(|c| c.name())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "unknown conversion `{}` (expected one of: {})",
+            self.name, names
+        )
+    }
+}
+
+impl Error for ParseConversionError {}
+
+/// An error which is returned when a character has no mapping by a conversion
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct UnmappedError {
+    conversion: Conversion,
+    c: char,
+}
+
+impl UnmappedError {
+    /// The character which has no mapping
+    pub fn char(&self) -> char {
+        self.c
+    }
+
+    /// The conversion kind which failed
+    pub fn conversion(&self) -> Conversion {
+        self.conversion
+    }
+}
+
+impl fmt::Display for UnmappedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` has no mapping by `{}`", self.c, self.conversion)
+    }
+}
+
+impl Error for UnmappedError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_short_name() {
+        assert_eq!("h2z".parse(), Ok(Conversion::H2z));
+        assert_eq!("z2h".parse(), Ok(Conversion::Z2h));
+        assert_eq!("h2k".parse(), Ok(Conversion::Hira2Kata));
+        assert_eq!("h2hk".parse(), Ok(Conversion::Hira2HKata));
+        assert_eq!("k2h".parse(), Ok(Conversion::Kata2Hira));
+    }
+
+    #[test]
+    fn test_from_str_long_name() {
+        assert_eq!("hira2kata".parse(), Ok(Conversion::Hira2Kata));
+        assert_eq!("hira2hkata".parse(), Ok(Conversion::Hira2HKata));
+        assert_eq!("kata2hira".parse(), Ok(Conversion::Kata2Hira));
+    }
+
+    #[test]
+    fn test_from_str_unknown() {
+        let err = "x2y".parse::<Conversion>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown conversion `x2y` (expected one of: h2z, z2h, h2k, h2hk, k2h)"
+        );
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for conversion in Conversion::ALL.iter() {
+            assert_eq!(conversion.to_string().parse(), Ok(*conversion));
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(Conversion::Hira2Kata.apply("あいう", option), "アイウ");
+        assert_eq!(Conversion::Hira2HKata.apply("あいう", option), "ｱｲｳ");
+        assert_eq!(Conversion::Kata2Hira.apply("アイウ", option), "あいう");
+        assert_eq!(Conversion::H2z.apply("ｱｲｳ", option), "アイウ");
+        assert_eq!(Conversion::Z2h.apply("アイウ", option), "ｱｲｳ");
+    }
+
+    #[test]
+    fn test_table_preserve_space() {
+        let option = ConvOption {
+            ascii: true,
+            ..Default::default()
+        };
+        let preserved = ConvOption {
+            preserve_space: true,
+            ..option
+        };
+        for conversion in [Conversion::H2z, Conversion::Z2h].iter() {
+            let table = conversion.table(&option);
+            let without_space = conversion.table(&preserved);
+            assert_eq!(table.len(), without_space.len() + 1);
+            assert!(!without_space.contains_key(&(' ' as u32)));
+            assert!(!without_space.contains_key(&('　' as u32)));
+        }
+        let result = Conversion::H2z.apply_counted("A B", preserved);
+        assert_eq!(result.output, "Ａ Ｂ");
+        assert_eq!(result.converted, 2);
+    }
+
+    #[test]
+    fn test_middle_dot_policy() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(Conversion::Z2h.apply("ア・イ", option), "ｱ･ｲ");
+        assert_eq!(Conversion::H2z.apply("ｱ･ｲ", option), "ア・イ");
+
+        let keep = ConvOption {
+            middle_dot_policy: MiddleDotPolicy::Keep,
+            ..option
+        };
+        assert_eq!(Conversion::Z2h.apply("ア・イ", keep), "ｱ・ｲ");
+        assert_eq!(Conversion::H2z.apply("ｱ･ｲ", keep), "ア･イ");
+        assert_eq!(Conversion::Z2h.apply("ア·イ", keep), "ｱ·ｲ");
+        let keep = ConvOption {
+            middle_dot: true,
+            ..keep
+        };
+        assert_eq!(Conversion::H2z.apply("ｱ･ｲ", keep), "ア･イ");
+        assert_eq!(Conversion::Z2h.apply("ア・イ", keep), "ｱ・ｲ");
+        assert_eq!(Conversion::Z2h.apply("ア·イ", keep), "ｱ・ｲ");
+
+        let strip = ConvOption {
+            middle_dot_policy: MiddleDotPolicy::Strip,
+            ..option
+        };
+        assert_eq!(Conversion::Z2h.apply("ア・イ･ウ·", strip), "ｱｲｳ·");
+        assert_eq!(Conversion::Kata2Hira.apply("ア・イ", strip), "あい");
+        let strip = ConvOption {
+            middle_dot: true,
+            ..strip
+        };
+        assert_eq!(Conversion::Z2h.apply("ア・イ･ウ·", strip), "ｱｲｳ");
+    }
+
+    #[test]
+    fn test_table_keep_kana_punct() {
+        let option = ConvOption {
+            keep_kana_punct: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Conversion::Hira2HKata.apply("「あ、い。」・", option),
+            "「ｱ、ｲ。」・"
+        );
+        assert_eq!(
+            Conversion::Kata2Hira
+                .apply_counted("「ア」", option)
+                .converted,
+            1
+        );
+        assert_eq!(
+            Conversion::Hira2Kata
+                .apply_counted("「あ」", option)
+                .converted,
+            1
+        );
+        assert_eq!(Conversion::Z2h.apply("「ア」", option), "｢ｱ｣");
+    }
+
+    #[test]
+    fn test_table_voiced_kana() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(Conversion::Z2h.apply("ガパヴ", option), "ｶﾞﾊﾟｳﾞ");
+
+        let base = ConvOption {
+            voiced_kana: VoicedKanaPolicy::Base,
+            ..option
+        };
+        assert_eq!(Conversion::Z2h.apply("ガパヴカ", base), "ｶﾊｳｶ");
+        assert_eq!(Conversion::Hira2HKata.apply("がぱか", base), "ｶﾊｶ");
+        assert_eq!(Conversion::H2z.apply("ｶﾞ", base), "ガ");
+
+        let keep = ConvOption {
+            voiced_kana: VoicedKanaPolicy::Keep,
+            ..option
+        };
+        assert_eq!(Conversion::Z2h.apply("ガパカ", keep), "ガパｶ");
+        assert_eq!(Conversion::Hira2HKata.apply("がか", keep), "がｶ");
+    }
+
+    #[test]
+    fn test_table_kana_fallback() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(Conversion::Z2h.apply("ヮヰヱヵヶ", option), "ヮヰヱヵヶ");
+
+        let option = ConvOption {
+            kana_fallback: true,
+            ..option
+        };
+        assert_eq!(Conversion::Z2h.apply("ヮヰヱヵヶ", option), "ﾜｲｴｶｹ");
+        assert_eq!(Conversion::Hira2HKata.apply("ゎゐゑゕゖ", option), "ﾜｲｴｶｹ");
+        assert_eq!(Conversion::Kata2Hira.apply("ヮヵ", option), "ゎゕ");
+    }
+
+    #[test]
+    fn test_table_modern_kana() {
+        let option = ConvOption {
+            modern_kana: true,
+            ..Default::default()
+        };
+        assert_eq!(Conversion::Kata2Hira.apply("ヰヱゐゑ", option), "いえいえ");
+        assert_eq!(Conversion::Hira2Kata.apply("ゐゑヰヱ", option), "イエイエ");
+        assert_eq!(Conversion::Hira2HKata.apply("ゐゑ", option), "ｲｴ");
+        assert_eq!(Conversion::Z2h.apply("ゐヰ", option), "ゐヰ");
+        assert_eq!(
+            Conversion::Kata2Hira.apply("ヰ", ConvOption::default()),
+            "ゐ"
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_apply_batch() {
+        let items = ["ＡＢＣ", "アイウ", "１２３"];
+        let option = ConvOption::all();
+        for conversion in Conversion::ALL.iter() {
+            assert_eq!(
+                conversion.par_apply_batch(&items, &option),
+                conversion.apply_batch(items, &option)
+            );
+        }
+    }
+}
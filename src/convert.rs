@@ -1,9 +1,21 @@
 //! Functions which convert strings.
-use std::collections::HashMap;
-use std::vec::Vec;
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Range;
 
-use crate::conv_table::{Method, Target, MAP_KANA};
+use crate::conv_option::IgnoreSet;
+use crate::conv_table::{
+    Method, Table, Target, KANA_VOWELS, MAP_KANA, MAP_SEMI_VOICED, MAP_VOICED, ZERO_WIDTH,
+};
+#[cfg(feature = "hentaigana")]
+use crate::conv_table::{HENTAIGANA, OBSOLETE_KANA};
 use crate::ConvOption;
+use crate::Conversion;
+use crate::UnmappedError;
+use crate::{AsKanaOption, AsWidthOption};
 
 /// Convert from hiragana to full-witdh katakana
 ///
@@ -25,10 +37,17 @@ use crate::ConvOption;
 /// };
 /// let converted = hira2kata("かきくけこ", option);
 /// assert_eq!("かキクケこ", converted);
+///
+/// // A reference to an option can be reused for many strings.
+/// let option = ConvOption::default();
+/// assert_eq!("アイ", hira2kata("あい", &option));
+/// assert_eq!("ウエ", hira2kata("うえ", &option));
 /// ```
-pub fn hira2kata(text: &str, option: ConvOption) -> String {
-    let method = Method::HiraToKana;
-    convert(text, method.table(), &option.ignore)
+///
+/// Hiragana iteration marks(`ゝ`, `ゞ`) are converted to
+/// katakana iteration marks(`ヽ`, `ヾ`) as well.
+pub fn hira2kata<'a>(text: impl AsRef<str>, option: impl AsKanaOption<'a>) -> String {
+    hira2kata_counted(text.as_ref(), option).output
 }
 
 /// Convert from hiragana to half-width katakana
@@ -52,9 +71,8 @@ pub fn hira2kata(text: &str, option: ConvOption) -> String {
 /// let converted = hira2hkata("がぎぐげご", option);
 /// assert_eq!("がｷﾞｸﾞｹﾞご", converted);
 /// ```
-pub fn hira2hkata(text: &str, option: ConvOption) -> String {
-    let method = Method::HiraToHalfKana;
-    convert(text, method.table(), &option.ignore)
+pub fn hira2hkata<'a>(text: impl AsRef<str>, option: impl AsKanaOption<'a>) -> String {
+    hira2hkata_counted(text.as_ref(), option).output
 }
 
 /// Convert from full-width katakana to hiragana
@@ -78,9 +96,25 @@ pub fn hira2hkata(text: &str, option: ConvOption) -> String {
 /// let converted = kata2hira("カキクケコ", option);
 /// assert_eq!("かキクケこ", converted);
 /// ```
-pub fn kata2hira(text: &str, option: ConvOption) -> String {
-    let method = Method::KanaToHira;
-    convert(text, method.table(), &option.ignore)
+///
+/// Katakana iteration marks(`ヽ`, `ヾ`) are converted to
+/// hiragana iteration marks(`ゝ`, `ゞ`) as well.
+///
+/// With `long_vowel`, prolonged sound marks(`ー`) are replaced with
+/// the vowel of the preceding kana.
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::kata2hira;
+///
+/// let option = ConvOption {
+///     long_vowel: true,
+///     ..Default::default()
+/// };
+/// assert_eq!("こおひい", kata2hira("コーヒー", option));
+/// ```
+pub fn kata2hira<'a>(text: impl AsRef<str>, option: impl AsKanaOption<'a>) -> String {
+    kata2hira_counted(text.as_ref(), option).output
 }
 
 /// Convert from half-width to full-width
@@ -105,21 +139,33 @@ pub fn kata2hira(text: &str, option: ConvOption) -> String {
 ///     digit: true,
 ///     kana: true,
 ///     ignore: "Aｱ0",
+///     ..Default::default()
 /// };
 /// let converted = h2z("ABCｱｲｳ012", option);
 /// assert_eq!("AＢＣｱイウ0１２", converted);
 /// ```
-pub fn h2z(text: &str, option: ConvOption) -> String {
-    let method = Method::HalfToFull(Target::from(&option));
-    if option.kana {
-        convert(
-            &before_convert(text, MAP_KANA.to_vec()),
-            method.table(),
-            &option.ignore,
-        )
-    } else {
-        convert(text, method.table(), &option.ignore)
-    }
+///
+/// # Round trip
+///
+/// For every katakana, `h2z(&z2h(text, option), option)` returns `text`
+/// and `z2h(&h2z(text, option), option)` returns `text`, except the following.
+///
+/// - A half-width voiced consonant mark is always merged with the preceding
+///   half-width katakana, so `カﾞ`(full-width `カ` and half-width `ﾞ`)
+///   becomes `ｶﾞ` by `z2h` and then `ガ` by `h2z`, not `カﾞ`.
+/// - A pair whose katakana or mark is in `ignore` is not merged,
+///   so `ｶﾞ` with `ignore: "ｶ"` becomes `ｶﾞ`.
+/// - Decomposed kana like `カ゛` is not composed and kept as it is.
+///
+/// # Voiced consonant marks
+///
+/// A half-width voiced consonant mark(`ﾞ` or `ﾟ`) which cannot be merged with
+/// the preceding character is kept as it is, and the preceding character is
+/// converted alone. This applies to a mark at the beginning of strings,
+/// a mark after a katakana which has no voiced form(e.g. `ｱﾞ` becomes `アﾞ`),
+/// and a second mark after a merged pair(e.g. `ｶﾞﾞ` becomes `ガﾞ`).
+pub fn h2z<'a>(text: impl AsRef<str>, option: impl AsWidthOption<'a>) -> String {
+    h2z_counted(text.as_ref(), option).output
 }
 
 /// Convert from full-width to half-width
@@ -144,276 +190,2630 @@ pub fn h2z(text: &str, option: ConvOption) -> String {
 ///     digit: true,
 ///     ignore: "Ａア０",
 ///     kana: true,
+///     ..Default::default()
 /// };
 /// let converted = z2h("ＡＢＣアイウ０１２", option);
 /// assert_eq!("ＡBCアｲｳ０12", converted);
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     preserve_space: true,
+///     ..Default::default()
+/// };
+/// let converted = z2h("Ａ　Ｂ", option);
+/// assert_eq!("A　B", converted);
 /// ```
-pub fn z2h(text: &str, option: ConvOption) -> String {
-    let method = Method::FullToHalf(Target::from(&option));
-    convert(text, method.table(), &option.ignore)
+pub fn z2h<'a>(text: impl AsRef<str>, option: impl AsWidthOption<'a>) -> String {
+    z2h_counted(text.as_ref(), option).output
 }
 
-/// Replace strings before convert
-fn before_convert(text: &str, convert: Vec<(&str, &str)>) -> String {
-    let mut converted = text.to_string();
-    convert
-        .iter()
-        .for_each(|(b, a)| converted = converted.replace(b, a));
-    converted
+/// Convert from small kana to large kana
+///
+/// Small hiragana and katakana(full-width and half-width) are converted
+/// in one pass. A prolonged sound mark(`ー`) is not a small kana
+/// and kept as it is.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::small2large;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = small2large("ちょっとキャッシュー", option);
+/// assert_eq!("ちよつとキヤツシユー", converted);
+///
+/// let option = ConvOption {
+///     ignore: "ッ",
+///     ..Default::default()
+/// };
+/// let converted = small2large("ショッピング", option);
+/// assert_eq!("シヨッピング", converted);
+/// ```
+pub fn small2large<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    convert(
+        text.as_ref(),
+        &Method::SmallToLarge.table(),
+        option.borrow(),
+    )
 }
 
-/// Convert strings refers conversion table and option settings
-fn convert(text: &str, table: HashMap<u32, String>, ignore: &str) -> String {
-    let ignore = ignore.chars().map(|c| c as u32).collect::<Vec<_>>();
-
-    text.chars()
-        .map(|c| {
-            let ord = c as u32;
-            match table.get(&ord) {
-                Some(s) if !ignore.contains(&ord) => s.to_string(),
-                _ => c.to_string(),
-            }
+/// Convert from large kana to small kana
+///
+/// This is the inverse of `small2large`, except small katakana for Ainu(e.g. `ㇰ`)
+/// which are not produced. Every large kana which has a small form is converted,
+/// so use `ignore` or `only` to restrict characters.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::large2small;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = large2small("アツカ", option);
+/// assert_eq!("ァッヵ", converted);
+///
+/// let option = ConvOption {
+///     only: "つツ",
+///     ..Default::default()
+/// };
+/// let converted = large2small("まつタツ", option);
+/// assert_eq!("まっタッ", converted);
+/// ```
+pub fn large2small<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    convert(
+        text.as_ref(),
+        &Method::LargeToSmall.table(),
+        option.borrow(),
+    )
+}
+
+/// Convert from hentaigana to hiragana
+///
+/// Hentaigana of the Kana Supplement and Kana Extended-A blocks(U+1B001 to U+1B11E)
+/// are converted to standard hiragana, e.g. `𛀁` to `え` and `𛀙` to `か`.
+/// Obsolete kana(`ゐ` and `ゑ`) are modernized too if `modern_kana` is enabled.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::hentaigana2hira;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = hentaigana2hira("\u{1b019}\u{1b0a7}", option);
+/// assert_eq!("かは", converted);
+/// ```
+#[cfg(feature = "hentaigana")]
+pub fn hentaigana2hira<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    let table = HENTAIGANA
+        .iter()
+        .map(|(h, k)| {
+            let k = match OBSOLETE_KANA.iter().find(|(o, _)| o == k) {
+                Some((_, modern)) if option.modern_kana => *modern,
+                _ => *k,
+            };
+            (*h as u32, k.to_string())
         })
-        .collect::<Vec<String>>()
-        .join("")
+        .collect::<Table>();
+    convert(text, &table, option)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::conv_table::*;
+/// Voiced consonant marks which `decompose_marks` emits
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum VoicedMark {
+    /// Combining marks(U+3099 and U+309A) as in NFD
+    #[default]
+    Combining,
+    /// Spacing marks(`゛` and `゜`)
+    Spacing,
+}
 
-    macro_rules! strings {
-        ($($x:expr), *) => {{
-            let mut concat = String::new();
-            $(
-                concat = format!("{}{}", concat, $x.join(""));
-            )*
-            concat
-        }};
-    }
+/// Decompose kana with a voiced consonant mark into kana and the mark
+///
+/// This is the inverse of `compose_mark` of `ConvOption`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{decompose_marks, ConvOption, VoicedMark};
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let decomposed = decompose_marks("がパ", VoicedMark::Combining, option);
+/// assert_eq!("か\u{3099}ハ\u{309a}", decomposed);
+///
+/// let decomposed = decompose_marks("がパ", VoicedMark::Spacing, option);
+/// assert_eq!("か゛ハ゜", decomposed);
+/// ```
+pub fn decompose_marks<'a>(
+    text: impl AsRef<str>,
+    mark: VoicedMark,
+    option: impl Borrow<ConvOption<'a>>,
+) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    let (voiced, semi_voiced) = match mark {
+        VoicedMark::Combining => ('\u{3099}', '\u{309a}'),
+        VoicedMark::Spacing => ('゛', '゜'),
+    };
+    let table = MAP_VOICED
+        .iter()
+        .map(|(base, v)| (*v, *base, voiced))
+        .chain(
+            MAP_SEMI_VOICED
+                .iter()
+                .map(|(base, v)| (*v, *base, semi_voiced)),
+        )
+        .map(|(v, base, mark)| (v as u32, [base, mark].iter().collect()))
+        .collect::<Table>();
+    convert(text, &table, option)
+}
 
-    #[test]
-    fn test_hira2kata() {
-        let before = strings!(HIRAGANA);
-        let after = strings!(FULL_KANA);
-        let option = ConvOption {
-            ..Default::default()
-        };
-        assert_eq!(hira2kata(&before, option), after);
-    }
+/// A buffer which converted strings are appended to
+///
+/// This is implemented for `String` and `Vec<u8>`(as UTF-8 bytes),
+/// so a buffer can be cleared and reused for many strings.
+pub trait Buffer {
+    /// Append strings to the end of this buffer
+    fn push_str(&mut self, s: &str);
+}
 
-    #[test]
-    fn test_hira2kata_with_ignore() {
-        let before = strings!(HIRAGANA);
-        let option = ConvOption {
-            ignore: &before,
-            ..Default::default()
-        };
-        assert_eq!(hira2kata(&before, option), before);
+impl Buffer for String {
+    fn push_str(&mut self, s: &str) {
+        String::push_str(self, s);
     }
+}
 
-    #[test]
-    fn test_hira2hkata() {
-        let before = strings!(HIRAGANA);
-        let after = strings!(HALF_KANA);
-        let option = ConvOption {
-            ..Default::default()
-        };
-        assert_eq!(hira2hkata(&before, option), after);
+impl Buffer for Vec<u8> {
+    fn push_str(&mut self, s: &str) {
+        self.extend_from_slice(s.as_bytes());
     }
+}
 
-    #[test]
-    fn test_hira2hkata_with_ignore() {
-        let before = strings!(HIRAGANA);
-        let option = ConvOption {
-            ignore: &before,
-            ..Default::default()
-        };
-        assert_eq!(hira2hkata(&before, option), before);
+/// Converted strings and the number of converted characters
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConvResult {
+    /// Converted strings
+    pub output: String,
+    /// The number of characters which were replaced
+    pub converted: usize,
+}
+
+/// A conversion kind and its option with a prebuilt conversion table
+///
+/// The table is built once in `Converter::new`,
+/// so a converter can be reused for many strings.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{ConvOption, Conversion, Converter};
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     ignore: "Ｃ",
+///     ..Default::default()
+/// };
+/// let converter = Converter::new(Conversion::Z2h, option);
+/// assert_eq!("ABＣ", converter.convert("ＡＢＣ"));
+/// assert_eq!("xyz", converter.convert("ｘｙｚ"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Converter<'a> {
+    conversion: Conversion,
+    option: ConvOption<'a>,
+    table: Table,
+    /// Characters of `option.ignore`
+    ignore: IgnoreSet,
+}
+
+impl<'a> Converter<'a> {
+    /// Returns a converter of the conversion kind and the option
+    pub fn new(conversion: Conversion, option: ConvOption<'a>) -> Converter<'a> {
+        let table = conversion.table(&option);
+        let ignore = option.ignore_set();
+        Converter {
+            conversion,
+            option,
+            table,
+            ignore,
+        }
     }
 
-    #[test]
-    fn test_kata2hira() {
-        let before = strings!(FULL_KANA);
-        let after = strings!(HIRAGANA);
-        let option = ConvOption {
-            ..Default::default()
-        };
-        assert_eq!(kata2hira(&before, option), after);
+    /// Conversion kind of this converter
+    pub fn conversion(&self) -> Conversion {
+        self.conversion
     }
 
-    #[test]
-    fn test_kata2hira_with_ignore() {
-        let before = strings!(FULL_KANA);
-        let option = ConvOption {
-            ignore: &before,
-            ..Default::default()
-        };
-        assert_eq!(kata2hira(&before, option), before);
+    /// Option of this converter
+    pub fn option(&self) -> &ConvOption<'a> {
+        &self.option
     }
 
-    #[test]
-    fn test_h2z_all() {
-        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
-        let after = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
-        let option = ConvOption {
-            ascii: true,
-            digit: true,
-            kana: true,
-            ..Default::default()
-        };
-        assert_eq!(h2z(&before, option), after);
+    /// Convert strings
+    pub fn convert(&self, text: impl AsRef<str>) -> String {
+        self.convert_counted(text.as_ref()).output
     }
 
-    #[test]
-    fn test_h2z_ascii() {
-        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
-        let after = strings!(FULL_ASCII, HALF_DIGIT, HALF_KANA);
-        let option = ConvOption {
-            ascii: true,
-            ..Default::default()
-        };
-        assert_eq!(h2z(&before, option), after);
+    /// Convert strings and append converted strings to the buffer
+    ///
+    /// The buffer is not cleared, and neither the table nor the buffer is reallocated
+    /// if it has enough capacity.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kelp::{ConvOption, Conversion, Converter};
+    ///
+    /// let converter = Converter::new(Conversion::Z2h, ConvOption::all());
+    /// let mut buf = String::new();
+    /// let mut lines = Vec::new();
+    /// for line in ["ＡＢＣ", "アイウ"] {
+    ///     buf.clear();
+    ///     converter.convert_into(&mut buf, line);
+    ///     lines.push(buf.clone());
+    /// }
+    /// assert_eq!(vec!["ABC", "ｱｲｳ"], lines);
+    ///
+    /// let mut bytes = b"> ".to_vec();
+    /// converter.convert_into(&mut bytes, "１２３");
+    /// assert_eq!(b"> 123", bytes.as_slice());
+    /// ```
+    pub fn convert_into<B: Buffer>(&self, buf: &mut B, text: impl AsRef<str>) {
+        run_into(
+            self.conversion,
+            &self.table,
+            buf,
+            text.as_ref(),
+            &self.option,
+        )
     }
 
-    #[test]
-    fn test_h2z_ascii_and_digits() {
-        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
-        let after = strings!(FULL_ASCII, FULL_DIGIT, HALF_KANA);
-        let option = ConvOption {
-            ascii: true,
-            digit: true,
-            ..Default::default()
-        };
-        assert_eq!(h2z(&before, option), after);
+    /// Convert strings and count converted characters
+    pub fn convert_counted(&self, text: impl AsRef<str>) -> ConvResult {
+        run_with(self.conversion, &self.table, text.as_ref(), &self.option)
     }
 
-    #[test]
-    fn test_h2z_ascii_and_kana() {
-        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
-        let after = strings!(FULL_ASCII, HALF_DIGIT, FULL_KANA);
-        let option = ConvOption {
-            ascii: true,
-            kana: true,
-            ..Default::default()
-        };
-        assert_eq!(h2z(&before, option), after);
+    /// Convert strings and pair byte ranges
+    ///
+    /// See `z2h_spans` for details of spans.
+    pub fn convert_spans(&self, text: impl AsRef<str>) -> (String, Vec<Span>) {
+        let rules = Rules::new(self.conversion, &self.option);
+        convert_spans(text.as_ref(), &self.table, &self.option, rules, None)
     }
 
-    #[test]
-    fn test_h2z_digits() {
-        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
-        let after = strings!(HALF_ASCII, FULL_DIGIT, HALF_KANA);
-        let option = ConvOption {
-            digit: true,
-            ..Default::default()
-        };
-        assert_eq!(h2z(&before, option), after);
+    /// Convert strings which follow converted strings ending with `prev`, and pair byte ranges
+    ///
+    /// `prev` is the context of `long_vowel`.
+    pub(crate) fn convert_spans_after(
+        &self,
+        text: &str,
+        prev: Option<char>,
+    ) -> (String, Vec<Span>) {
+        let rules = Rules::new(self.conversion, &self.option);
+        convert_spans(text, &self.table, &self.option, rules, prev)
     }
 
-    #[test]
-    fn test_h2z_digits_and_kana() {
-        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
-        let after = strings!(HALF_ASCII, FULL_DIGIT, FULL_KANA);
-        let option = ConvOption {
-            digit: true,
-            kana: true,
-            ..Default::default()
-        };
-        assert_eq!(h2z(&before, option), after);
+    /// Whether each character is converted only by the table regardless of its neighbors
+    pub(crate) fn is_per_char(&self) -> bool {
+        #[cfg(feature = "regex")]
+        if self.option.ignore_pattern.is_some() {
+            return false;
+        }
+        let rules = Rules::new(self.conversion, &self.option);
+        !rules.compose_mark && !rules.merge_half_mark && rules.long_vowel.is_none()
     }
 
-    #[test]
-    fn test_h2z_kana() {
-        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
-        let after = strings!(HALF_ASCII, HALF_DIGIT, FULL_KANA);
-        let option = ConvOption {
-            kana: true,
-            ..Default::default()
-        };
-        assert_eq!(h2z(&before, option), after);
+    /// Convert each character of strings only by the table
+    ///
+    /// This is the same as `convert` if `is_per_char` is true.
+    pub(crate) fn convert_per_char(&self, text: &str) -> String {
+        text.chars()
+            .fold(String::with_capacity(text.len()), |mut output, c| {
+                match self.table.get(&(c as u32)) {
+                    Some(s) if !self.option.is_ignored_in(&self.ignore, c) => output.push_str(s),
+                    _ => output.push(c),
+                }
+                output
+            })
     }
 
-    #[test]
-    fn test_z2h_all() {
-        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
-        let after = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
-        let option = ConvOption {
-            ascii: true,
-            digit: true,
-            kana: true,
-            ..Default::default()
-        };
-        assert_eq!(z2h(&before, option), after);
+    /// Keys of the table
+    pub(crate) fn keys(&self) -> impl Iterator<Item = char> + '_ {
+        self.table.keys().filter_map(|k| char::from_u32(*k))
     }
+}
 
-    #[test]
-    fn test_z2h_ascii() {
-        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
-        let after = strings!(HALF_ASCII, FULL_DIGIT, FULL_KANA);
-        let option = ConvOption {
+/// Convert from hiragana to full-width katakana with the number of converted characters
+pub fn hira2kata_counted<'a>(text: impl AsRef<str>, option: impl AsKanaOption<'a>) -> ConvResult {
+    run(Conversion::Hira2Kata, text.as_ref(), option.conv_option())
+}
+
+/// Convert from hiragana to half-width katakana with the number of converted characters
+pub fn hira2hkata_counted<'a>(text: impl AsRef<str>, option: impl AsKanaOption<'a>) -> ConvResult {
+    run(Conversion::Hira2HKata, text.as_ref(), option.conv_option())
+}
+
+/// Convert from full-width katakana to hiragana with the number of converted characters
+pub fn kata2hira_counted<'a>(text: impl AsRef<str>, option: impl AsKanaOption<'a>) -> ConvResult {
+    run(Conversion::Kata2Hira, text.as_ref(), option.conv_option())
+}
+
+/// Convert from half-width to full-width with the number of converted characters
+///
+/// A pair of half-width katakana and a voiced consonant mark(e.g. `ｶﾞ`)
+/// is counted as one character.
+pub fn h2z_counted<'a>(text: impl AsRef<str>, option: impl AsWidthOption<'a>) -> ConvResult {
+    run(Conversion::H2z, text.as_ref(), option.conv_option())
+}
+
+/// Convert from full-width to half-width with the number of converted characters
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::z2h_counted;
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     ignore: "Ｃ",
+///     ..Default::default()
+/// };
+/// let result = z2h_counted("ＡＢＣabc", option);
+/// assert_eq!("ABＣabc", result.output);
+/// assert_eq!(2, result.converted);
+/// ```
+pub fn z2h_counted<'a>(text: impl AsRef<str>, option: impl AsWidthOption<'a>) -> ConvResult {
+    run(Conversion::Z2h, text.as_ref(), option.conv_option())
+}
+
+/// Convert from hiragana to full-width katakana in the buffer
+pub fn hira2kata_in_place<'a>(buf: &mut String, option: impl AsKanaOption<'a>) {
+    in_place(buf, Conversion::Hira2Kata, option.conv_option());
+}
+
+/// Convert from hiragana to half-width katakana in the buffer
+pub fn hira2hkata_in_place<'a>(buf: &mut String, option: impl AsKanaOption<'a>) {
+    in_place(buf, Conversion::Hira2HKata, option.conv_option());
+}
+
+/// Convert from full-width katakana to hiragana in the buffer
+pub fn kata2hira_in_place<'a>(buf: &mut String, option: impl AsKanaOption<'a>) {
+    in_place(buf, Conversion::Kata2Hira, option.conv_option());
+}
+
+/// Convert from half-width to full-width in the buffer
+pub fn h2z_in_place<'a>(buf: &mut String, option: impl AsWidthOption<'a>) {
+    in_place(buf, Conversion::H2z, option.conv_option());
+}
+
+/// Convert from full-width to half-width in the buffer
+///
+/// The converted strings are written back to `buf`,
+/// and the capacity of `buf` is retained.
+/// `buf` is left untouched without allocation if nothing is converted, but otherwise
+/// the output is built in a temporary `String` and the conversion table is built on each call.
+/// To reuse both the table and the output buffer, use `Converter::convert_into`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::z2h_in_place;
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     ..Default::default()
+/// };
+/// let mut buf = String::from("ＡＢＣ");
+/// z2h_in_place(&mut buf, &option);
+/// assert_eq!("ABC", buf);
+/// ```
+pub fn z2h_in_place<'a>(buf: &mut String, option: impl AsWidthOption<'a>) {
+    in_place(buf, Conversion::Z2h, option.conv_option());
+}
+
+/// Replace contents of the buffer with converted strings
+///
+/// Nothing is allocated if no character changes. Otherwise the output is built
+/// in a temporary buffer and copied back, since it may be longer than the input.
+fn in_place(buf: &mut String, conversion: Conversion, option: &ConvOption) {
+    if let Cow::Owned(converted) = run_cow(conversion, buf, option) {
+        buf.clear();
+        buf.push_str(&converted);
+    }
+}
+
+/// Convert many strings from hiragana to full-width katakana
+pub fn hira2kata_batch<'t, 'a, I>(items: I, option: impl AsKanaOption<'a>) -> Vec<String>
+where
+    I: IntoIterator<Item = &'t str>,
+{
+    batch(Conversion::Hira2Kata, items, option.conv_option())
+}
+
+/// Convert many strings from hiragana to half-width katakana
+pub fn hira2hkata_batch<'t, 'a, I>(items: I, option: impl AsKanaOption<'a>) -> Vec<String>
+where
+    I: IntoIterator<Item = &'t str>,
+{
+    batch(Conversion::Hira2HKata, items, option.conv_option())
+}
+
+/// Convert many strings from full-width katakana to hiragana
+pub fn kata2hira_batch<'t, 'a, I>(items: I, option: impl AsKanaOption<'a>) -> Vec<String>
+where
+    I: IntoIterator<Item = &'t str>,
+{
+    batch(Conversion::Kata2Hira, items, option.conv_option())
+}
+
+/// Convert many strings from half-width to full-width
+pub fn h2z_batch<'t, 'a, I>(items: I, option: impl AsWidthOption<'a>) -> Vec<String>
+where
+    I: IntoIterator<Item = &'t str>,
+{
+    batch(Conversion::H2z, items, option.conv_option())
+}
+
+/// Convert many strings from full-width to half-width
+///
+/// The conversion table is built only once and reused for all strings.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::z2h_batch;
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     ..Default::default()
+/// };
+/// let converted = z2h_batch(vec!["ＡＢＣ", "ＤＥＦ"], &option);
+/// assert_eq!(vec!["ABC", "DEF"], converted);
+/// ```
+pub fn z2h_batch<'t, 'a, I>(items: I, option: impl AsWidthOption<'a>) -> Vec<String>
+where
+    I: IntoIterator<Item = &'t str>,
+{
+    batch(Conversion::Z2h, items, option.conv_option())
+}
+
+/// Convert many strings with the conversion kind and one conversion table
+pub(crate) fn batch<'a, I>(conversion: Conversion, items: I, option: &ConvOption) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let table = conversion.table(option);
+    items
+        .into_iter()
+        .map(|text| run_with(conversion, &table, text, option).output)
+        .collect()
+}
+
+/// Convert many strings in parallel with the conversion kind and one conversion table
+#[cfg(feature = "rayon")]
+pub(crate) fn par_batch(
+    conversion: Conversion,
+    items: &[&str],
+    option: &ConvOption,
+) -> Vec<String> {
+    use rayon::prelude::*;
+
+    let table = conversion.table(option);
+    items
+        .par_iter()
+        .map(|text| run_with(conversion, &table, text, option).output)
+        .collect()
+}
+
+/// A pair of a byte range of the source and that of converted strings
+pub type Span = (Range<usize>, Range<usize>);
+
+/// Convert from hiragana to full-width katakana with byte ranges of each character
+pub fn hira2kata_spans<'a>(
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> (String, Vec<Span>) {
+    run_spans(Conversion::Hira2Kata, text.as_ref(), option.conv_option())
+}
+
+/// Convert from hiragana to half-width katakana with byte ranges of each character
+pub fn hira2hkata_spans<'a>(
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> (String, Vec<Span>) {
+    run_spans(Conversion::Hira2HKata, text.as_ref(), option.conv_option())
+}
+
+/// Convert from full-width katakana to hiragana with byte ranges of each character
+pub fn kata2hira_spans<'a>(
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> (String, Vec<Span>) {
+    run_spans(Conversion::Kata2Hira, text.as_ref(), option.conv_option())
+}
+
+/// Convert from half-width to full-width with byte ranges of each character
+pub fn h2z_spans<'a>(text: impl AsRef<str>, option: impl AsWidthOption<'a>) -> (String, Vec<Span>) {
+    run_spans(Conversion::H2z, text.as_ref(), option.conv_option())
+}
+
+/// Convert from full-width to half-width with byte ranges of each character
+///
+/// Each span pairs a byte range of a source character with
+/// the byte range of its converted strings in the output.
+/// A pair of katakana and a voiced consonant mark which is merged
+/// into one character has one span.
+/// Characters which are not converted(including ignored ones) have spans too.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::z2h_spans;
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     kana: true,
+///     ..Default::default()
+/// };
+/// let (converted, spans) = z2h_spans("Ａガ", &option);
+/// assert_eq!("Aｶﾞ", converted);
+/// assert_eq!(vec![(0..3, 0..1), (3..6, 1..7)], spans);
+/// ```
+pub fn z2h_spans<'a>(text: impl AsRef<str>, option: impl AsWidthOption<'a>) -> (String, Vec<Span>) {
+    run_spans(Conversion::Z2h, text.as_ref(), option.conv_option())
+}
+
+/// Convert from hiragana to full-width katakana without allocating if nothing changes
+pub fn hira2kata_cow<'t, 'a>(text: &'t str, option: impl AsKanaOption<'a>) -> Cow<'t, str> {
+    run_cow(Conversion::Hira2Kata, text, option.conv_option())
+}
+
+/// Convert from hiragana to half-width katakana without allocating if nothing changes
+pub fn hira2hkata_cow<'t, 'a>(text: &'t str, option: impl AsKanaOption<'a>) -> Cow<'t, str> {
+    run_cow(Conversion::Hira2HKata, text, option.conv_option())
+}
+
+/// Convert from full-width katakana to hiragana without allocating if nothing changes
+pub fn kata2hira_cow<'t, 'a>(text: &'t str, option: impl AsKanaOption<'a>) -> Cow<'t, str> {
+    run_cow(Conversion::Kata2Hira, text, option.conv_option())
+}
+
+/// Convert from half-width to full-width without allocating if nothing changes
+pub fn h2z_cow<'t, 'a>(text: &'t str, option: impl AsWidthOption<'a>) -> Cow<'t, str> {
+    run_cow(Conversion::H2z, text, option.conv_option())
+}
+
+/// Convert from full-width to half-width without allocating if nothing changes
+///
+/// `Cow::Borrowed` of `text` is returned when the converted strings are the same as `text`,
+/// otherwise this is the same as `z2h`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use kelp::{z2h_cow, ConvOption};
+///
+/// let option = ConvOption::all();
+/// assert!(matches!(z2h_cow("abc漢字", &option), Cow::Borrowed("abc漢字")));
+/// assert_eq!("abc", z2h_cow("ａｂｃ", &option));
+/// ```
+pub fn z2h_cow<'t, 'a>(text: &'t str, option: impl AsWidthOption<'a>) -> Cow<'t, str> {
+    run_cow(Conversion::Z2h, text, option.conv_option())
+}
+
+/// Convert from hiragana to full-width katakana into the writer
+pub fn hira2kata_to<'a, W: fmt::Write>(
+    out: &mut W,
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> fmt::Result {
+    run_to(
+        Conversion::Hira2Kata,
+        out,
+        text.as_ref(),
+        option.conv_option(),
+    )
+}
+
+/// Convert from hiragana to half-width katakana into the writer
+pub fn hira2hkata_to<'a, W: fmt::Write>(
+    out: &mut W,
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> fmt::Result {
+    run_to(
+        Conversion::Hira2HKata,
+        out,
+        text.as_ref(),
+        option.conv_option(),
+    )
+}
+
+/// Convert from full-width katakana to hiragana into the writer
+pub fn kata2hira_to<'a, W: fmt::Write>(
+    out: &mut W,
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> fmt::Result {
+    run_to(
+        Conversion::Kata2Hira,
+        out,
+        text.as_ref(),
+        option.conv_option(),
+    )
+}
+
+/// Convert from half-width to full-width into the writer
+pub fn h2z_to<'a, W: fmt::Write>(
+    out: &mut W,
+    text: impl AsRef<str>,
+    option: impl AsWidthOption<'a>,
+) -> fmt::Result {
+    run_to(Conversion::H2z, out, text.as_ref(), option.conv_option())
+}
+
+/// Convert from full-width to half-width into the writer
+///
+/// Converted strings are appended to `out` without allocating a new `String`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::{h2z_to, z2h_to};
+///
+/// let option = ConvOption {
+///     ascii: true,
+///     ..Default::default()
+/// };
+/// let mut buf = String::from("> ");
+/// z2h_to(&mut buf, "ＡＢＣ", &option).unwrap();
+/// h2z_to(&mut buf, "abc", &option).unwrap();
+/// assert_eq!("> ABCａｂｃ", buf);
+/// ```
+pub fn z2h_to<'a, W: fmt::Write>(
+    out: &mut W,
+    text: impl AsRef<str>,
+    option: impl AsWidthOption<'a>,
+) -> fmt::Result {
+    run_to(Conversion::Z2h, out, text.as_ref(), option.conv_option())
+}
+
+/// Convert from hiragana to full-width katakana with unknown hiragana
+///
+/// See `kata2hira_with_unknown` for details.
+pub fn hira2kata_with_unknown<'a>(
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> (String, Vec<char>) {
+    let text = text.as_ref();
+    let block = '\u{3040}'..='\u{309f}';
+    let (result, unknown) = run_unknown(Conversion::Hira2Kata, text, option.conv_option(), |c| {
+        block.contains(&c)
+    });
+    (result.output, unknown)
+}
+
+/// Convert from full-width katakana to hiragana with unknown katakana
+///
+/// The second element lists characters in the katakana block(U+30A0 to U+30FF)
+/// which have no mapping, in order of first appearance without duplicates.
+/// Ignored characters are not listed. The converted strings are the same as `kata2hira`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::kata2hira_with_unknown;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let (converted, unknown) = kata2hira_with_unknown("ヷイヺヷ", &option);
+/// assert_eq!("ヷいヺヷ", converted);
+/// assert_eq!(vec!['ヷ', 'ヺ'], unknown);
+/// ```
+pub fn kata2hira_with_unknown<'a>(
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> (String, Vec<char>) {
+    let text = text.as_ref();
+    let block = '\u{30a0}'..='\u{30ff}';
+    let (result, unknown) = run_unknown(Conversion::Kata2Hira, text, option.conv_option(), |c| {
+        block.contains(&c)
+    });
+    (result.output, unknown)
+}
+
+/// Convert from hiragana to full-width katakana, failing at hiragana which has no mapping
+///
+/// See `try_kata2hira` for details.
+pub fn try_hira2kata<'a>(
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> Result<String, UnmappedError> {
+    Conversion::Hira2Kata.try_apply(text, option.conv_option())
+}
+
+/// Convert from hiragana to half-width katakana, failing at hiragana which has no mapping
+///
+/// See `try_kata2hira` for details.
+pub fn try_hira2hkata<'a>(
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> Result<String, UnmappedError> {
+    Conversion::Hira2HKata.try_apply(text, option.conv_option())
+}
+
+/// Convert from full-width katakana to hiragana, failing at katakana which has no mapping
+///
+/// When `option.fallback` is `Fallback::Error`, the first katakana which has no mapping
+/// is returned as an error. Otherwise this is the same as `kata2hira`.
+/// `Fallback::Error` is only honored by these `try_` functions and `Conversion::try_apply`,
+/// and the other functions keep such characters as they are.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{kata2hira, try_kata2hira, ConvOption, Fallback};
+///
+/// let option = ConvOption {
+///     fallback: Fallback::Error,
+///     ..Default::default()
+/// };
+/// assert_eq!(Ok("あい".to_string()), try_kata2hira("アイ", option));
+/// assert_eq!('ヷ', try_kata2hira("アヷイ", option).unwrap_err().char());
+/// assert_eq!("あヷい", kata2hira("アヷイ", option));
+/// ```
+pub fn try_kata2hira<'a>(
+    text: impl AsRef<str>,
+    option: impl AsKanaOption<'a>,
+) -> Result<String, UnmappedError> {
+    Conversion::Kata2Hira.try_apply(text, option.conv_option())
+}
+
+/// Convert from full-width to half-width, failing at katakana which has no mapping
+///
+/// See `try_kata2hira` for details.
+pub fn try_z2h<'a>(
+    text: impl AsRef<str>,
+    option: impl AsWidthOption<'a>,
+) -> Result<String, UnmappedError> {
+    Conversion::Z2h.try_apply(text, option.conv_option())
+}
+
+/// Convert strings with a user-supplied conversion table
+///
+/// Characters in `option.ignore` are not converted, same as the other functions.
+/// Only the table lookup is done, so pairs of half-width katakana and
+/// voiced consonant marks(e.g. `ｶﾞ`) are not merged like `h2z`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{convert_with, merge_table, ConvOption, Conversion};
+///
+/// let option = ConvOption {
+///     ignore: "ウ",
+///     ..Default::default()
+/// };
+/// let table = merge_table(Conversion::Z2h.table(&option), &[('ア', "a"), ('Ω', "O")]);
+/// let converted = convert_with("アイウΩ", &table, &option);
+/// assert_eq!("aｲウO", converted);
+/// ```
+pub fn convert_with(text: impl AsRef<str>, table: &Table, option: &ConvOption) -> String {
+    convert(text.as_ref(), table, option)
+}
+
+/// Layer user-supplied entries on top of a conversion table
+///
+/// When a character exists in both `table` and `overrides`,
+/// the entry of `overrides` takes precedence.
+pub fn merge_table(mut table: Table, overrides: &[(char, &str)]) -> Table {
+    overrides.iter().for_each(|(k, v)| {
+        table.insert(*k as u32, v.to_string());
+    });
+    table
+}
+
+/// Convert strings with the conversion kind and passes enabled by the option
+fn run(conversion: Conversion, text: &str, option: &ConvOption) -> ConvResult {
+    run_with(conversion, &conversion.table(option), text, option)
+}
+
+/// Convert strings with the conversion kind, and pair byte ranges
+pub(crate) fn run_spans(
+    conversion: Conversion,
+    text: &str,
+    option: &ConvOption,
+) -> (String, Vec<Span>) {
+    let table = conversion.table(option);
+    let rules = Rules::new(conversion, option);
+    convert_spans(text, &table, option, rules, None)
+}
+
+/// Convert strings with the conversion kind, and list characters satisfying `is_unknown`
+/// which have no mapping
+pub(crate) fn run_unknown<F>(
+    conversion: Conversion,
+    text: &str,
+    option: &ConvOption,
+    is_unknown: F,
+) -> (ConvResult, Vec<char>)
+where
+    F: Fn(char) -> bool,
+{
+    let table = conversion.table(option);
+    let rules = Rules::new(conversion, option);
+    let mut output = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut unknown = Vec::new();
+    let ignore = option.ignore_set();
+
+    scan(text, &table, option, rules, |range, s, converted| {
+        output.push_str(s);
+        if converted {
+            count += 1;
+            return;
+        }
+        text[range]
+            .chars()
+            .filter(|c| is_unknown(*c) && !option.is_ignored_in(&ignore, *c))
+            .for_each(|c| {
+                if !unknown.contains(&c) {
+                    unknown.push(c);
+                }
+            });
+    });
+    let result = ConvResult {
+        output,
+        converted: count,
+    };
+    (result, unknown)
+}
+
+/// Convert strings with the conversion kind and the prebuilt table into the buffer
+pub(crate) fn run_into<B: Buffer>(
+    conversion: Conversion,
+    table: &Table,
+    buf: &mut B,
+    text: &str,
+    option: &ConvOption,
+) {
+    let rules = Rules::new(conversion, option);
+    scan(text, table, option, rules, |_, s, _| buf.push_str(s));
+}
+
+/// Convert strings with the conversion kind, and borrow `text` if nothing changes
+pub(crate) fn run_cow<'t>(
+    conversion: Conversion,
+    text: &'t str,
+    option: &ConvOption,
+) -> Cow<'t, str> {
+    let table = conversion.table(option);
+    let rules = Rules::new(conversion, option);
+    let mut output: Option<String> = None;
+
+    scan(text, &table, option, rules, |range, s, _| {
+        match output.as_mut() {
+            Some(output) => output.push_str(s),
+            None if s == &text[range.clone()] => (),
+            None => {
+                let mut owned = String::with_capacity(text.len());
+                owned.push_str(&text[..range.start]);
+                owned.push_str(s);
+                output = Some(owned);
+            }
+        }
+    });
+    output.map_or(Cow::Borrowed(text), Cow::Owned)
+}
+
+/// Convert strings with the conversion kind into the writer
+///
+/// Writing stops at the first error.
+pub(crate) fn run_to<W: fmt::Write>(
+    conversion: Conversion,
+    out: &mut W,
+    text: &str,
+    option: &ConvOption,
+) -> fmt::Result {
+    let table = conversion.table(option);
+    let rules = Rules::new(conversion, option);
+    let mut result = Ok(());
+    scan(text, &table, option, rules, |_, s, _| {
+        if result.is_ok() {
+            result = out.write_str(s);
+        }
+    });
+    result
+}
+
+/// Convert strings with the conversion kind and the prebuilt table
+pub(crate) fn run_with(
+    conversion: Conversion,
+    table: &Table,
+    text: &str,
+    option: &ConvOption,
+) -> ConvResult {
+    let rules = Rules::new(conversion, option);
+    convert_counted(text, table, option, rules)
+}
+
+/// Rules which are applied while scanning strings
+#[derive(Debug, Default, Clone, Copy)]
+struct Rules {
+    /// Compose kana and a following voiced consonant mark(゛, ゜, U+3099 or U+309A)
+    compose_mark: bool,
+    /// Merge half-width katakana and a following voiced consonant mark(ﾞ or ﾟ)
+    merge_half_mark: bool,
+    /// Replace prolonged sound marks(ー) with vowels in hiragana(`true`) or katakana(`false`)
+    long_vowel: Option<bool>,
+}
+
+impl Rules {
+    fn new(conversion: Conversion, option: &ConvOption) -> Rules {
+        let long_vowel = match conversion {
+            _ if !option.long_vowel => None,
+            Conversion::Kata2Hira => Some(true),
+            Conversion::Hira2Kata => Some(false),
+            _ => None,
+        };
+        Rules {
+            compose_mark: option.compose_mark,
+            merge_half_mark: conversion == Conversion::H2z
+                && Target::from(option).contains(Target::KANA)
+                && !option.exclude.contains(Target::KANA),
+            long_vowel,
+        }
+    }
+
+    /// Merge a pair of characters into one character
+    fn merge(&self, c: char, next: char) -> Option<char> {
+        let voiced: &[(char, char)] = match next {
+            '゛' | '\u{3099}' if self.compose_mark => &MAP_VOICED,
+            '゜' | '\u{309a}' if self.compose_mark => &MAP_SEMI_VOICED,
+            _ => &[],
+        };
+        if let Some((_, v)) = voiced.iter().find(|(base, _)| *base == c) {
+            return Some(*v);
+        }
+        if self.merge_half_mark && (next == 'ﾞ' || next == 'ﾟ') {
+            return MAP_KANA
+                .iter()
+                .find(|(half, _)| half.starts_with(c) && half.ends_with(next))
+                .and_then(|(_, full)| full.chars().next());
+        }
+        None
+    }
+
+    /// A vowel which replaces a prolonged sound mark after `prev`
+    fn vowel(&self, prev: char) -> Option<char> {
+        let hiragana = self.long_vowel?;
+        KANA_VOWELS
+            .iter()
+            .find(|(kana, _, _)| kana.contains(prev))
+            .map(|(_, h, k)| if hiragana { *h } else { *k })
+    }
+}
+
+/// Scan strings and emit each character(or merged pair) with its converted strings
+///
+/// `emit` receives a byte range of `text`, converted strings,
+/// and whether any table or rule was applied to the range.
+/// Ignored characters(including ones out of non-empty `only` and in `ignore_pattern`) are emitted
+/// as they are, and a pair which contains any ignored character is not merged.
+/// Zero-width characters between a pair are dropped when the pair is merged.
+fn scan<F>(text: &str, table: &Table, option: &ConvOption, rules: Rules, emit: F)
+where
+    F: FnMut(Range<usize>, &str, bool),
+{
+    scan_after(text, table, option, rules, None, emit)
+}
+
+/// Scan strings which follow converted strings ending with `prev`
+///
+/// See `scan` for details.
+fn scan_after<F>(
+    text: &str,
+    table: &Table,
+    option: &ConvOption,
+    rules: Rules,
+    mut prev: Option<char>,
+    mut emit: F,
+) where
+    F: FnMut(Range<usize>, &str, bool),
+{
+    let ignore = option.ignore_set();
+    let regions = option.ignored_regions(text);
+    let is_ignored = |i: usize, c: char| {
+        let k = regions.partition_point(|r| r.end <= i);
+        regions.get(k).is_some_and(|r| r.start <= i) || option.is_ignored_in(&ignore, c)
+    };
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mark = chars
+            .clone()
+            .find(|(i, next)| !ZERO_WIDTH.contains(next) || is_ignored(*i, *next))
+            .filter(|(_, next)| matches!(next, '゛' | '゜' | '\u{3099}' | '\u{309a}' | 'ﾞ' | 'ﾟ'))
+            .filter(|(i, next)| !is_ignored(start, c) && !is_ignored(*i, *next));
+        let merged = mark.and_then(|(_, next)| rules.merge(c, next));
+        let (range, c) = match (merged, mark) {
+            (Some(m), Some((i, next))) => {
+                chars.by_ref().find(|(j, _)| *j == i);
+                (start..i + next.len_utf8(), m)
+            }
+            _ => (start..start + c.len_utf8(), c),
+        };
+
+        let mut buf = [0; 4];
+        let vowel = match (c, prev) {
+            ('ー', Some(p)) if !is_ignored(start, c) => rules.vowel(p),
+            _ => None,
+        };
+        let (output, converted) = match (vowel, table.get(&(c as u32))) {
+            (Some(v), _) => (&*v.encode_utf8(&mut buf), true),
+            (None, Some(s)) if !is_ignored(start, c) => (s.as_str(), true),
+            _ if merged.is_some() => (&*c.encode_utf8(&mut buf), true),
+            _ => (&text[range.clone()], false),
+        };
+
+        if rules.long_vowel.is_some() {
+            prev = output.chars().last().or(prev);
+        }
+        emit(range, output, converted);
+    }
+}
+
+/// Convert strings refers conversion table and option settings
+fn convert(text: &str, table: &Table, option: &ConvOption) -> String {
+    convert_counted(text, table, option, Rules::default()).output
+}
+
+/// Convert strings refers conversion table and option settings,
+/// and count characters which were replaced
+///
+/// A merged pair of characters is counted as one character.
+fn convert_counted(text: &str, table: &Table, option: &ConvOption, rules: Rules) -> ConvResult {
+    let mut output = String::with_capacity(text.len());
+    let mut count = 0;
+
+    scan(text, table, option, rules, |_, s, converted| {
+        output.push_str(s);
+        if converted {
+            count += 1;
+        }
+    });
+
+    ConvResult {
+        output,
+        converted: count,
+    }
+}
+
+/// Convert strings refers conversion table and option settings,
+/// and pair byte ranges of the source with those of converted strings
+fn convert_spans(
+    text: &str,
+    table: &Table,
+    option: &ConvOption,
+    rules: Rules,
+    prev: Option<char>,
+) -> (String, Vec<Span>) {
+    let mut output = String::with_capacity(text.len());
+    let mut spans = Vec::new();
+
+    scan_after(text, table, option, rules, prev, |range, s, _| {
+        let start = output.len();
+        output.push_str(s);
+        spans.push((range, start..output.len()));
+    });
+
+    (output, spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conv_table::*;
+    use crate::Fallback;
+    use alloc::format;
+    use alloc::vec;
+
+    macro_rules! strings {
+        ($($x:expr), *) => {{
+            let mut concat = String::new();
+            $(
+                concat = format!("{}{}", concat, $x.join(""));
+            )*
+            concat
+        }};
+    }
+
+    #[test]
+    fn test_hira2kata() {
+        let before = strings!(HIRAGANA);
+        let after = strings!(FULL_KANA);
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(hira2kata(&before, option), after);
+    }
+
+    #[test]
+    fn test_hira2kata_with_ignore() {
+        let before = strings!(HIRAGANA);
+        let option = ConvOption {
+            ignore: &before,
+            ..Default::default()
+        };
+        assert_eq!(hira2kata(&before, option), before);
+    }
+
+    #[test]
+    fn test_hira2hkata() {
+        let before = strings!(HIRAGANA);
+        let after = strings!(HALF_KANA);
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(hira2hkata(&before, option), after);
+    }
+
+    #[test]
+    fn test_hira2hkata_with_ignore() {
+        let before = strings!(HIRAGANA);
+        let option = ConvOption {
+            ignore: &before,
+            ..Default::default()
+        };
+        assert_eq!(hira2hkata(&before, option), before);
+    }
+
+    #[test]
+    fn test_kata2hira() {
+        let before = strings!(FULL_KANA);
+        let after = strings!(HIRAGANA);
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(kata2hira(&before, option), after);
+    }
+
+    #[test]
+    fn test_kata2hira_with_ignore() {
+        let before = strings!(FULL_KANA);
+        let option = ConvOption {
+            ignore: &before,
+            ..Default::default()
+        };
+        assert_eq!(kata2hira(&before, option), before);
+    }
+
+    #[test]
+    fn test_h2z_all() {
+        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let after = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let option = ConvOption {
+            ascii: true,
+            digit: true,
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z(&before, option), after);
+    }
+
+    #[test]
+    fn test_h2z_option_all() {
+        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let after = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        assert_eq!(h2z(&before, ConvOption::all()), after);
+    }
+
+    #[test]
+    fn test_h2z_ascii() {
+        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let after = strings!(FULL_ASCII, HALF_DIGIT, HALF_KANA);
+        let option = ConvOption {
+            ascii: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z(&before, option), after);
+    }
+
+    #[test]
+    fn test_h2z_ascii_and_digits() {
+        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let after = strings!(FULL_ASCII, FULL_DIGIT, HALF_KANA);
+        let option = ConvOption {
+            ascii: true,
+            digit: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z(&before, option), after);
+    }
+
+    #[test]
+    fn test_h2z_ascii_and_kana() {
+        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let after = strings!(FULL_ASCII, HALF_DIGIT, FULL_KANA);
+        let option = ConvOption {
+            ascii: true,
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z(&before, option), after);
+    }
+
+    #[test]
+    fn test_h2z_digits() {
+        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let after = strings!(HALF_ASCII, FULL_DIGIT, HALF_KANA);
+        let option = ConvOption {
+            digit: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z(&before, option), after);
+    }
+
+    #[test]
+    fn test_h2z_digits_and_kana() {
+        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let after = strings!(HALF_ASCII, FULL_DIGIT, FULL_KANA);
+        let option = ConvOption {
+            digit: true,
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z(&before, option), after);
+    }
+
+    #[test]
+    fn test_h2z_kana() {
+        let before = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let after = strings!(HALF_ASCII, HALF_DIGIT, FULL_KANA);
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z(&before, option), after);
+    }
+
+    #[test]
+    fn test_z2h_all() {
+        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let after = strings!(HALF_ASCII, HALF_DIGIT, HALF_KANA);
+        let option = ConvOption {
+            ascii: true,
+            digit: true,
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h(&before, option), after);
+    }
+
+    #[test]
+    fn test_z2h_option_none() {
+        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let after = strings!(FULL_ASCII, FULL_DIGIT, HALF_KANA);
+        assert_eq!(z2h(&before, ConvOption::none()), after);
+    }
+
+    #[test]
+    fn test_z2h_ascii() {
+        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let after = strings!(HALF_ASCII, FULL_DIGIT, FULL_KANA);
+        let option = ConvOption {
+            ascii: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h(&before, option), after);
+    }
+
+    #[test]
+    fn test_z2h_ascii_and_digits() {
+        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let after = strings!(HALF_ASCII, HALF_DIGIT, FULL_KANA);
+        let option = ConvOption {
+            ascii: true,
+            digit: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h(&before, option), after);
+    }
+
+    #[test]
+    fn test_z2h_ascii_and_kana() {
+        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let after = strings!(HALF_ASCII, FULL_DIGIT, HALF_KANA);
+        let option = ConvOption {
+            ascii: true,
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h(&before, option), after);
+    }
+
+    #[test]
+    fn test_z2h_digits() {
+        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let after = strings!(FULL_ASCII, HALF_DIGIT, FULL_KANA);
+        let option = ConvOption {
+            digit: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h(&before, option), after);
+    }
+
+    #[test]
+    fn test_z2h_digits_and_kana() {
+        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let after = strings!(FULL_ASCII, HALF_DIGIT, HALF_KANA);
+        let option = ConvOption {
+            digit: true,
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h(&before, option), after);
+    }
+
+    #[test]
+    fn test_z2h_kana() {
+        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
+        let after = strings!(FULL_ASCII, FULL_DIGIT, HALF_KANA);
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h(&before, option), after);
+    }
+
+    #[test]
+    fn test_z2h_counted_ascii_disabled() {
+        let before = strings!(FULL_ASCII);
+        let option = ConvOption {
+            digit: true,
+            ..Default::default()
+        };
+        let result = z2h_counted(&before, option);
+        assert_eq!(result.output, before);
+        assert_eq!(result.converted, 0);
+    }
+
+    #[test]
+    fn test_z2h_counted_with_ignore() {
+        let option = ConvOption {
+            ascii: true,
+            digit: true,
+            ignore: "Ａ１",
+            ..Default::default()
+        };
+        let result = z2h_counted("ＡＢ１２あ", option);
+        assert_eq!(result.output, "ＡB１2あ");
+        assert_eq!(result.converted, 2);
+    }
+
+    #[test]
+    fn test_h2z_counted_voiced_kana() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        let result = h2z_counted("ｶﾞｷﾞｸあ", option);
+        assert_eq!(result.output, "ガギクあ");
+        assert_eq!(result.converted, 3);
+    }
+
+    #[test]
+    fn test_hira2kata_counted() {
+        let option = ConvOption {
+            ignore: "い",
+            ..Default::default()
+        };
+        let result = hira2kata_counted("あいうA", option);
+        assert_eq!(result.output, "アいウA");
+        assert_eq!(result.converted, 2);
+    }
+
+    #[test]
+    fn test_merge_table_overrides_win() {
+        let table = merge_table(Method::KanaToHira.table(), &[('ア', "ａ"), ('Ａ', "a")]);
+        assert_eq!(table.get(&('ア' as u32)).unwrap(), "ａ");
+        assert_eq!(table.get(&('イ' as u32)).unwrap(), "い");
+        assert_eq!(table.get(&('Ａ' as u32)).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_convert_with_ignore() {
+        let table = merge_table(Method::KanaToHira.table(), &[('ア', "ａ")]);
+        let option = ConvOption {
+            ignore: "アイ",
+            ..Default::default()
+        };
+        assert_eq!(convert_with("アイウ", &table, &option), "アイう");
+    }
+
+    #[test]
+    fn test_z2h_in_place_reuse_buffer() {
+        let option = ConvOption::all();
+        let mut buf = String::with_capacity(64);
+        let capacity = buf.capacity();
+
+        for (before, after) in [("ＡＢＣ", "ABC"), ("ガギグ", "ｶﾞｷﾞｸﾞ"), ("１", "1")]
+        {
+            buf.clear();
+            buf.push_str(before);
+            z2h_in_place(&mut buf, option);
+            assert_eq!(buf, after);
+            assert_eq!(buf.capacity(), capacity);
+        }
+    }
+
+    #[test]
+    fn test_z2h_in_place_unchanged() {
+        let option = ConvOption::all();
+        let mut buf = String::from("abc");
+        let ptr = buf.as_ptr();
+        z2h_in_place(&mut buf, option);
+        assert_eq!(buf, "abc");
+        assert_eq!(buf.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_h2z_in_place() {
+        let option = ConvOption::all();
+        let mut buf = String::from("ｶﾞA1");
+        h2z_in_place(&mut buf, option);
+        assert_eq!(buf, "ガＡ１");
+    }
+
+    #[test]
+    fn test_round_trip_full_kana() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        for kana in FULL_KANA.iter() {
+            assert_eq!(h2z(z2h(kana, option), option), *kana);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_half_kana() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        for kana in HALF_KANA.iter() {
+            assert_eq!(z2h(h2z(kana, option), option), *kana);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_with_ignore() {
+        let option = ConvOption {
+            kana: true,
+            ignore: "ｶﾟ",
+            ..Default::default()
+        };
+        assert_eq!(h2z("ｶﾞｷﾞﾊﾟ", option), "ｶﾞギハﾟ");
+        assert_eq!(z2h(h2z("ｶﾞ", option), option), "ｶﾞ");
+    }
+
+    #[test]
+    fn test_round_trip_not_composed() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z(z2h("カﾞ", option), option), "ガ");
+        assert_eq!(h2z(z2h("カ゛", option), option), "カ゛");
+        assert_eq!(h2z(z2h("カ\u{3099}", option), option), "カ\u{3099}");
+    }
+
+    #[test]
+    fn test_z2h_preserve_space() {
+        let option = ConvOption {
+            ascii: true,
+            preserve_space: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("Ａ　Ｂ", option), "A　B");
+    }
+
+    #[test]
+    fn test_h2z_preserve_space() {
+        let option = ConvOption {
+            ascii: true,
+            preserve_space: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("A B", option), "Ａ Ｂ");
+    }
+
+    #[test]
+    fn test_compose_mark() {
+        let option = ConvOption {
+            compose_mark: true,
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("カ゛ハ゜", option), "がぱ");
+        assert_eq!(hira2kata("か゛は゜う゛", option), "ガパヴ");
+        assert_eq!(z2h("カ゛ハ゜", option), "ｶﾞﾊﾟ");
+    }
+
+    #[test]
+    fn test_compose_combining_mark() {
+        let option = ConvOption {
+            compose_mark: true,
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("カ\u{3099}ハ\u{309a}", option), "がぱ");
+        assert_eq!(hira2kata("か\u{3099}は\u{309a}", option), "ガパ");
+        assert_eq!(z2h("カ\u{3099}", option), "ｶﾞ");
+        assert_eq!(hira2kata("あ\u{3099}", option), "ア\u{3099}");
+
+        let option = ConvOption::default();
+        assert_eq!(kata2hira("カ\u{3099}", option), "か\u{3099}");
+    }
+
+    #[test]
+    fn test_decompose_marks() {
+        let option = ConvOption::default();
+        let combining = decompose_marks("がぱヴヷゞあ", VoicedMark::Combining, option);
+        assert_eq!(
+            combining,
+            "か\u{3099}は\u{309a}ウ\u{3099}ワ\u{3099}ゝ\u{3099}あ"
+        );
+        let spacing = decompose_marks("ガパ", VoicedMark::Spacing, option);
+        assert_eq!(spacing, "カ゛ハ゜");
+
+        let option = ConvOption {
+            compose_mark: true,
+            ..Default::default()
+        };
+        assert_eq!(hira2kata(&combining, option), "ガパヴヷヾア");
+    }
+
+    #[test]
+    fn test_decompose_marks_with_ignore() {
+        let option = ConvOption {
+            ignore: "が",
+            ..Default::default()
+        };
+        assert_eq!(
+            decompose_marks("がぎ", VoicedMark::Spacing, option),
+            "がき゛"
+        );
+    }
+
+    #[test]
+    fn test_compose_mark_no_composition() {
+        let option = ConvOption {
+            compose_mark: true,
+            ..Default::default()
+        };
+        assert_eq!(hira2kata("あ゛か゜゛", option), "ア゛カ゜゛");
+        assert_eq!(kata2hira("゛カ", option), "゛か");
+    }
+
+    #[test]
+    fn test_compose_mark_with_ignore() {
+        let option = ConvOption {
+            compose_mark: true,
+            ignore: "カ",
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("カ゛キ゛", option), "カ゛ぎ");
+    }
+
+    #[test]
+    fn test_compose_mark_disabled() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("カ゛", option), "か゛");
+    }
+
+    #[test]
+    fn test_borrowed_option() {
+        let option = ConvOption::all();
+        assert_eq!(h2z("ｱｲｳ123", option), h2z("ｱｲｳ123", option));
+        assert_eq!(z2h("アイウ１２３", option), "ｱｲｳ123");
+        assert_eq!(kata2hira_counted("アイウ", option).converted, 3);
+        assert_eq!(Conversion::Hira2Kata.apply("あいう", option), "アイウ");
+    }
+
+    #[test]
+    fn test_batch_matches_single() {
+        let items = ["ＡＢＣ１２３", "ｱｲｳｶﾞ", "あいうえお", "アイウエオ", ""];
+        let option = ConvOption::all();
+        let expected = |f: fn(&'static str, ConvOption<'static>) -> String| {
+            items.iter().map(|t| f(t, option)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(hira2kata_batch(items, option), expected(hira2kata));
+        assert_eq!(hira2hkata_batch(items, option), expected(hira2hkata));
+        assert_eq!(kata2hira_batch(items, option), expected(kata2hira));
+        assert_eq!(h2z_batch(items, option), expected(h2z));
+        assert_eq!(z2h_batch(items, option), expected(z2h));
+    }
+
+    #[test]
+    fn test_hira2kata_iteration_marks() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(hira2kata("ゝゞ", option), "ヽヾ");
+        assert_eq!(hira2kata("ヽヾ", option), "ヽヾ");
+        assert_eq!(hira2kata("カゝ", option), "カヽ");
+        assert_eq!(hira2kata("すゞ", option), "スヾ");
+    }
+
+    #[test]
+    fn test_kata2hira_iteration_marks() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("ヽヾ", option), "ゝゞ");
+        assert_eq!(kata2hira("ゝゞ", option), "ゝゞ");
+        assert_eq!(kata2hira("かヽ", option), "かゝ");
+        assert_eq!(kata2hira("スヾ", option), "すゞ");
+    }
+
+    #[test]
+    fn test_kata2hira_long_vowel() {
+        let option = ConvOption {
+            long_vowel: true,
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("コーヒー", option), "こおひい");
+        assert_eq!(kata2hira("ラーメン", option), "らあめん");
+        assert_eq!(kata2hira("シュークリーム", option), "しゅうくりいむ");
+        assert_eq!(kata2hira("ゴーール", option), "ごおおる");
+    }
+
+    #[test]
+    fn test_long_vowel_small_hiragana() {
+        let option = ConvOption {
+            long_vowel: true,
+            ..Default::default()
+        };
+        assert_eq!(hira2kata("ゕーゖー", option), "ヵアヶエ");
+        assert_eq!(kata2hira("ゕーゖー", option), "ゕあゖえ");
+    }
+
+    #[test]
+    fn test_kata2hira_long_vowel_kept() {
+        let option = ConvOption {
+            long_vowel: true,
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("ーア", option), "ーあ");
+        assert_eq!(kata2hira("ンー", option), "んー");
+        assert_eq!(kata2hira("ッー", option), "っー");
+        assert_eq!(kata2hira("Aー", option), "Aー");
+    }
+
+    #[test]
+    fn test_hira2kata_long_vowel() {
+        let option = ConvOption {
+            long_vowel: true,
+            ..Default::default()
+        };
+        assert_eq!(hira2kata("らーめん", option), "ラアメン");
+    }
+
+    #[test]
+    fn test_long_vowel_disabled_or_ignored() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("コーヒー", option), "こーひー");
+
+        let option = ConvOption {
+            long_vowel: true,
+            ignore: "ー",
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("コーヒー", option), "こーひー");
+    }
+
+    /// The previous implementation of `convert` which joins strings of each character
+    fn convert_joined(text: &str, table: &Table, ignore: &str) -> String {
+        let ignore = ignore.chars().map(|c| c as u32).collect::<Vec<_>>();
+
+        text.chars()
+            .map(|c| {
+                let ord = c as u32;
+                match table.get(&ord) {
+                    Some(s) if !ignore.contains(&ord) => s.to_string(),
+                    _ => c.to_string(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("")
+    }
+
+    #[test]
+    fn test_convert_same_as_joined() {
+        let text = strings!(
+            FULL_ASCII, HALF_ASCII, FULL_DIGIT, HALF_DIGIT, FULL_KANA, HALF_KANA, HIRAGANA
+        );
+        let text = format!("{}漢字😀\u{fe0f}", text);
+        let ignore = "Ａaアｱあ";
+        let option = ConvOption {
+            ignore,
+            ..ConvOption::all()
+        };
+        for conversion in Conversion::ALL.iter() {
+            let table = conversion.table(&option);
+            assert_eq!(
+                convert(&text, &table, &option),
+                convert_joined(&text, &table, ignore)
+            );
+        }
+    }
+
+    #[test]
+    fn test_z2h_spans() {
+        let option = ConvOption {
+            ascii: true,
+            kana: true,
+            ignore: "Ｂ",
+            ..Default::default()
+        };
+        let text = "ＡＢガ漢a";
+        let (converted, spans) = z2h_spans(text, option);
+        assert_eq!(converted, "AＢｶﾞ漢a");
+        assert_eq!(
+            spans,
+            vec![
+                (0..3, 0..1),
+                (3..6, 1..4),
+                (6..9, 4..10),
+                (9..12, 10..13),
+                (12..13, 13..14)
+            ]
+        );
+
+        // Spans cover both strings without gaps
+        let rebuilt = spans
+            .iter()
+            .map(|(_, o)| &converted[o.clone()])
+            .collect::<String>();
+        assert_eq!(rebuilt, converted);
+        let source = spans
+            .iter()
+            .map(|(s, _)| &text[s.clone()])
+            .collect::<String>();
+        assert_eq!(source, text);
+    }
+
+    #[test]
+    fn test_h2z_spans_merged_pair() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        let (converted, spans) = h2z_spans("ｶﾞa", option);
+        assert_eq!(converted, "ガa");
+        assert_eq!(spans, vec![(0..6, 0..3), (6..7, 3..4)]);
+    }
+
+    #[test]
+    fn test_z2h_punct() {
+        let option = ConvOption {
+            punct: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("！？（Ａ１）「ア」　", option), "!?(Ａ１)「ア」　");
+    }
+
+    #[test]
+    fn test_h2z_punct() {
+        let option = ConvOption {
+            punct: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("!?(A1)｢ｱ｣ ", option), "！？（A1）｢ｱ｣ ");
+    }
+
+    #[test]
+    fn test_z2h_letter() {
+        let option = ConvOption {
+            letter: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("Ａｂ＠＃１アー　", option), "Ab＠＃１アー　");
+    }
+
+    #[test]
+    fn test_h2z_letter() {
+        let option = ConvOption {
+            letter: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("Ab@#1ｱ ", option), "Ａｂ@#1ｱ ");
+    }
+
+    #[test]
+    fn test_space() {
+        let option = ConvOption {
+            space: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("Ａ　Ｂ", option), "Ａ Ｂ");
+        assert_eq!(h2z("A B", option), "A　B");
+
+        let option = ConvOption {
+            letter: true,
+            ..option
+        };
+        assert_eq!(z2h("Ａ　Ｂ", option), "A B");
+
+        let option = ConvOption {
+            preserve_space: true,
+            ..option
+        };
+        assert_eq!(z2h("Ａ　Ｂ", option), "A　B");
+    }
+
+    #[test]
+    fn test_exclude() {
+        let option = ConvOption {
+            ascii: true,
+            exclude: Target::LETTER,
+            ..Default::default()
+        };
+        assert_eq!(z2h("ＡＢ（　）", option), "ＡＢ( )");
+        assert_eq!(h2z("AB( )", option), "AB（　）");
+
+        let option = ConvOption {
+            kana: true,
+            exclude: Target::SMALL_KANA,
+            ..Default::default()
+        };
+        assert_eq!(z2h("キャット", option), "ｷャッﾄ");
+        assert_eq!(h2z("ｷｬｯﾄ", option), "キｬｯト");
+        assert_eq!(kata2hira("キャット", option), "きャッと");
+        assert_eq!(hira2kata("きゃっと", option), "キゃっト");
+
+        let option = ConvOption {
+            kana: true,
+            exclude: Target::KANA,
+            ..Default::default()
+        };
+        assert_eq!(h2z("ｶﾞｷ", option), "ｶﾞｷ");
+        assert_eq!(z2h("ガキ", option), "ガキ");
+    }
+
+    #[test]
+    fn test_as_ref_inputs() {
+        let option = ConvOption::all();
+        let owned = String::from("ｱｲｳ");
+        let cow: alloc::borrow::Cow<str> = alloc::borrow::Cow::Borrowed("ｱｲｳ");
+        assert_eq!(h2z(&owned, option), "アイウ");
+        assert_eq!(h2z(cow, option), "アイウ");
+        assert_eq!(kata2hira(h2z(owned, option), option), "あいう");
+        assert_eq!(Conversion::H2z.apply(String::from("ABC"), option), "ＡＢＣ");
+    }
+
+    #[test]
+    fn test_convert_into() {
+        let option = ConvOption::all();
+        let converter = Converter::new(Conversion::H2z, option);
+        let mut buf = String::from("> ");
+        converter.convert_into(&mut buf, "ｶﾞA");
+        assert_eq!(buf, "> ガＡ");
+
+        let mut bytes = Vec::new();
+        Conversion::Kata2Hira.apply_into(&mut bytes, "アイ", option);
+        Conversion::Kata2Hira.apply_into(&mut bytes, String::from("ウ"), option);
+        assert_eq!(bytes, "あいう".as_bytes());
+    }
+
+    #[test]
+    fn test_cow() {
+        let option = ConvOption::all();
+        assert!(matches!(
+            h2z_cow("ＡＢ漢字", option),
+            Cow::Borrowed("ＡＢ漢字")
+        ));
+        assert!(matches!(kata2hira_cow("", option), Cow::Borrowed("")));
+        assert!(matches!(z2h_cow("ヮ", option), Cow::Borrowed("ヮ")));
+        assert_eq!(h2z_cow("漢ｶﾞ字", option), "漢ガ字");
+        assert_eq!(z2h_cow("漢ガ字", option), "漢ｶﾞ字");
+        assert_eq!(hira2kata_cow("かな", option), "カナ");
+        assert_eq!(hira2hkata_cow("かな", option), "ｶﾅ");
+        assert_eq!(Conversion::Kata2Hira.apply_cow("アa", option), "あa");
+    }
+
+    #[test]
+    fn test_fallback() {
+        let option = ConvOption {
+            fallback: Fallback::Replace('\u{fffd}'),
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("アヷ漢゠", option), "あ\u{fffd}漢゠");
+        assert_eq!(hira2kata("あゟ", option), "ア\u{fffd}");
+        assert_eq!(z2h("アヷ", option), "ｱ\u{fffd}");
+        assert_eq!(h2z("ｱヷ", option), "アヷ");
+
+        let option = ConvOption {
+            ignore: "ヷ",
+            ..option
+        };
+        assert_eq!(kata2hira("ヷヺ", option), "ヷ\u{fffd}");
+
+        let option = ConvOption {
+            fallback: Fallback::Drop,
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("アヷイ", option), "あい");
+        assert_eq!(kata2hira_counted("アヷイ", option).converted, 3);
+    }
+
+    #[test]
+    fn test_fallback_error() {
+        let option = ConvOption {
+            fallback: Fallback::Error,
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("アヷ", option), "あヷ");
+        let error = Conversion::Kata2Hira.try_apply("アヷヺ", option);
+        assert_eq!(
+            error.map_err(|e| e.to_string()),
+            Err("`ヷ` has no mapping by `k2h`".to_string())
+        );
+        assert_eq!(
+            Conversion::Z2h.try_apply("アイ", option),
+            Ok("ｱｲ".to_string())
+        );
+        assert_eq!(
+            Conversion::Kata2Hira
+                .try_apply_counted("アイう", option)
+                .map(|r| r.converted),
+            Ok(2)
+        );
+        assert_eq!(
+            try_hira2kata("あゟ", option).map_err(|e| e.char()),
+            Err('ゟ')
+        );
+        assert_eq!(try_hira2hkata("あい", option), Ok("ｱｲ".to_string()));
+        assert_eq!(
+            try_z2h(
+                "ヷ",
+                ConvOption {
+                    kana: true,
+                    ..option
+                }
+            )
+            .map_err(|e| e.char()),
+            Err('ヷ')
+        );
+
+        let option = ConvOption {
+            ignore: "ヷ",
+            ..option
+        };
+        assert_eq!(
+            Conversion::Kata2Hira.try_apply("アヷ", option),
+            Ok("あヷ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ainu_kana() {
+        let option = ConvOption {
+            ainu_kana: true,
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("カㇺイ", option), "ｶﾑｲ");
+        assert_eq!(kata2hira("カㇺイ", option), "かむい");
+        assert_eq!(h2z("ｶㇺｲ", option), "カㇺイ");
+        assert_eq!(kata2hira("カㇺイ", ConvOption::default()), "かㇺい");
+
+        let option = ConvOption {
+            exclude: Target::SMALL_KANA,
+            ..option
+        };
+        assert_eq!(z2h("カㇺイ", option), "ｶㇺｲ");
+    }
+
+    #[test]
+    fn test_exclude_prolonged_mark() {
+        let option = ConvOption {
+            kana: true,
+            exclude: Target::PROLONGED_MARK,
+            ..Default::default()
+        };
+        assert_eq!(z2h("ケーキ", option), "ｹーｷ");
+        assert_eq!(h2z("ｹｰｷ", option), "ケｰキ");
+        assert_eq!(hira2hkata("けーき", option), "ｹーｷ");
+    }
+
+    #[test]
+    fn test_z2h_letter_and_punct() {
+        let option = ConvOption {
+            letter: true,
+            punct: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("Ａ＠１　", option), "A@１　");
+    }
+
+    #[test]
+    fn test_h2z_trailing_mark() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("ｶ", option), "カ");
+        assert_eq!(h2z("ｱｶﾞ", option), "アガ");
+        assert_eq!(h2z("ｱｶﾟ", option), "アカﾟ");
+    }
+
+    #[test]
+    fn test_h2z_lone_mark() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("ﾞ", option), "ﾞ");
+        assert_eq!(h2z("ﾟ", option), "ﾟ");
+        assert_eq!(h2z("ﾞｶ", option), "ﾞカ");
+        assert_eq!(h2z("aﾞ", option), "aﾞ");
+    }
+
+    #[test]
+    fn test_h2z_mark_after_unvoiceable() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("ｱﾞ", option), "アﾞ");
+        assert_eq!(h2z("ﾏﾟｲ", option), "マﾟイ");
+        assert_eq!(h2z("ｶﾞﾞ", option), "ガﾞ");
+        assert_eq!(h2z_counted("ｱﾞ", option).converted, 1);
+    }
+
+    #[test]
+    fn test_h2z_mark_default_option() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(h2z("ｶﾞﾊﾟ", option), "ガパ");
+    }
+
+    #[test]
+    fn test_h2z_mark_after_zero_width() {
+        let option = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("ｶ\u{200d}ﾞ", option), "ガ");
+        assert_eq!(h2z("ﾊ\u{200b}\u{feff}ﾟﾝ", option), "パン");
+        assert_eq!(h2z("ｱ\u{200d}ﾞ", option), "ア\u{200d}ﾞ");
+        assert_eq!(h2z("ｶ\u{200d}", option), "カ\u{200d}");
+        assert_eq!(h2z_spans("ｶ\u{200d}ﾞ", option).1, vec![(0..9, 0..3)]);
+
+        let option = ConvOption {
+            ignore: "\u{200d}",
+            ..option
+        };
+        assert_eq!(h2z("ｶ\u{200d}ﾞ", option), "カ\u{200d}ﾞ");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_ignore_pattern() {
+        let brackets = regex::Regex::new(r"\[[^\]]*\]").unwrap();
+        let option = ConvOption::build()
+            .enable_ascii()
+            .enable_kana()
+            .ignore_pattern(&brackets)
+            .finalize();
+        assert_eq!(h2z("a[b]c", option), "ａ[b]ｃ");
+        assert_eq!(h2z("ｶ[ﾞ]ｶﾞ", option), "カ[ﾞ]ガ");
+        assert_eq!(h2z_counted("[ab]", option).converted, 0);
+        assert_eq!(z2h("ｘ［ｙ］", option), "x[y]");
+    }
+
+    #[test]
+    fn test_compose_mark_after_zero_width() {
+        let option = ConvOption {
+            compose_mark: true,
+            ..Default::default()
+        };
+        assert_eq!(hira2kata("か\u{200d}゛", option), "ガ");
+    }
+
+    #[test]
+    fn test_z2h_enclosed() {
+        let option = ConvOption {
+            enclosed: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("①から⑳、⑴、⒉、㊿", option), "1から20、(1)、2.、50");
+        assert_eq!(h2z("①1", option), "①1");
+    }
+
+    #[test]
+    fn test_z2h_square() {
+        let option = ConvOption {
+            square: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("３㌔、５㎝、㍻", option), "３キロ、５cm、平成");
+        assert_eq!(h2z("㌔cm", option), "㌔cm");
+    }
+
+    #[test]
+    fn test_z2h_numeral() {
+        let option = ConvOption {
+            numeral: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("第Ⅲ章、ⅻ", option), "第III章、xii");
+        assert_eq!(h2z("III", option), "III");
+    }
+
+    #[test]
+    fn test_currency() {
+        let option = ConvOption {
+            currency: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("￥100＄5￠￡￦", option), "¥100$5¢£₩");
+        assert_eq!(h2z("¥100$5¢£₩", option), "￥100＄5￠￡￦");
+    }
+
+    #[test]
+    fn test_z2h_wide_symbol() {
+        let option = ConvOption {
+            wide_symbol: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("￥100−20→※☆", option), "¥100-20￫※☆");
+        assert_eq!(z2h("－", option), "－");
+    }
+
+    #[test]
+    fn test_h2z_wide_symbol() {
+        let option = ConvOption {
+            wide_symbol: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("¥100-20￫", option), "￥100-20→");
+    }
+
+    #[test]
+    fn test_small2large() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        let before = concat!(
+            "ぁぃぅぇぉっゃゅょゎゕゖ",
+            "ァィゥェォッャュョヮヵヶ",
+            "ㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ",
+            "ｧｨｩｪｫｯｬｭｮ"
+        );
+        let after = concat!(
+            "あいうえおつやゆよわかけ",
+            "アイウエオツヤユヨワカケ",
+            "クシストヌハヒフヘホムラリルレロ",
+            "ｱｲｳｴｵﾂﾔﾕﾖ"
+        );
+        assert_eq!(small2large(before, option), after);
+    }
+
+    #[test]
+    fn test_small2large_keep_others() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        assert_eq!(small2large("ーｰあア漢ａ", option), "ーｰあア漢ａ");
+    }
+
+    #[test]
+    fn test_small2large_with_ignore() {
+        let option = ConvOption {
+            ignore: "ゃャ",
+            ..Default::default()
+        };
+        assert_eq!(small2large("きゃキャっ", option), "きゃキャつ");
+    }
+
+    #[test]
+    fn test_large2small() {
+        let option = ConvOption::default();
+        let before = "あいうえおつやゆよわかけアイウエオツヤユヨワカケｱｲｳｴｵﾂﾔﾕﾖクー";
+        let after = "ぁぃぅぇぉっゃゅょゎゕゖァィゥェォッャュョヮヵヶｧｨｩｪｫｯｬｭｮクー";
+        assert_eq!(large2small(before, option), after);
+        assert_eq!(small2large(after, option), before);
+    }
+
+    #[cfg(feature = "hentaigana")]
+    #[test]
+    fn test_hentaigana2hira() {
+        let option = ConvOption::default();
+        assert_eq!(
+            hentaigana2hira("\u{1b002}\u{1b019}\u{1b11e}", option),
+            "あかん"
+        );
+        assert_eq!(hentaigana2hira("\u{1b0b5}あ", option), "へあ");
+
+        let wi = HENTAIGANA
+            .iter()
+            .find(|(_, k)| *k == 'ゐ')
+            .unwrap()
+            .0
+            .to_string();
+        let option = ConvOption {
+            modern_kana: true,
+            ..Default::default()
+        };
+        assert_eq!(hentaigana2hira(&wi, option), "い");
+
+        let option = ConvOption {
+            ignore: "\u{1b0b5}",
+            ..Default::default()
+        };
+        assert_eq!(hentaigana2hira("\u{1b0b5}\u{1b002}", option), "\u{1b0b5}あ");
+    }
+
+    #[test]
+    fn test_ignore_range() {
+        let option = ConvOption::build()
+            .enable_kana()
+            .ignore_ranges(&['イ'..='エ'])
+            .finalize();
+        assert_eq!(z2h("アィイウエォオ", option), "ｱｨイウエｫｵ");
+    }
+
+    #[test]
+    fn test_ignore_range_multiple() {
+        let option = ConvOption::build()
+            .enable_ascii()
+            .ignore("Ｚ")
+            .ignore_ranges(&['Ａ'..='Ｃ', 'Ｂ'..='Ｄ', 'ｘ'..='ｘ'])
+            .finalize();
+        assert_eq!(z2h("ＡＢＣＤＥｗｘｙＺ", option), "ＡＢＣＤEwｘyＺ");
+    }
+
+    #[test]
+    fn test_ignore_range_not_merged() {
+        let option = ConvOption::build()
+            .enable_kana()
+            .ignore_ranges(&['\u{ff9e}'..='\u{ff9f}'])
+            .finalize();
+        assert_eq!(h2z("ｶﾞﾊﾟ", option), "カﾞハﾟ");
+    }
+
+    #[test]
+    fn test_z2h_wave_dash() {
+        let option = ConvOption {
             ascii: true,
+            wave_dash: true,
             ..Default::default()
         };
-        assert_eq!(z2h(&before, option), after);
+        assert_eq!(z2h("〜～~ー", option), "~~~ー");
     }
 
     #[test]
-    fn test_z2h_ascii_and_digits() {
-        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
-        let after = strings!(HALF_ASCII, HALF_DIGIT, FULL_KANA);
+    fn test_h2z_wave_dash() {
         let option = ConvOption {
             ascii: true,
-            digit: true,
+            wave_dash: true,
             ..Default::default()
         };
-        assert_eq!(z2h(&before, option), after);
+        assert_eq!(h2z("〜～~ｰ", option), "～～～ｰ");
     }
 
     #[test]
-    fn test_z2h_ascii_and_kana() {
-        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
-        let after = strings!(HALF_ASCII, FULL_DIGIT, HALF_KANA);
+    fn test_wave_dash_without_width_conversion() {
+        let option = ConvOption {
+            wave_dash: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("〜～~ー", option), "～～~ｰ");
+        assert_eq!(kata2hira("ア〜ー", option), "あ～ー");
+    }
+
+    #[test]
+    fn test_wave_dash_disabled() {
         let option = ConvOption {
             ascii: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("〜～", option), "〜~");
+    }
+
+    #[test]
+    fn test_to_same_buffer() {
+        let option = ConvOption::all();
+        let mut buf = String::new();
+        hira2kata_to(&mut buf, "あいう", option).unwrap();
+        z2h_to(&mut buf, "アイウ１", option).unwrap();
+        assert_eq!(buf, "アイウｱｲｳ1");
+    }
+
+    #[test]
+    fn test_to_matches_single() {
+        let text = "ＡＢＣ１２３ｱｲｳｶﾞあいうえおアイウエオ";
+        let option = ConvOption::all();
+        let mut buf = String::new();
+        hira2hkata_to(&mut buf, text, option).unwrap();
+        kata2hira_to(&mut buf, text, option).unwrap();
+        h2z_to(&mut buf, text, option).unwrap();
+        let expected = format!(
+            "{}{}{}",
+            hira2hkata(text, option),
+            kata2hira(text, option),
+            h2z(text, option)
+        );
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_to_error() {
+        struct Full(usize);
+
+        impl fmt::Write for Full {
+            fn write_str(&mut self, _: &str) -> fmt::Result {
+                self.0 += 1;
+                if self.0 > 2 {
+                    return Err(fmt::Error);
+                }
+                Ok(())
+            }
+        }
+
+        let mut out = Full(0);
+        assert_eq!(
+            z2h_to(&mut out, "アイウエオ", ConvOption::all()),
+            Err(fmt::Error)
+        );
+        assert_eq!(out.0, 3);
+    }
+
+    #[test]
+    fn test_vu() {
+        let option = ConvOption {
             kana: true,
             ..Default::default()
         };
-        assert_eq!(z2h(&before, option), after);
+        assert_eq!(hira2kata("ゔぁいおりん", option), "ヴァイオリン");
+        assert_eq!(kata2hira("ヴァイオリン", option), "ゔぁいおりん");
+        assert_eq!(hira2hkata("ゔ", option), "ｳﾞ");
+        assert_eq!(h2z("ｳﾞ", option), "ヴ");
+        assert_eq!(z2h("ヴ", option), "ｳﾞ");
     }
 
     #[test]
-    fn test_z2h_digits() {
-        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
-        let after = strings!(FULL_ASCII, HALF_DIGIT, FULL_KANA);
+    fn test_vu_chained() {
         let option = ConvOption {
-            digit: true,
+            kana: true,
             ..Default::default()
         };
-        assert_eq!(z2h(&before, option), after);
+        let hira = kata2hira(h2z("ｳﾞｪﾙﾃﾞｨ", option), option);
+        assert_eq!(hira, "ゔぇるでぃ");
+        let half = hira2hkata(&hira, option);
+        assert_eq!(half, "ｳﾞｪﾙﾃﾞｨ");
+        assert_eq!(kata2hira(h2z(&half, option), option), hira);
     }
 
     #[test]
-    fn test_z2h_digits_and_kana() {
-        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
-        let after = strings!(FULL_ASCII, HALF_DIGIT, HALF_KANA);
+    fn test_converter_reuse() {
+        let option = ConvOption::all();
+        let inputs = ["ＡＢＣ１２３", "ｱｲｳｶﾞ", "あいうえお", "アイウエオ", ""];
+        for conversion in Conversion::ALL.iter() {
+            let converter = Converter::new(*conversion, option);
+            for text in inputs.iter() {
+                assert_eq!(converter.convert(text), conversion.apply(text, option));
+            }
+        }
+    }
+
+    #[test]
+    fn test_converter_counted() {
         let option = ConvOption {
-            digit: true,
             kana: true,
             ..Default::default()
         };
-        assert_eq!(z2h(&before, option), after);
+        let converter = Converter::new(Conversion::H2z, option);
+        assert_eq!(converter.conversion(), Conversion::H2z);
+        assert!(converter.option().kana);
+
+        let result = converter.convert_counted("ｶﾞｷﾞA");
+        assert_eq!(result.output, "ガギA");
+        assert_eq!(result.converted, 2);
+        assert_eq!(converter.convert_counted("ﾊﾟ").converted, 1);
     }
 
     #[test]
-    fn test_z2h_kana() {
-        let before = strings!(FULL_ASCII, FULL_DIGIT, FULL_KANA);
-        let after = strings!(FULL_ASCII, FULL_DIGIT, HALF_KANA);
+    fn test_astral_and_variation_selectors() {
+        let texts = [
+            "😀🍣𠮷",
+            "\u{1f1ef}\u{1f1f5}\u{1f1fa}\u{1f1f8}",
+            "➖\u{fe0f}☺\u{fe0e}♥\u{fe0f}",
+            "👨\u{200d}👩\u{200d}👧",
+            "葛\u{e0100}",
+        ];
+        let option = ConvOption {
+            compose_mark: true,
+            long_vowel: true,
+            wave_dash: true,
+            wide_symbol: true,
+            ..ConvOption::all()
+        };
+        for conversion in Conversion::ALL.iter() {
+            for text in texts.iter() {
+                assert_eq!(conversion.apply(text, option), *text);
+                let (converted, spans) = conversion.apply_spans(text, &option);
+                assert_eq!(converted, *text);
+                assert!(spans.iter().all(|(s, o)| s == o));
+            }
+        }
+    }
+
+    #[test]
+    fn test_variation_selector_after_converted() {
+        let option = ConvOption {
+            wide_symbol: true,
+            ..ConvOption::all()
+        };
+        assert_eq!(z2h("Ａ\u{fe0f}→\u{fe0f}", option), "A\u{fe0f}￫\u{fe0f}");
+        assert_eq!(h2z("ｶ\u{fe0f}ﾞ", option), "カ\u{fe0f}ﾞ");
+    }
+
+    #[test]
+    fn test_z2h_only() {
+        let option = ConvOption::build()
+            .enable_ascii()
+            .enable_digit()
+            .enable_kana()
+            .only("０１２")
+            .finalize();
+        assert_eq!(z2h("０１２３ＡＢアイ", option), "012３ＡＢアイ");
+    }
+
+    #[test]
+    fn test_only_with_ignore() {
+        let option = ConvOption::build()
+            .enable_digit()
+            .only("０１２３４５６７８９")
+            .ignore("５")
+            .finalize();
+        assert_eq!(z2h("０５９Ａ", option), "0５9Ａ");
+    }
+
+    #[test]
+    fn test_only_pair() {
+        let option = ConvOption::build().enable_kana().only("ｶﾞ").finalize();
+        assert_eq!(h2z("ｶﾞｷﾞﾊﾟ", option), "ガｷﾞﾊﾟ");
+        let option = ConvOption::build().enable_kana().only("ｶ").finalize();
+        assert_eq!(h2z("ｶﾞ", option), "カﾞ");
+    }
+
+    #[test]
+    fn test_only_out_of_table() {
+        let option = ConvOption::build().enable_ascii().only("漢Ａ").finalize();
+        assert_eq!(z2h("漢ＡＢ", option), "漢AＢ");
+    }
+
+    #[test]
+    fn test_kata2hira_with_unknown() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        let (converted, unknown) = kata2hira_with_unknown("アヸ漢゠ヿアヸ", option);
+        assert_eq!(converted, "あヸ漢゠ヿあヸ");
+        assert_eq!(unknown, vec!['ヸ', '゠', 'ヿ']);
+        assert_eq!(converted, kata2hira("アヸ漢゠ヿアヸ", option));
+    }
+
+    #[test]
+    fn test_kata2hira_with_unknown_ignore() {
+        let option = ConvOption {
+            ignore: "ヸア",
+            ..Default::default()
+        };
+        let (converted, unknown) = kata2hira_with_unknown("アヸヹ", option);
+        assert_eq!(converted, "アヸヹ");
+        assert_eq!(unknown, vec!['ヹ']);
+    }
+
+    #[test]
+    fn test_hira2kata_with_unknown() {
+        let option = ConvOption {
+            ..Default::default()
+        };
+        let (converted, unknown) = hira2kata_with_unknown("あゟいア", option);
+        assert_eq!(converted, "アゟイア");
+        assert_eq!(unknown, vec!['ゟ']);
+        assert!(hira2kata_with_unknown("あいう", option).1.is_empty());
+    }
+
+    #[test]
+    fn test_h2z_kana_punct() {
+        let option = ConvOption {
+            kana_punct: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("｢あ｣｡ｱ､ｶﾞ･", option), "「あ」。ｱ、ｶﾞ・");
+    }
+
+    #[test]
+    fn test_z2h_kana_punct() {
+        let option = ConvOption {
+            kana_punct: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("「ア」。、・ー", option), "｢ア｣｡､･ー");
+    }
+
+    #[test]
+    fn test_prolonged_mark() {
+        let option = ConvOption {
+            kana_punct: true,
+            prolonged_mark: true,
+            ..Default::default()
+        };
+        assert_eq!(z2h("「アー」。、・ー", option), "｢アｰ｣｡､･ｰ");
+        assert_eq!(h2z("｢ｱｰ｣｡､･", option), "「ｱー」。、・");
+    }
+
+    #[test]
+    fn test_middle_dot() {
+        let option = ConvOption {
+            ascii: true,
+            middle_dot: true,
+            ..Default::default()
+        };
+        assert_eq!(h2z("A·B•C･D", option), "Ａ・Ｂ・Ｃ・Ｄ");
+        assert_eq!(z2h("A·B•C･D・", option), "A・B・C・D・");
+    }
+
+    #[test]
+    fn test_middle_dot_with_kana() {
         let option = ConvOption {
             kana: true,
+            middle_dot: true,
             ..Default::default()
         };
-        assert_eq!(z2h(&before, option), after);
+        assert_eq!(z2h("A·B•C･D・", option), "A･B･C･D･");
+        assert_eq!(h2z("A·B•C･D・", option), "A・B・C・D・");
+        assert_eq!(z2h_counted("･", option).converted, 0);
+    }
+
+    #[test]
+    fn test_middle_dot_kana_conversion() {
+        let option = ConvOption {
+            middle_dot: true,
+            ..Default::default()
+        };
+        assert_eq!(kata2hira("ジョン·スミス", option), "じょん・すみす");
+        assert_eq!(hira2hkata("じょん•すみす", option), "ｼﾞｮﾝ･ｽﾐｽ");
     }
 }
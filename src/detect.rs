@@ -0,0 +1,468 @@
+//! Detect kinds of characters.
+use crate::analyze;
+use crate::conv_table::{
+    AINU_KANA, FULL_ASCII, FULL_DIGIT, FULL_KANA_PUNCT, HALF_ASCII, HALF_DIGIT, HALF_KANA,
+    HIRAGANA_LETTERS, KANJI, KATAKANA_LETTERS,
+};
+
+/// Kinds of characters, returned by `classify`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CharKind {
+    /// Hiragana letters
+    Hiragana,
+    /// Katakana(full-width) letters and prolonged sound mark
+    Katakana,
+    /// Katakana(half-width), including voiced consonant marks and punctuation
+    HalfKana,
+    /// Kanji
+    Kanji,
+    /// ASCII(full-width) except digits, including ideographic space
+    FullAscii,
+    /// ASCII(half-width) except digits, including space
+    HalfAscii,
+    /// Digits(half-width)
+    Digit,
+    /// Digits(full-width)
+    FullDigit,
+    /// Other characters
+    Other,
+}
+
+impl CharKind {
+    /// All kinds in the order of variants
+    pub(crate) const ALL: [CharKind; 9] = [
+        CharKind::Hiragana,
+        CharKind::Katakana,
+        CharKind::HalfKana,
+        CharKind::Kanji,
+        CharKind::FullAscii,
+        CharKind::HalfAscii,
+        CharKind::Digit,
+        CharKind::FullDigit,
+        CharKind::Other,
+    ];
+}
+
+/// Classify a character by the conversion tables
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{classify, CharKind};
+///
+/// assert_eq!(CharKind::Hiragana, classify('あ'));
+/// assert_eq!(CharKind::HalfKana, classify('ｱ'));
+/// assert_eq!(CharKind::FullDigit, classify('１'));
+/// assert_eq!(CharKind::HalfAscii, classify(' '));
+/// assert_eq!(CharKind::Other, classify('・'));
+/// ```
+pub fn classify(c: char) -> CharKind {
+    if contains(&FULL_DIGIT, c) {
+        CharKind::FullDigit
+    } else if contains(&HALF_DIGIT, c) {
+        CharKind::Digit
+    } else if contains(&FULL_ASCII, c) {
+        CharKind::FullAscii
+    } else if contains(&HALF_ASCII, c) {
+        CharKind::HalfAscii
+    } else if is_hiragana(c) {
+        CharKind::Hiragana
+    } else if is_katakana(c) {
+        CharKind::Katakana
+    } else if is_half_kana(c) {
+        CharKind::HalfKana
+    } else if is_kanji(c) {
+        CharKind::Kanji
+    } else {
+        CharKind::Other
+    }
+}
+
+/// The most frequent kind of characters and its ratio to all characters
+///
+/// Returns `None` for empty strings. Kinds of the same count are preferred
+/// in the order of `CharKind` variants.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{dominant, h2z, CharKind, ConvOption};
+///
+/// assert_eq!(Some((CharKind::HalfKana, 0.75)), dominant("ｱｲｳA"));
+/// assert_eq!(Some((CharKind::Hiragana, 0.5)), dominant("あ漢"));
+/// assert_eq!(None, dominant(""));
+///
+/// let text = "ひらがなだけ";
+/// if dominant(text).map_or(false, |(kind, _)| kind == CharKind::HalfKana) {
+///     h2z(text, ConvOption::all());
+/// }
+/// ```
+pub fn dominant(text: &str) -> Option<(CharKind, f64)> {
+    let stats = analyze(text);
+    let total = stats.total();
+    let kind = CharKind::ALL.into_iter().reduce(|max, kind| {
+        if stats.get(kind) > stats.get(max) {
+            kind
+        } else {
+            max
+        }
+    })?;
+    (total > 0).then(|| (kind, stats.get(kind) as f64 / total as f64))
+}
+
+/// Split strings into maximal runs of the same kind of characters
+///
+/// Combining voiced consonant marks(U+3099, U+309A) and variation selectors
+/// belong to the run of the characters before them.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{segment, CharKind};
+///
+/// let runs = segment("ｶﾞｲﾄﾞは漢字ABC１２").collect::<Vec<_>>();
+/// assert_eq!(
+///     vec![
+///         (CharKind::HalfKana, "ｶﾞｲﾄﾞ"),
+///         (CharKind::Hiragana, "は"),
+///         (CharKind::Kanji, "漢字"),
+///         (CharKind::HalfAscii, "ABC"),
+///         (CharKind::FullDigit, "１２"),
+///     ],
+///     runs
+/// );
+/// ```
+pub fn segment(text: &str) -> impl Iterator<Item = (CharKind, &str)> {
+    let mut rest = text;
+    core::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        let kind = classify(chars.next()?.1);
+        let end = chars
+            .find(|(_, c)| !is_combining(*c) && classify(*c) != kind)
+            .map_or(rest.len(), |(i, _)| i);
+        let (run, tail) = rest.split_at(end);
+        rest = tail;
+        Some((kind, run))
+    })
+}
+
+/// Whether the character is combined with the character before it
+fn is_combining(c: char) -> bool {
+    matches!(c, '\u{3099}' | '\u{309a}' | '\u{fe00}'..='\u{fe0f}' | '\u{e0100}'..='\u{e01ef}')
+}
+
+/// Whether the character is a hiragana letter
+///
+/// Marks shared with katakana(e.g. `ー` and `。`) are not hiragana.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::is_hiragana;
+///
+/// assert!(is_hiragana('あ'));
+/// assert!(is_hiragana('ゝ'));
+/// assert!(!is_hiragana('ア'));
+/// assert!(!is_hiragana('ー'));
+/// ```
+pub fn is_hiragana(c: char) -> bool {
+    HIRAGANA_LETTERS.iter().any(|r| r.contains(&c))
+}
+
+/// Whether the character is a full-width katakana letter or a prolonged sound mark(`ー`)
+///
+/// Small katakana of Ainu(e.g. `ㇰ`) are katakana.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::is_katakana;
+///
+/// assert!(is_katakana('ア'));
+/// assert!(is_katakana('ー'));
+/// assert!(is_katakana('ㇰ'));
+/// assert!(!is_katakana('ｱ'));
+/// assert!(!is_katakana('・'));
+/// ```
+pub fn is_katakana(c: char) -> bool {
+    c == 'ー'
+        || KATAKANA_LETTERS.iter().any(|r| r.contains(&c))
+        || AINU_KANA.iter().any(|(small, _)| *small == c)
+}
+
+/// Whether the character is a half-width katakana, including voiced consonant marks
+/// and half-width punctuation(e.g. `｡` and `･`)
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::is_half_kana;
+///
+/// assert!(is_half_kana('ｱ'));
+/// assert!(is_half_kana('ﾞ'));
+/// assert!(is_half_kana('｡'));
+/// assert!(!is_half_kana('ア'));
+/// ```
+pub fn is_half_kana(c: char) -> bool {
+    ('\u{ff61}'..='\u{ff9f}').contains(&c) && HALF_KANA.iter().any(|s| s.contains(c))
+}
+
+/// Whether the character is a kanji
+///
+/// Kanji are CJK unified ideographs(including extensions), compatibility ideographs,
+/// iteration mark(`々`) and ideographic number zero(`〇`).
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::is_kanji;
+///
+/// assert!(is_kanji('漢'));
+/// assert!(is_kanji('々'));
+/// assert!(!is_kanji('あ'));
+/// ```
+pub fn is_kanji(c: char) -> bool {
+    KANJI.iter().any(|r| r.contains(&c))
+}
+
+/// Whether the character is a full-width ASCII, including digits and ideographic space
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::is_full_ascii;
+///
+/// assert!(is_full_ascii('Ａ'));
+/// assert!(is_full_ascii('１'));
+/// assert!(is_full_ascii('　'));
+/// assert!(!is_full_ascii('A'));
+/// ```
+pub fn is_full_ascii(c: char) -> bool {
+    contains(&FULL_ASCII, c) || contains(&FULL_DIGIT, c)
+}
+
+/// Whether all characters are full-width
+///
+/// Full-width characters are hiragana, katakana, kanji, full-width ASCII(including digits
+/// and ideographic space) and Japanese punctuation(e.g. `。` and `・`).
+/// Returns `true` for empty strings.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::is_all_zenkaku;
+///
+/// assert!(is_all_zenkaku("カタカナ、ひらがな。漢字ＡＢＣ１２３"));
+/// assert!(!is_all_zenkaku("ｶﾀｶﾅ"));
+/// assert!(!is_all_zenkaku("ＡＢＣ 123"));
+/// ```
+pub fn is_all_zenkaku(text: &str) -> bool {
+    text.chars().all(|c| {
+        matches!(
+            classify(c),
+            CharKind::Hiragana
+                | CharKind::Katakana
+                | CharKind::Kanji
+                | CharKind::FullAscii
+                | CharKind::FullDigit
+        ) || contains(&FULL_KANA_PUNCT, c)
+    })
+}
+
+/// Whether all characters are hiragana letters or prolonged sound marks(`ー`)
+///
+/// Returns `true` for empty strings.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::is_all_hiragana;
+///
+/// assert!(is_all_hiragana("ひらがなー"));
+/// assert!(!is_all_hiragana("ひらがなカナ"));
+/// ```
+pub fn is_all_hiragana(text: &str) -> bool {
+    text.chars().all(|c| c == 'ー' || is_hiragana(c))
+}
+
+/// Whether all characters are full-width katakana letters or prolonged sound marks(`ー`)
+///
+/// Returns `true` for empty strings.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::is_all_katakana;
+///
+/// assert!(is_all_katakana("カタカナー"));
+/// assert!(!is_all_katakana("ｶﾀｶﾅ"));
+/// assert!(!is_all_katakana("カタカナ "));
+/// ```
+pub fn is_all_katakana(text: &str) -> bool {
+    text.chars().all(is_katakana)
+}
+
+/// Whether any character is a half-width katakana
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::contains_half_kana;
+///
+/// assert!(contains_half_kana("カタｶﾅ"));
+/// assert!(!contains_half_kana("カタカナ"));
+/// ```
+pub fn contains_half_kana(text: &str) -> bool {
+    text.chars().any(is_half_kana)
+}
+
+/// Whether any character is a kanji
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::contains_kanji;
+///
+/// assert!(contains_kanji("ひらがなと漢字"));
+/// assert!(!contains_kanji("ひらがな"));
+/// ```
+pub fn contains_kanji(text: &str) -> bool {
+    text.chars().any(is_kanji)
+}
+
+/// Whether a table has an entry of the character
+fn contains(table: &[&str], c: char) -> bool {
+    table.iter().any(|s| s.chars().eq(Some(c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conv_table::{FULL_KANA, HIRAGANA};
+    use crate::{h2z, hira2kata, kata2hira, z2h, ConvOption};
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_exclusive() {
+        let predicates: [fn(char) -> bool; 5] = [
+            is_hiragana,
+            is_katakana,
+            is_half_kana,
+            is_kanji,
+            is_full_ascii,
+        ];
+        for c in "あゔゟアヴヮヿㇿーｱｦﾟ｡漢々〇𠮷Ａ１｀　".chars() {
+            assert_eq!(predicates.iter().filter(|p| p(c)).count(), 1, "{}", c);
+        }
+        for c in "・。\u{3099}A1 ~😀".chars() {
+            assert!(predicates.iter().all(|p| !p(c)), "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let kinds = [
+            ('ゔ', CharKind::Hiragana),
+            ('ー', CharKind::Katakana),
+            ('ㇰ', CharKind::Katakana),
+            ('ﾞ', CharKind::HalfKana),
+            ('𠮷', CharKind::Kanji),
+            ('　', CharKind::FullAscii),
+            ('~', CharKind::HalfAscii),
+            ('9', CharKind::Digit),
+            ('９', CharKind::FullDigit),
+            ('。', CharKind::Other),
+            ('😀', CharKind::Other),
+        ];
+        for (c, kind) in kinds {
+            assert_eq!(classify(c), kind, "{}", c);
+        }
+    }
+
+    #[test]
+    fn test_dominant() {
+        assert_eq!(dominant("アイウえお"), Some((CharKind::Katakana, 0.6)));
+        assert_eq!(dominant("12ab"), Some((CharKind::HalfAscii, 0.5)));
+        assert_eq!(dominant("😀"), Some((CharKind::Other, 1.0)));
+        assert_eq!(dominant(""), None);
+    }
+
+    #[test]
+    fn test_segment() {
+        let runs = segment("か\u{3099}カ\u{309a}葛\u{e0100}\u{fe0f}!ａ").collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            [
+                (CharKind::Hiragana, "か\u{3099}"),
+                (CharKind::Katakana, "カ\u{309a}"),
+                (CharKind::Kanji, "葛\u{e0100}\u{fe0f}"),
+                (CharKind::HalfAscii, "!"),
+                (CharKind::FullAscii, "ａ"),
+            ]
+        );
+        assert_eq!(segment("").next(), None);
+        assert_eq!(
+            segment("\u{3099}\u{3099}").collect::<Vec<_>>(),
+            [(CharKind::Other, "\u{3099}\u{3099}")]
+        );
+        let text = "あいうｱｲｳ漢字😀ABC";
+        assert_eq!(segment(text).map(|(_, run)| run).collect::<String>(), text);
+    }
+
+    #[test]
+    fn test_string_predicates() {
+        assert!(is_all_zenkaku(""));
+        assert!(is_all_zenkaku("「ア・イ」　ゔ々"));
+        assert!(!is_all_zenkaku("ア･イ"));
+        assert!(!is_all_zenkaku("ｱ😀"));
+        assert!(is_all_hiragana("") && is_all_katakana(""));
+        assert!(!is_all_hiragana("ひら　がな"));
+        assert!(is_all_katakana("ㇰヴヷ"));
+        assert!(!is_all_katakana("カナ・カナ"));
+        assert!(contains_half_kana("Aﾞ"));
+        assert!(!contains_half_kana(""));
+        assert!(contains_kanji("𠮷野家"));
+        assert!(!contains_kanji("ABC"));
+
+        let option = ConvOption::all();
+        let text = "ｶﾀｶﾅ ABC 123";
+        assert!(!is_all_zenkaku(text));
+        assert!(is_all_zenkaku(&h2z(text, option)));
+        assert!(!contains_half_kana(&h2z(text, option)));
+        assert!(is_all_katakana(&h2z("ｶﾀｶﾅｰ", option)));
+        assert!(is_all_hiragana(&kata2hira("カタカナー", option)));
+    }
+
+    #[test]
+    fn test_agrees_with_tables() {
+        for (hira, kata) in HIRAGANA
+            .iter()
+            .zip(FULL_KANA.iter())
+            .filter(|(h, k)| h != k)
+        {
+            let (hira, kata) = (hira.chars().next().unwrap(), kata.chars().next().unwrap());
+            assert_eq!(is_hiragana(hira), is_katakana(kata), "{}", hira);
+        }
+        for (full, half) in FULL_ASCII.iter().zip(HALF_ASCII.iter()) {
+            assert!(!is_full_ascii(half.chars().next().unwrap()));
+            assert!(is_full_ascii(full.chars().next().unwrap()));
+        }
+        for half in HALF_DIGIT.iter() {
+            assert!(!is_full_ascii(half.chars().next().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_conversion() {
+        let option = ConvOption::all();
+        let text = "あいうアイウ";
+        assert!(hira2kata(text, option).chars().all(is_katakana));
+        assert!(kata2hira(text, option).chars().all(is_hiragana));
+        assert!(z2h(text, option)
+            .chars()
+            .filter(|c| !is_hiragana(*c))
+            .all(is_half_kana));
+        assert!(h2z("ABC123 ", option).chars().all(is_full_ascii));
+    }
+}
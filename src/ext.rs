@@ -0,0 +1,109 @@
+//! Extension methods of conversions on strings.
+use crate::{h2z, hira2hkata, hira2kata, kata2hira, z2h, AsKanaOption, AsWidthOption, ConvOption};
+use alloc::string::String;
+
+/// Conversions which can be called as methods of `str`
+///
+/// Methods without `_default` are the same as the functions of the same names,
+/// and ones with `_default` convert with `ConvOption::default()`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{ConvOption, JapaneseExt};
+///
+/// let option = ConvOption::all();
+/// assert_eq!("アイウ", "あいう".hira2kata(&option));
+/// assert_eq!("ＡＢアイ", "ABｱｲ".h2z(&option));
+/// assert_eq!("ABアイ", "ABｱｲ".h2z_default());
+/// ```
+pub trait JapaneseExt {
+    /// Convert from half-width to full-width
+    fn h2z<'a>(&self, option: impl AsWidthOption<'a>) -> String;
+
+    /// Convert from full-width to half-width
+    fn z2h<'a>(&self, option: impl AsWidthOption<'a>) -> String;
+
+    /// Convert from hiragana to full-width katakana
+    fn hira2kata<'a>(&self, option: impl AsKanaOption<'a>) -> String;
+
+    /// Convert from hiragana to half-width katakana
+    fn hira2hkata<'a>(&self, option: impl AsKanaOption<'a>) -> String;
+
+    /// Convert from full-width katakana to hiragana
+    fn kata2hira<'a>(&self, option: impl AsKanaOption<'a>) -> String;
+
+    /// Convert from half-width to full-width with the default option
+    fn h2z_default(&self) -> String {
+        self.h2z(ConvOption::default())
+    }
+
+    /// Convert from full-width to half-width with the default option
+    fn z2h_default(&self) -> String {
+        self.z2h(ConvOption::default())
+    }
+
+    /// Convert from hiragana to full-width katakana with the default option
+    fn hira2kata_default(&self) -> String {
+        self.hira2kata(ConvOption::default())
+    }
+
+    /// Convert from hiragana to half-width katakana with the default option
+    fn hira2hkata_default(&self) -> String {
+        self.hira2hkata(ConvOption::default())
+    }
+
+    /// Convert from full-width katakana to hiragana with the default option
+    fn kata2hira_default(&self) -> String {
+        self.kata2hira(ConvOption::default())
+    }
+}
+
+impl JapaneseExt for str {
+    fn h2z<'a>(&self, option: impl AsWidthOption<'a>) -> String {
+        h2z(self, option)
+    }
+
+    fn z2h<'a>(&self, option: impl AsWidthOption<'a>) -> String {
+        z2h(self, option)
+    }
+
+    fn hira2kata<'a>(&self, option: impl AsKanaOption<'a>) -> String {
+        hira2kata(self, option)
+    }
+
+    fn hira2hkata<'a>(&self, option: impl AsKanaOption<'a>) -> String {
+        hira2hkata(self, option)
+    }
+
+    fn kata2hira<'a>(&self, option: impl AsKanaOption<'a>) -> String {
+        kata2hira(self, option)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_same_as_functions() {
+        let text = "ＡＢ１ｱｲあいアイ";
+        let option = ConvOption::all();
+        assert_eq!(text.h2z(option), h2z(text, option));
+        assert_eq!(text.z2h(option), z2h(text, option));
+        assert_eq!(text.hira2kata(option), hira2kata(text, option));
+        assert_eq!(text.hira2hkata(option), hira2hkata(text, option));
+        assert_eq!(text.kata2hira(option), kata2hira(text, option));
+    }
+
+    #[test]
+    fn test_default() {
+        let text = "ＡＢ１ｱｲあいアイ".to_string();
+        assert_eq!(text.h2z_default(), "ＡＢ１アイあいアイ");
+        assert_eq!(text.z2h_default(), "ＡＢ１ｱｲあいｱｲ");
+        assert_eq!(text.hira2kata_default(), "ＡＢ１ｱｲアイアイ");
+        assert_eq!(text.hira2hkata_default(), "ＡＢ１ｱｲｱｲアイ");
+        assert_eq!(text.kata2hira_default(), "ＡＢ１ｱｲあいあい");
+    }
+}
@@ -1,20 +1,157 @@
 //! # kelp
 //!
 //! This is a porting from [jaconv(Python)](https://github.com/ikegami-yukino/jaconv).
+//!
+//! ## Features
+//!
+//! - `std` (enabled by default)
+//!     - Use `std`. Without this feature, kelp is built as `no_std` with `alloc`.
+//! - `cli` (enabled by default)
+//!     - Build `kelp-cli`. This feature depends on `clap` and `serde_json`.
+//! - `rayon`
+//!     - Convert many strings in parallel with `Conversion::par_apply_batch`.
+//! - `hentaigana`
+//!     - Convert hentaigana to hiragana with `hentaigana2hira`.
+//! - `regex`
+//!     - Pass regions matching a regular expression through with `ConvOption::ignore_pattern`.
+//!
+//! ## Unmapped characters
+//!
+//! Characters which are not in conversion tables, including astral-plane
+//! characters(e.g. emoji), flag sequences and variation selectors(U+FE00 to U+FE0F),
+//! are kept byte-for-byte by every conversion.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod analyze;
+pub mod conv_option;
 mod conv_table;
+mod conversion;
 mod convert;
+mod detect;
+mod ext;
+mod normalize;
+mod number;
+mod pipeline;
+mod roman;
+mod stream;
+mod typed_option;
+mod wareki;
 
+pub use analyze::analyze;
+pub use analyze::ScriptStats;
+pub use conv_option::CharPredicate;
+pub use conv_option::CharSet;
+pub use conv_option::ConvOption;
+pub use conv_option::ConvOptionBuilder;
+pub use conv_option::Fallback;
+pub use conv_option::MiddleDotPolicy;
+pub use conv_option::OptionError;
+#[cfg(feature = "regex")]
+pub use conv_option::Pattern;
+pub use conv_option::VoicedKanaPolicy;
+pub use conv_table::Table;
+pub use conv_table::Target;
+pub use conversion::Conversion;
+pub use conversion::ParseConversionError;
+pub use conversion::UnmappedError;
+pub use convert::convert_with;
+pub use convert::decompose_marks;
 pub use convert::h2z;
+pub use convert::h2z_batch;
+pub use convert::h2z_counted;
+pub use convert::h2z_cow;
+pub use convert::h2z_in_place;
+pub use convert::h2z_spans;
+pub use convert::h2z_to;
+#[cfg(feature = "hentaigana")]
+pub use convert::hentaigana2hira;
 pub use convert::hira2hkata;
+pub use convert::hira2hkata_batch;
+pub use convert::hira2hkata_counted;
+pub use convert::hira2hkata_cow;
+pub use convert::hira2hkata_in_place;
+pub use convert::hira2hkata_spans;
+pub use convert::hira2hkata_to;
 pub use convert::hira2kata;
+pub use convert::hira2kata_batch;
+pub use convert::hira2kata_counted;
+pub use convert::hira2kata_cow;
+pub use convert::hira2kata_in_place;
+pub use convert::hira2kata_spans;
+pub use convert::hira2kata_to;
+pub use convert::hira2kata_with_unknown;
 pub use convert::kata2hira;
+pub use convert::kata2hira_batch;
+pub use convert::kata2hira_counted;
+pub use convert::kata2hira_cow;
+pub use convert::kata2hira_in_place;
+pub use convert::kata2hira_spans;
+pub use convert::kata2hira_to;
+pub use convert::kata2hira_with_unknown;
+pub use convert::large2small;
+pub use convert::merge_table;
+pub use convert::small2large;
+pub use convert::try_hira2hkata;
+pub use convert::try_hira2kata;
+pub use convert::try_kata2hira;
+pub use convert::try_z2h;
 pub use convert::z2h;
-
-/// Convert options
-#[derive(Debug, Default, Clone, Copy)]
-pub struct ConvOption<'a> {
-    pub ascii: bool,
-    pub digit: bool,
-    pub ignore: &'a str,
-    pub kana: bool,
-}
+pub use convert::z2h_batch;
+pub use convert::z2h_counted;
+pub use convert::z2h_cow;
+pub use convert::z2h_in_place;
+pub use convert::z2h_spans;
+pub use convert::z2h_to;
+pub use convert::Buffer;
+pub use convert::ConvResult;
+pub use convert::Converter;
+pub use convert::Span;
+pub use convert::VoicedMark;
+pub use detect::classify;
+pub use detect::contains_half_kana;
+pub use detect::contains_kanji;
+pub use detect::dominant;
+pub use detect::is_all_hiragana;
+pub use detect::is_all_katakana;
+pub use detect::is_all_zenkaku;
+pub use detect::is_full_ascii;
+pub use detect::is_half_kana;
+pub use detect::is_hiragana;
+pub use detect::is_kanji;
+pub use detect::is_katakana;
+pub use detect::segment;
+pub use detect::CharKind;
+pub use ext::JapaneseExt;
+pub use normalize::normalize;
+pub use normalize::unify_prolonged_marks;
+pub use normalize::unify_wave_dash;
+pub use number::digits2kanji;
+pub use number::kanji2number;
+pub use number::number2kanji;
+pub use number::number2kanji_with;
+pub use number::number2reading;
+pub use number::KanjiNumberStyle;
+pub use pipeline::Pipeline;
+pub use roman::alphabet2kana;
+pub use roman::kana2alphabet;
+pub use roman::kana2roman;
+pub use roman::roman2kana;
+pub use roman::roman2kata;
+pub use roman::RomanizationSystem;
+pub use stream::ConvertChars;
+pub use stream::ConvertedChars;
+pub use stream::ConvertingFmtWriter;
+#[cfg(feature = "std")]
+pub use stream::ConvertingReader;
+#[cfg(feature = "std")]
+pub use stream::ConvertingWriter;
+pub use typed_option::AsKanaOption;
+pub use typed_option::AsWidthOption;
+pub use typed_option::KanaOption;
+pub use typed_option::WidthOption;
+pub use wareki::date2wareki;
+pub use wareki::wareki2year;
+pub use wareki::year2wareki;
+pub use wareki::Era;
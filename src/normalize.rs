@@ -0,0 +1,361 @@
+//! Normalization of Japanese text like jaconv.
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::ops::Range;
+
+use crate::conv_table::{HYPHENS, LONG_VOWEL_MARKS, OBSOLETE_KANA, PROLONGED_MARKS, QUOTES};
+use crate::{h2z, small2large, z2h, ConvOption};
+
+/// Normalize Japanese text like `normalize` of jaconv
+///
+/// The following passes are applied in order.
+/// 1. Wave dashes and dashes(e.g. `〜`, `～`, `－`, `─`) become `ー`,
+///    lookalikes of a hyphen(e.g. `‐`, `–`, `―`, `−`) become `-`,
+///    and quotation marks `’`, `”` and `“` become `'`, `"` and ``` `` ```.
+/// 2. Half-width katakana becomes full-width katakana, and kana and a following voiced
+///    consonant mark(including combining ones) are composed.
+/// 3. Full-width ascii and digits become half-width, enclosed numbers(e.g. `①`, `⑴`)
+///    become digits, roman numerals(e.g. `Ⅻ`) become ascii letters, and squared characters
+///    (e.g. `㌔`, `㎝`) are expanded.
+/// 4. Consecutive spaces are collapsed into one.
+/// 5. Small kana become large kana(e.g. `ッ` to `ツ`) if `large_kana` is enabled,
+///    which is useful for loose matching.
+/// 6. Obsolete kana become modern ones(e.g. `ゐ` to `い`) if `modern_kana` is enabled.
+///
+/// Only ignore settings(`ignore`, `ignore_chars`, `ignore_if`, `ignore_pattern`, `ignore_ranges` and `only`),
+/// `large_kana` and `modern_kana` of `option` are used.
+/// Every pass honors the ignore settings, and `ignore_pattern` is matched against the text of each pass.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::normalize;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let normalized = normalize("ﾃﾞｰﾀ〜　ＡＢＣ１２３  x−y", option);
+/// assert_eq!("データー ABC123 x-y", normalized);
+/// ```
+pub fn normalize<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    let marks = unify_marks(text, option);
+    let kana = h2z(&marks, pass(option, true));
+    let ascii = z2h(&kana, pass(option, false));
+    let spaces = collapse_spaces(&ascii, option);
+    let large = if option.large_kana {
+        small2large(&spaces, option)
+    } else {
+        spaces
+    };
+    if option.modern_kana {
+        modernize(&large, option)
+    } else {
+        large
+    }
+}
+
+/// Convert lookalikes of a long vowel mark following kana to `ー`
+///
+/// Hyphens, dashes, box drawings, wave dashes and tildes(e.g. `-`, `−`, `―`, `─`, `━`, `〜`),
+/// and half-width `ｰ` become `ー` when they follow hiragana, katakana or another long vowel mark.
+/// The same characters elsewhere are kept as they are.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::unify_prolonged_marks;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let unified = unify_prolonged_marks("コンピュ−タ― ス〜パ--, 1-2", option);
+/// assert_eq!("コンピューター スーパーー, 1-2", unified);
+/// ```
+pub fn unify_prolonged_marks<'a>(
+    text: impl AsRef<str>,
+    option: impl Borrow<ConvOption<'a>>,
+) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    let ignore = option.ignore_set();
+    let regions = option.ignored_regions(text);
+    let mut output = String::with_capacity(text.len());
+    let mut prev: Option<char> = None;
+    for (i, c) in text.char_indices() {
+        let mark = PROLONGED_MARKS.contains(&c)
+            && !in_regions(&regions, i)
+            && !option.is_ignored_in(&ignore, c);
+        let c = match prev {
+            Some(p) if mark && is_kana(p) => 'ー',
+            _ => c,
+        };
+        output.push(c);
+        prev = Some(c);
+    }
+    output
+}
+
+/// Convert wave dash(`〜`, U+301C) and full-width tilde(`～`, U+FF5E) into `to`
+///
+/// To fold wave dash into full-width tilde within a conversion, use `wave_dash` of `ConvOption`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::unify_wave_dash;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// assert_eq!("1〜2〜3", unify_wave_dash("1〜2～3", '〜', option));
+/// assert_eq!("1～2～3", unify_wave_dash("1〜2～3", '～', option));
+/// ```
+pub fn unify_wave_dash<'a>(
+    text: impl AsRef<str>,
+    to: char,
+    option: impl Borrow<ConvOption<'a>>,
+) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    let ignore = option.ignore_set();
+    let regions = option.ignored_regions(text);
+    text.char_indices()
+        .map(|(i, c)| match c {
+            '〜' | '～' if !in_regions(&regions, i) && !option.is_ignored_in(&ignore, c) => to,
+            _ => c,
+        })
+        .collect()
+}
+
+/// Whether a byte index `i` is in one of sorted `regions`
+fn in_regions(regions: &[Range<usize>], i: usize) -> bool {
+    let k = regions.partition_point(|r| r.end <= i);
+    regions.get(k).is_some_and(|r| r.start <= i)
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c, 'ぁ'..='ゖ' | 'ゝ'..='ゞ' | 'ァ'..='ヺ' | 'ー'..='ヾ' | 'ｦ'..='ﾟ')
+}
+
+/// An option of a conversion pass which keeps ignore settings of `option`
+fn pass<'a>(option: &ConvOption<'a>, kana: bool) -> ConvOption<'a> {
+    ConvOption {
+        ascii: !kana,
+        compose_mark: kana,
+        digit: !kana,
+        enclosed: !kana,
+        numeral: !kana,
+        square: !kana,
+        kana,
+        ignore: option.ignore,
+        ignore_chars: option.ignore_chars,
+        ignore_if: option.ignore_if,
+        #[cfg(feature = "regex")]
+        ignore_pattern: option.ignore_pattern,
+        ignore_ranges: option.ignore_ranges,
+        only: option.only,
+        ..Default::default()
+    }
+}
+
+fn unify_marks(text: &str, option: &ConvOption) -> String {
+    let ignore = option.ignore_set();
+    let regions = option.ignored_regions(text);
+    let mut result = String::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        if in_regions(&regions, i) || option.is_ignored_in(&ignore, c) {
+            result.push(c);
+        } else if LONG_VOWEL_MARKS.contains(&c) {
+            result.push('ー');
+        } else if HYPHENS.contains(&c) {
+            result.push('-');
+        } else if let Some((_, quote)) = QUOTES.iter().find(|(q, _)| *q == c) {
+            result.push_str(quote);
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn modernize(text: &str, option: &ConvOption) -> String {
+    let ignore = option.ignore_set();
+    let regions = option.ignored_regions(text);
+    text.char_indices()
+        .map(|(i, c)| match OBSOLETE_KANA.iter().find(|(o, _)| *o == c) {
+            Some((_, modern)) if !in_regions(&regions, i) && !option.is_ignored_in(&ignore, c) => {
+                *modern
+            }
+            _ => c,
+        })
+        .collect()
+}
+
+fn collapse_spaces(text: &str, option: &ConvOption) -> String {
+    let ignore = option.ignore_set();
+    let regions = option.ignored_regions(text);
+    let mut output = String::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        if c == ' '
+            && output.ends_with(' ')
+            && !in_regions(&regions, i)
+            && !option.is_ignored_in(&ignore, c)
+        {
+            continue;
+        }
+        output.push(c);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_normalize_marks() {
+        let option = ConvOption::default();
+        assert_eq!(
+            normalize("ゆ〜ゆ～ゆ－ゆ─ゆ━", option),
+            "ゆーゆーゆーゆーゆー"
+        );
+        assert_eq!(normalize("a‐b‑c–d−e―f", option), "a-b-c-d-e-f");
+        assert_eq!(normalize("ゆ―", option), "ゆ-");
+    }
+
+    #[test]
+    fn test_normalize_quotes() {
+        let option = ConvOption::default();
+        assert_eq!(normalize("it’s “kelp”", option), "it's ``kelp\"");
+        let ignored = ['’'];
+        let option = ConvOption {
+            ignore_chars: &ignored,
+            ..Default::default()
+        };
+        assert_eq!(normalize("it’s “kelp”", option), "it’s ``kelp\"");
+    }
+
+    #[test]
+    fn test_normalize_kana() {
+        let option = ConvOption::default();
+        assert_eq!(normalize("ｶﾞｷﾞﾊﾟｰﾃｨｰ", option), "ガギパーティー");
+        assert_eq!(normalize("か\u{3099}ハ\u{309a}", option), "がパ");
+    }
+
+    #[test]
+    fn test_normalize_ascii() {
+        let option = ConvOption::default();
+        assert_eq!(normalize("ＡＢＣ！１２３", option), "ABC!123");
+        assert_eq!(normalize("①⑳⑴㊿", option), "120(1)50");
+        assert_eq!(normalize("３㌔、５㎝", option), "3キロ、5cm");
+        assert_eq!(normalize("第Ⅻ章ⅳ", option), "第XII章iv");
+    }
+
+    #[test]
+    fn test_normalize_spaces() {
+        let option = ConvOption::default();
+        assert_eq!(normalize("a 　  b　　c", option), "a b c");
+    }
+
+    #[test]
+    fn test_normalize_large_kana() {
+        let option = ConvOption::default();
+        assert_eq!(normalize("ｷｬｯﾁ", option), "キャッチ");
+
+        let option = ConvOption {
+            large_kana: true,
+            ..Default::default()
+        };
+        assert_eq!(normalize("ｷｬｯﾁ", option), "キヤツチ");
+    }
+
+    #[test]
+    fn test_normalize_modern_kana() {
+        let option = ConvOption::default();
+        assert_eq!(normalize("ゐゑヰヱ", option), "ゐゑヰヱ");
+
+        let option = ConvOption {
+            modern_kana: true,
+            ..Default::default()
+        };
+        assert_eq!(normalize("ゐゑヰヱ", option), "いえイエ");
+    }
+
+    #[test]
+    fn test_normalize_ignore() {
+        let option = ConvOption {
+            ignore: "～ｱ　",
+            ..Default::default()
+        };
+        assert_eq!(normalize("～ｱｲＡ　　", option), "～ｱイA　　");
+
+        let option = ConvOption {
+            only: "ｶﾞ",
+            ..Default::default()
+        };
+        assert_eq!(normalize("ｶﾞｷﾞ〜Ａ", option), "ガｷﾞ〜Ａ");
+    }
+
+    #[test]
+    fn test_unify_prolonged_marks() {
+        let option = ConvOption::default();
+        for mark in PROLONGED_MARKS {
+            let text = format!("カ{}ド", mark);
+            assert_eq!(unify_prolonged_marks(&text, option), "カード");
+        }
+        assert_eq!(unify_prolonged_marks("ﾊﾟｰﾃｨｰ", option), "ﾊﾟーﾃｨー");
+        assert_eq!(unify_prolonged_marks("すご〜〜い", option), "すごーーい");
+    }
+
+    #[test]
+    fn test_unify_prolonged_marks_not_after_kana() {
+        let option = ConvOption::default();
+        assert_eq!(unify_prolonged_marks("-カ", option), "-カ");
+        assert_eq!(
+            unify_prolonged_marks("x-y 1−2 漢─字", option),
+            "x-y 1−2 漢─字"
+        );
+        assert_eq!(unify_prolonged_marks("カ 〜", option), "カ 〜");
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_normalize_ignore_pattern() {
+        let brackets = regex::Regex::new(r"\[[^\]]*\]").unwrap();
+        let option = ConvOption::build()
+            .ignore_pattern(&brackets)
+            .modern_kana(true)
+            .finalize();
+        assert_eq!(normalize("[ゐ〜  ｶﾞ]ゐ〜  ｶﾞ", option), "[ゐ〜  ｶﾞ]いー ガ");
+        assert_eq!(unify_prolonged_marks("[カ-]カ-", option), "[カ-]カー");
+        assert_eq!(unify_wave_dash("[〜]〜", '～', option), "[〜]～");
+    }
+
+    #[test]
+    fn test_unify_prolonged_marks_ignore() {
+        let option = ConvOption {
+            ignore: "〜",
+            ..Default::default()
+        };
+        assert_eq!(unify_prolonged_marks("ス〜パ-", option), "ス〜パー");
+    }
+
+    #[test]
+    fn test_unify_wave_dash() {
+        let option = ConvOption::default();
+        assert_eq!(unify_wave_dash("〜～~", '~', option), "~~~");
+        assert_eq!(unify_wave_dash("〜～", 'ー', option), "ーー");
+
+        let option = ConvOption {
+            ignore: "〜",
+            ..Default::default()
+        };
+        assert_eq!(unify_wave_dash("〜～", '〜', option), "〜〜");
+    }
+}
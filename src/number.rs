@@ -0,0 +1,719 @@
+//! Conversion between kanji numerals and numbers.
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+use crate::conv_table::{
+    DAIJI_DIGIT, DAIJI_SMALL_UNIT, FULL_DIGIT, KANJI_DIGIT, KANJI_LARGE_UNIT, KANJI_SMALL_UNIT,
+};
+use crate::ConvOption;
+
+/// Styles of kanji numerals
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum KanjiNumberStyle {
+    /// Numerals with positions(e.g. `千二百三十四`)
+    #[default]
+    Positions,
+    /// Numerals digit by digit(e.g. `一二三四`)
+    Positional,
+    /// Daiji used on invoices and contracts(e.g. `壱千弐百参拾四`)
+    Daiji,
+}
+
+/// Parse kanji numerals into a number
+///
+/// Both numerals with positions(e.g. `千二百三十四`, `一万五千`) and positional numerals
+/// (e.g. `二〇二四`) are accepted, and digits can be mixed(e.g. `1万5千`, `３億`).
+/// Daiji(e.g. `壱万弐千`) and `萬` are accepted too.
+/// Positions up to `京` are supported.
+///
+/// Returns `None` if `text` is empty, contains other characters, has positions which are not
+/// in descending order(e.g. `一万一億`, `百百`), or overflows `u64`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::kanji2number;
+///
+/// assert_eq!(Some(1234), kanji2number("千二百三十四"));
+/// assert_eq!(Some(15000), kanji2number("一万五千"));
+/// assert_eq!(Some(2024), kanji2number("二〇二四"));
+/// assert_eq!(None, kanji2number("千円"));
+/// assert_eq!(None, kanji2number("十十"));
+/// ```
+pub fn kanji2number(text: &str) -> Option<u64> {
+    let mut total: u64 = 0;
+    let mut section: u64 = 0;
+    let mut digits: Option<u64> = None;
+    let mut small: Option<u64> = None;
+    let mut large: Option<u64> = None;
+    let mut empty = true;
+
+    for c in text.chars() {
+        empty = false;
+        if let Some(d) = digit(c) {
+            digits = Some(digits.unwrap_or(0).checked_mul(10)?.checked_add(d)?);
+        } else if let Some(&(_, unit)) = KANJI_SMALL_UNIT
+            .iter()
+            .chain(DAIJI_SMALL_UNIT.iter())
+            .find(|(u, _)| *u == c)
+        {
+            if small.is_some_and(|prev| prev <= unit) {
+                return None;
+            }
+            small = Some(unit);
+            section = section.checked_add(digits.take().unwrap_or(1).checked_mul(unit)?)?;
+        } else if let Some(&(_, unit)) = KANJI_LARGE_UNIT
+            .iter()
+            .find(|(u, _)| *u == c || (*u == '万' && c == '萬'))
+        {
+            if large.is_some_and(|prev| prev <= unit) {
+                return None;
+            }
+            large = Some(unit);
+            let value = section.checked_add(digits.take().unwrap_or(0))?;
+            if value == 0 {
+                return None;
+            }
+            total = total.checked_add(value.checked_mul(unit)?)?;
+            section = 0;
+            small = None;
+        } else {
+            return None;
+        }
+    }
+    if empty {
+        return None;
+    }
+    total.checked_add(section)?.checked_add(digits.unwrap_or(0))
+}
+
+/// Format a number into kanji numerals with positions
+///
+/// `1` is omitted before `十`, `百` and `千`, and kept before `万` and larger positions.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::number2kanji;
+///
+/// assert_eq!("千二百三十四", number2kanji(1234));
+/// assert_eq!("一万五千", number2kanji(15000));
+/// ```
+pub fn number2kanji(number: u64) -> String {
+    number2kanji_with(number, KanjiNumberStyle::Positions)
+}
+
+/// Format a number into kanji numerals with the given style
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{number2kanji_with, KanjiNumberStyle};
+///
+/// assert_eq!("一二三四", number2kanji_with(1234, KanjiNumberStyle::Positional));
+/// assert_eq!("壱千弐百参拾四", number2kanji_with(1234, KanjiNumberStyle::Daiji));
+/// ```
+pub fn number2kanji_with(number: u64, style: KanjiNumberStyle) -> String {
+    let (digits, units) = match style {
+        KanjiNumberStyle::Positional => {
+            let mut buf = [0u8; 20];
+            let mut i = buf.len();
+            let mut n = number;
+            loop {
+                i -= 1;
+                buf[i] = (n % 10) as u8;
+                n /= 10;
+                if n == 0 {
+                    break;
+                }
+            }
+            return buf[i..].iter().map(|d| KANJI_DIGIT[*d as usize]).collect();
+        }
+        KanjiNumberStyle::Positions => (&KANJI_DIGIT, &KANJI_SMALL_UNIT),
+        KanjiNumberStyle::Daiji => (&DAIJI_DIGIT, &DAIJI_SMALL_UNIT),
+    };
+    if number == 0 {
+        return String::from(digits[0]);
+    }
+
+    let mut output = String::new();
+    let mut rest = number;
+    for &(large, value) in KANJI_LARGE_UNIT.iter().rev() {
+        let section = rest / value;
+        rest %= value;
+        if section > 0 {
+            format_section(&mut output, section, digits, units, style);
+            output.push(large);
+        }
+    }
+    format_section(&mut output, rest, digits, units, style);
+    output
+}
+
+/// Convert runs of half-width and full-width digits into kanji numerals
+///
+/// Each run of digits is formatted with `style`. `KanjiNumberStyle::Positional` converts
+/// every digit including leading zeros(e.g. `007` to `〇〇七`). The other styles format
+/// the value of a run, so leading zeros are dropped(e.g. `007` to `七`) and a run of
+/// only zeros is zero. A run which overflows `u64` is converted digit by digit.
+///
+/// Digits to convert can be restricted with ignore settings(`ignore`, `ignore_chars`,
+/// `ignore_if`, `ignore_ranges` and `only`) of `option`, e.g. only full-width digits.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{digits2kanji, ConvOption, KanjiNumberStyle};
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = digits2kanji("３階の12号室", KanjiNumberStyle::Positions, option);
+/// assert_eq!("三階の十二号室", converted);
+///
+/// let option = ConvOption {
+///     only: "０１２３４５６７８９",
+///     ..Default::default()
+/// };
+/// let converted = digits2kanji("３階の12号室", KanjiNumberStyle::Positions, option);
+/// assert_eq!("三階の12号室", converted);
+/// ```
+pub fn digits2kanji<'a>(
+    text: impl AsRef<str>,
+    style: KanjiNumberStyle,
+    option: impl Borrow<ConvOption<'a>>,
+) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    let ignore = option.ignore_set();
+    let mut output = String::with_capacity(text.len());
+    let mut run = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match number_digit(c).filter(|_| !option.is_ignored_in(&ignore, c)) {
+            Some(d) => run.push(d),
+            None => output.push(c),
+        }
+        let next = chars
+            .peek()
+            .and_then(|n| number_digit(*n).filter(|_| !option.is_ignored_in(&ignore, *n)));
+        if !run.is_empty() && next.is_none() {
+            push_run(&mut output, &run, style);
+            run.clear();
+        }
+    }
+    output
+}
+
+/// Push a run of digits as kanji numerals
+fn push_run(output: &mut String, run: &[u64], style: KanjiNumberStyle) {
+    let number = run
+        .iter()
+        .try_fold(0u64, |n, d| n.checked_mul(10)?.checked_add(*d))
+        .filter(|_| style != KanjiNumberStyle::Positional);
+    match number {
+        Some(n) => output.push_str(&number2kanji_with(n, style)),
+        None => output.extend(run.iter().map(|d| KANJI_DIGIT[*d as usize])),
+    }
+}
+
+/// Value of a half-width or full-width digit
+fn number_digit(c: char) -> Option<u64> {
+    c.to_digit(10).map(u64::from).or_else(|| {
+        FULL_DIGIT
+            .iter()
+            .position(|f| f.chars().eq(Some(c)))
+            .map(|d| d as u64)
+    })
+}
+
+/// Format a number below ten thousand
+fn format_section(
+    output: &mut String,
+    section: u64,
+    digits: &[char; 10],
+    units: &[(char, u64); 3],
+    style: KanjiNumberStyle,
+) {
+    for &(unit, value) in units.iter().rev() {
+        let d = section / value % 10;
+        if d > 1 || (d == 1 && style == KanjiNumberStyle::Daiji) {
+            output.push(digits[d as usize]);
+        }
+        if d > 0 {
+            output.push(unit);
+        }
+    }
+    let d = section % 10;
+    if d > 0 {
+        output.push(digits[d as usize]);
+    }
+}
+
+/// A counter word and its sound changes by the preceding word
+struct Counter {
+    kanji: &'static str,
+    kana: &'static str,
+    /// Preceding words which become geminate(e.g. `いち` to `いっ`)
+    geminate: &'static [&'static str],
+    /// The counter word after a geminate
+    geminated: &'static str,
+    /// Preceding words after which the counter word is voiced
+    voicing: &'static [&'static str],
+    /// The counter word after `voicing`
+    voiced: &'static str,
+    /// Irregular forms of preceding words(e.g. `よん` to `よ`)
+    words: &'static [(&'static str, &'static str)],
+    /// Irregular readings of whole numbers(e.g. `1` to `ひとり`)
+    special: &'static [(u64, &'static str)],
+}
+
+impl Counter {
+    const fn new(kanji: &'static str, kana: &'static str) -> Counter {
+        Counter {
+            kanji,
+            kana,
+            geminate: &[],
+            geminated: kana,
+            voicing: &[],
+            voiced: kana,
+            words: &[],
+            special: &[],
+        }
+    }
+
+    const fn geminate(mut self, words: &'static [&'static str], kana: &'static str) -> Counter {
+        self.geminate = words;
+        self.geminated = kana;
+        self
+    }
+
+    const fn voicing(mut self, words: &'static [&'static str], kana: &'static str) -> Counter {
+        self.voicing = words;
+        self.voiced = kana;
+        self
+    }
+
+    /// Push the counter word with the sound change of the last word
+    fn push(&self, words: &mut Vec<String>) {
+        let Some(mut last) = words.pop() else {
+            words.push(self.kana.to_string());
+            return;
+        };
+        if let Some((_, word)) = self.words.iter().find(|(w, _)| *w == last) {
+            last = word.to_string();
+        }
+        let counter = if self.geminate.contains(&last.as_str()) {
+            last.pop();
+            last.push('っ');
+            self.geminated
+        } else if self.voicing.contains(&last.as_str()) {
+            self.voiced
+        } else {
+            self.kana
+        };
+        words.push(last);
+        words.push(counter.to_string());
+    }
+}
+
+const H_GEMINATE: &[&str] = &[
+    "いち",
+    "ろく",
+    "はち",
+    "じゅう",
+    "ひゃく",
+    "びゃく",
+    "ぴゃく",
+];
+const S_GEMINATE: &[&str] = &["いち", "はち", "じゅう"];
+const H_VOICING: &[&str] = &["さん", "なん", "せん", "ぜん", "まん"];
+
+/// Positions of a number
+const HUNDRED: Counter = Counter::new("百", "ひゃく")
+    .geminate(&["ろく", "はち"], "ぴゃく")
+    .voicing(&["さん", "なん"], "びゃく");
+const THOUSAND: Counter = Counter::new("千", "せん")
+    .geminate(&["はち"], "せん")
+    .voicing(&["さん", "なん"], "ぜん");
+const LARGE_UNIT: [Counter; 4] = [
+    Counter::new("万", "まん"),
+    Counter::new("億", "おく"),
+    Counter::new("兆", "ちょう").geminate(&["いち", "はち", "じゅう"], "ちょう"),
+    Counter::new("京", "けい").geminate(H_GEMINATE, "けい"),
+];
+
+/// Common counters
+const COUNTERS: [Counter; 15] = [
+    Counter::new("本", "ほん")
+        .geminate(H_GEMINATE, "ぽん")
+        .voicing(H_VOICING, "ぼん"),
+    Counter::new("匹", "ひき")
+        .geminate(H_GEMINATE, "ぴき")
+        .voicing(H_VOICING, "びき"),
+    Counter::new("杯", "はい")
+        .geminate(H_GEMINATE, "ぱい")
+        .voicing(H_VOICING, "ばい"),
+    Counter::new("分", "ふん")
+        .geminate(H_GEMINATE, "ぷん")
+        .voicing(&["さん", "よん", "なん", "せん", "ぜん", "まん"], "ぷん"),
+    Counter::new("個", "こ").geminate(H_GEMINATE, "こ"),
+    Counter::new("回", "かい").geminate(H_GEMINATE, "かい"),
+    Counter::new("階", "かい")
+        .geminate(H_GEMINATE, "かい")
+        .voicing(&["さん", "なん"], "がい"),
+    Counter {
+        special: &[(20, "はたち")],
+        ..Counter::new("歳", "さい").geminate(S_GEMINATE, "さい")
+    },
+    Counter::new("冊", "さつ").geminate(S_GEMINATE, "さつ"),
+    Counter::new("枚", "まい"),
+    Counter {
+        words: &[("よん", "よ")],
+        ..Counter::new("円", "えん")
+    },
+    Counter {
+        words: &[("よん", "よ")],
+        special: &[(1, "ひとり"), (2, "ふたり")],
+        ..Counter::new("人", "にん")
+    },
+    Counter {
+        words: &[("よん", "よ"), ("なな", "しち"), ("きゅう", "く")],
+        ..Counter::new("時", "じ")
+    },
+    Counter {
+        words: &[("よん", "し"), ("なな", "しち"), ("きゅう", "く")],
+        ..Counter::new("月", "がつ")
+    },
+    Counter {
+        special: &[
+            (1, "ひとつ"),
+            (2, "ふたつ"),
+            (3, "みっつ"),
+            (4, "よっつ"),
+            (5, "いつつ"),
+            (6, "むっつ"),
+            (7, "ななつ"),
+            (8, "やっつ"),
+            (9, "ここのつ"),
+            (10, "とお"),
+        ],
+        ..Counter::new("つ", "つ")
+    },
+];
+
+/// Readings of digits
+const DIGIT_READING: [&str; 10] = [
+    "ぜろ",
+    "いち",
+    "に",
+    "さん",
+    "よん",
+    "ご",
+    "ろく",
+    "なな",
+    "はち",
+    "きゅう",
+];
+
+/// Format a number with a counter into its reading in hiragana
+///
+/// Sound changes by common counters are applied(e.g. `3本` to `さんぼん`, `6本` to `ろっぽん`,
+/// `4時` to `よじ`, `1人` to `ひとり`).
+/// Supported counters are `本`, `匹`, `杯`, `分`, `個`, `回`, `階`, `歳`, `冊`, `枚`, `円`,
+/// `人`, `時`, `月` and `つ`. Other counters are appended as they are, and an empty counter
+/// gives the reading of the number.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::number2reading;
+///
+/// assert_eq!("さんぼん", number2reading(3, "本"));
+/// assert_eq!("いっぴき", number2reading(1, "匹"));
+/// assert_eq!("さんびゃっぽん", number2reading(300, "本"));
+/// assert_eq!("ふたり", number2reading(2, "人"));
+/// assert_eq!("せんにひゃくさんじゅうよん", number2reading(1234, ""));
+/// ```
+pub fn number2reading(number: u64, counter: &str) -> String {
+    let known = COUNTERS.iter().find(|c| c.kanji == counter);
+    if let Some((_, word)) = known.and_then(|c| c.special.iter().find(|(n, _)| *n == number)) {
+        return word.to_string();
+    }
+
+    let mut words = Vec::new();
+    if number == 0 {
+        words.push(DIGIT_READING[0].to_string());
+    }
+    let mut rest = number;
+    for (i, unit) in LARGE_UNIT.iter().enumerate().rev() {
+        let value = 10_000u64.pow(i as u32 + 1);
+        let section = rest / value;
+        rest %= value;
+        if section > 0 {
+            push_section(&mut words, section);
+            unit.push(&mut words);
+        }
+    }
+    push_section(&mut words, rest);
+
+    match known {
+        Some(known) => known.push(&mut words),
+        None => words.push(counter.to_string()),
+    }
+    words.concat()
+}
+
+/// Push readings of a number below ten thousand
+fn push_section(words: &mut Vec<String>, section: u64) {
+    for (value, unit) in [(1000, Some(&THOUSAND)), (100, Some(&HUNDRED)), (10, None)] {
+        let d = (section / value % 10) as usize;
+        if d > 1 {
+            words.push(DIGIT_READING[d].to_string());
+        }
+        if d > 0 {
+            match unit {
+                Some(unit) if d > 1 => unit.push(words),
+                Some(unit) => words.push(unit.kana.to_string()),
+                None => words.push("じゅう".to_string()),
+            }
+        }
+    }
+    let d = (section % 10) as usize;
+    if d > 0 {
+        words.push(DIGIT_READING[d].to_string());
+    }
+}
+
+/// Value of a kanji, daiji, half-width or full-width digit
+fn digit(c: char) -> Option<u64> {
+    KANJI_DIGIT
+        .iter()
+        .position(|k| *k == c)
+        .or_else(|| DAIJI_DIGIT.iter().position(|k| *k == c))
+        .map(|d| d as u64)
+        .or_else(|| number_digit(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kanji2number_positions() {
+        assert_eq!(kanji2number("一"), Some(1));
+        assert_eq!(kanji2number("十"), Some(10));
+        assert_eq!(kanji2number("十一"), Some(11));
+        assert_eq!(kanji2number("二十"), Some(20));
+        assert_eq!(kanji2number("百五"), Some(105));
+        assert_eq!(kanji2number("千二百三十四"), Some(1234));
+        assert_eq!(kanji2number("一万五千"), Some(15000));
+        assert_eq!(kanji2number("十二万三千四百五十六"), Some(123456));
+        assert_eq!(kanji2number("三億二万"), Some(300_020_000));
+        assert_eq!(kanji2number("一兆"), Some(1_000_000_000_000));
+        assert_eq!(kanji2number("千八百京"), Some(18_000_000_000_000_000_000));
+    }
+
+    #[test]
+    fn test_kanji2number_positional() {
+        assert_eq!(kanji2number("〇"), Some(0));
+        assert_eq!(kanji2number("零"), Some(0));
+        assert_eq!(kanji2number("二〇二四"), Some(2024));
+        assert_eq!(kanji2number("一二万"), Some(120000));
+    }
+
+    #[test]
+    fn test_kanji2number_digits() {
+        assert_eq!(kanji2number("1万5千"), Some(15000));
+        assert_eq!(kanji2number("３億"), Some(300_000_000));
+        assert_eq!(kanji2number("1234"), Some(1234));
+    }
+
+    #[test]
+    fn test_kanji2number_invalid() {
+        assert_eq!(kanji2number(""), None);
+        assert_eq!(kanji2number("万"), None);
+        assert_eq!(kanji2number("千円"), None);
+        assert_eq!(kanji2number("一万 五千"), None);
+        assert_eq!(kanji2number("二万京"), None);
+    }
+
+    #[test]
+    fn test_kanji2number_order() {
+        assert_eq!(kanji2number("一万一億"), None);
+        assert_eq!(kanji2number("一万二万"), None);
+        assert_eq!(kanji2number("百百"), None);
+        assert_eq!(kanji2number("十十"), None);
+        assert_eq!(kanji2number("十百"), None);
+        assert_eq!(kanji2number("百拾十"), None);
+        assert_eq!(kanji2number("十万十"), Some(100_010));
+        assert_eq!(kanji2number("一億十万十"), Some(100_100_010));
+    }
+
+    #[test]
+    fn test_number2kanji() {
+        assert_eq!(number2kanji(0), "〇");
+        assert_eq!(number2kanji(1), "一");
+        assert_eq!(number2kanji(10), "十");
+        assert_eq!(number2kanji(11), "十一");
+        assert_eq!(number2kanji(105), "百五");
+        assert_eq!(number2kanji(1234), "千二百三十四");
+        assert_eq!(number2kanji(10000), "一万");
+        assert_eq!(number2kanji(15000), "一万五千");
+        assert_eq!(number2kanji(10_000_000), "千万");
+        assert_eq!(number2kanji(300_020_000), "三億二万");
+        assert_eq!(
+            number2kanji(u64::MAX),
+            "千八百四十四京六千七百四十四兆七百三十七億九百五十五万千六百十五"
+        );
+    }
+
+    #[test]
+    fn test_number2kanji_positional() {
+        let style = KanjiNumberStyle::Positional;
+        assert_eq!(number2kanji_with(0, style), "〇");
+        assert_eq!(number2kanji_with(2024, style), "二〇二四");
+    }
+
+    #[test]
+    fn test_number2kanji_daiji() {
+        let style = KanjiNumberStyle::Daiji;
+        assert_eq!(number2kanji_with(0, style), "零");
+        assert_eq!(number2kanji_with(10, style), "壱拾");
+        assert_eq!(number2kanji_with(1234, style), "壱千弐百参拾四");
+        assert_eq!(number2kanji_with(11000, style), "壱万壱千");
+    }
+
+    #[test]
+    fn test_number2kanji_round_trip() {
+        for style in [
+            KanjiNumberStyle::Positions,
+            KanjiNumberStyle::Positional,
+            KanjiNumberStyle::Daiji,
+        ] {
+            for n in [0, 1, 10, 19, 101, 1234, 10_001, 99_999_999, u64::MAX] {
+                assert_eq!(kanji2number(&number2kanji_with(n, style)), Some(n));
+            }
+        }
+    }
+
+    #[test]
+    fn test_digits2kanji() {
+        let option = ConvOption::default();
+        let style = KanjiNumberStyle::Positions;
+        assert_eq!(digits2kanji("３階", style, option), "三階");
+        assert_eq!(digits2kanji("2024年1月", style, option), "二千二十四年一月");
+        assert_eq!(digits2kanji("1２3", style, option), "百二十三");
+        assert_eq!(digits2kanji("なし", style, option), "なし");
+        assert_eq!(
+            digits2kanji("2024年", KanjiNumberStyle::Positional, option),
+            "二〇二四年"
+        );
+        assert_eq!(
+            digits2kanji("123456789012345678901", style, option),
+            "一二三四五六七八九〇一二三四五六七八九〇一"
+        );
+    }
+
+    #[test]
+    fn test_digits2kanji_leading_zeros() {
+        let option = ConvOption::default();
+        let text = "電話0120-007";
+        assert_eq!(
+            digits2kanji(text, KanjiNumberStyle::Positional, option),
+            "電話〇一二〇-〇〇七"
+        );
+        assert_eq!(
+            digits2kanji(text, KanjiNumberStyle::Positions, option),
+            "電話百二十-七"
+        );
+        assert_eq!(
+            digits2kanji(text, KanjiNumberStyle::Daiji, option),
+            "電話壱百弐拾-七"
+        );
+        assert_eq!(
+            digits2kanji("00", KanjiNumberStyle::Positions, option),
+            "〇"
+        );
+    }
+
+    #[test]
+    fn test_digits2kanji_ignore() {
+        let option = ConvOption {
+            ignore: "0",
+            ..Default::default()
+        };
+        let style = KanjiNumberStyle::Positions;
+        assert_eq!(digits2kanji("1203", style, option), "十二0三");
+    }
+
+    #[test]
+    fn test_number2reading() {
+        assert_eq!(number2reading(0, ""), "ぜろ");
+        assert_eq!(number2reading(4, ""), "よん");
+        assert_eq!(number2reading(10, ""), "じゅう");
+        assert_eq!(number2reading(300, ""), "さんびゃく");
+        assert_eq!(number2reading(600, ""), "ろっぴゃく");
+        assert_eq!(number2reading(800, ""), "はっぴゃく");
+        assert_eq!(number2reading(3000, ""), "さんぜん");
+        assert_eq!(number2reading(8000, ""), "はっせん");
+        assert_eq!(number2reading(1234, ""), "せんにひゃくさんじゅうよん");
+        assert_eq!(number2reading(10_000, ""), "いちまん");
+        assert_eq!(number2reading(10_000_000, ""), "せんまん");
+        assert_eq!(number2reading(100_000_000, ""), "いちおく");
+        assert_eq!(number2reading(1_000_000_000_000, ""), "いっちょう");
+        assert_eq!(number2reading(10_000_000_000_000, ""), "じゅっちょう");
+        assert_eq!(number2reading(60_000_000_000_000_000, ""), "ろっけい");
+    }
+
+    #[test]
+    fn test_number2reading_counter() {
+        let cases = [
+            (1, "本", "いっぽん"),
+            (2, "本", "にほん"),
+            (3, "本", "さんぼん"),
+            (4, "本", "よんほん"),
+            (6, "本", "ろっぽん"),
+            (8, "本", "はっぽん"),
+            (10, "本", "じゅっぽん"),
+            (21, "本", "にじゅういっぽん"),
+            (100, "本", "ひゃっぽん"),
+            (1000, "本", "せんぼん"),
+            (3, "匹", "さんびき"),
+            (6, "杯", "ろっぱい"),
+            (4, "分", "よんぷん"),
+            (5, "分", "ごふん"),
+            (8, "個", "はっこ"),
+            (3, "階", "さんがい"),
+            (6, "回", "ろっかい"),
+            (8, "歳", "はっさい"),
+            (20, "歳", "はたち"),
+            (6, "冊", "ろくさつ"),
+            (7, "枚", "ななまい"),
+            (4, "円", "よえん"),
+            (1, "人", "ひとり"),
+            (4, "人", "よにん"),
+            (4, "時", "よじ"),
+            (9, "時", "くじ"),
+            (4, "月", "しがつ"),
+            (7, "月", "しちがつ"),
+            (3, "つ", "みっつ"),
+        ];
+        for (number, counter, reading) in cases {
+            assert_eq!(
+                number2reading(number, counter),
+                reading,
+                "{}{}",
+                number,
+                counter
+            );
+        }
+    }
+
+    #[test]
+    fn test_number2reading_unknown_counter() {
+        assert_eq!(number2reading(3, "台"), "さん台");
+        assert_eq!(number2reading(11, "つ"), "じゅういちつ");
+    }
+}
@@ -0,0 +1,229 @@
+//! Pipeline which chains conversions.
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{convert_with, AsKanaOption, AsWidthOption, ConvOption, Conversion, Converter, Table};
+
+/// A sequence of conversions which are applied in order
+///
+/// Conversion tables are built when steps are added,
+/// so a pipeline can be reused for many strings.
+///
+/// Consecutive steps which convert each character regardless of its neighbors are fused
+/// into one table, and applied in a single pass over strings. Steps which merge pairs of
+/// characters(e.g. `h2z` with `kana`, `compose_mark`), replace prolonged sound marks
+/// (`long_vowel`) or have `ignore_pattern` are applied one by one.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{ConvOption, Conversion, Pipeline};
+///
+/// let digit = ConvOption {
+///     digit: true,
+///     ..Default::default()
+/// };
+/// let kana = ConvOption {
+///     kana: true,
+///     ..Default::default()
+/// };
+/// let pipeline = Pipeline::new()
+///     .then(Conversion::Z2h, digit)
+///     .then(Conversion::H2z, kana)
+///     .then(Conversion::Kata2Hira, ConvOption::default());
+/// assert_eq!("12あいがぎ", pipeline.run("１２ｱｲガｷﾞ"));
+///
+/// let pipeline = Pipeline::new()
+///     .z2h(ConvOption::all())
+///     .kata2hira(ConvOption::default());
+/// assert_eq!("ABｱｲ", pipeline.run("ＡＢアイ"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Pipeline<'a> {
+    stages: Vec<Stage<'a>>,
+    len: usize,
+}
+
+/// Steps of a pipeline which are applied in a single pass
+#[derive(Debug, Clone)]
+enum Stage<'a> {
+    /// A step which is applied alone
+    Step(Box<Converter<'a>>),
+    /// Consecutive steps which are fused into one table
+    Fused(Table),
+}
+
+impl<'a> Pipeline<'a> {
+    /// Returns an empty pipeline
+    pub fn new() -> Pipeline<'a> {
+        Pipeline {
+            stages: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Add a conversion step with its own option
+    pub fn then(mut self, conversion: Conversion, option: ConvOption<'a>) -> Pipeline<'a> {
+        let converter = Converter::new(conversion, option);
+        self.len += 1;
+        if !converter.is_per_char() {
+            self.stages.push(Stage::Step(Box::new(converter)));
+            return self;
+        }
+        match self.stages.last_mut() {
+            Some(Stage::Fused(table)) => *table = fuse(table, &converter),
+            _ => self
+                .stages
+                .push(Stage::Fused(fuse(&Table::new(), &converter))),
+        }
+        self
+    }
+
+    /// Add a step of `h2z`
+    pub fn h2z(self, option: impl AsWidthOption<'a>) -> Pipeline<'a> {
+        self.then(Conversion::H2z, *option.conv_option())
+    }
+
+    /// Add a step of `z2h`
+    pub fn z2h(self, option: impl AsWidthOption<'a>) -> Pipeline<'a> {
+        self.then(Conversion::Z2h, *option.conv_option())
+    }
+
+    /// Add a step of `hira2kata`
+    pub fn hira2kata(self, option: impl AsKanaOption<'a>) -> Pipeline<'a> {
+        self.then(Conversion::Hira2Kata, *option.conv_option())
+    }
+
+    /// Add a step of `hira2hkata`
+    pub fn hira2hkata(self, option: impl AsKanaOption<'a>) -> Pipeline<'a> {
+        self.then(Conversion::Hira2HKata, *option.conv_option())
+    }
+
+    /// Add a step of `kata2hira`
+    pub fn kata2hira(self, option: impl AsKanaOption<'a>) -> Pipeline<'a> {
+        self.then(Conversion::Kata2Hira, *option.conv_option())
+    }
+
+    /// The number of steps
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the pipeline has no steps
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Convert strings with all steps in order
+    pub fn run(&self, text: impl AsRef<str>) -> String {
+        let text = text.as_ref();
+        self.stages
+            .iter()
+            .fold(text.to_string(), |text, stage| match stage {
+                Stage::Step(converter) => converter.convert(&text),
+                Stage::Fused(table) => convert_with(&text, table, &ConvOption::default()),
+            })
+    }
+}
+
+/// Compose a fused table and a following step which converts each character by its table
+///
+/// Ignored characters of the step are baked into the composed table.
+fn fuse(table: &Table, converter: &Converter) -> Table {
+    let keys = table
+        .keys()
+        .filter_map(|k| char::from_u32(*k))
+        .chain(
+            converter
+                .keys()
+                .filter(|c| !table.contains_key(&(*c as u32))),
+        )
+        .collect::<Vec<_>>();
+    let mut fused = Table::new();
+    for c in keys {
+        let converted = match table.get(&(c as u32)) {
+            Some(s) => converter.convert_per_char(s),
+            None => converter.convert_per_char(c.encode_utf8(&mut [0; 4])),
+        };
+        if converted.chars().ne(Some(c)) {
+            fused.insert(c as u32, converted);
+        }
+    }
+    fused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{h2z, hira2kata, kata2hira, z2h};
+
+    #[test]
+    fn test_run_empty() {
+        assert_eq!(Pipeline::new().run("ＡｱA"), "ＡｱA");
+    }
+
+    #[test]
+    fn test_run_equals_sequential_calls() {
+        let digit = ConvOption {
+            digit: true,
+            ..Default::default()
+        };
+        let kana = ConvOption {
+            kana: true,
+            ignore: "ｳ",
+            ..Default::default()
+        };
+        let hira = ConvOption::default();
+        let pipeline = Pipeline::new()
+            .then(Conversion::Z2h, digit)
+            .then(Conversion::H2z, kana)
+            .then(Conversion::Kata2Hira, hira);
+
+        let text = "１２３ｱｲｳｶﾞＡＢＣ";
+        let expected = kata2hira(h2z(z2h(text, digit), kana), hira);
+        assert_eq!(pipeline.len(), 3);
+        assert_eq!(pipeline.run(text), expected);
+        assert_eq!(pipeline.run(text), "123あいｳがＡＢＣ");
+    }
+
+    #[test]
+    fn test_fused_steps() {
+        let ascii = ConvOption {
+            ascii: true,
+            kana: true,
+            ignore: "Ｂア",
+            ..Default::default()
+        };
+        let hira = ConvOption {
+            ignore: "イ",
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new()
+            .z2h(ascii)
+            .kata2hira(hira)
+            .hira2kata(ConvOption::default());
+        assert_eq!(pipeline.stages.len(), 1);
+        assert_eq!(pipeline.len(), 3);
+
+        let text = "ＡＢアイウガあ";
+        let expected = hira2kata(kata2hira(z2h(text, ascii), hira), ConvOption::default());
+        assert_eq!(pipeline.run(text), expected);
+        assert_eq!(pipeline.run(text), "AＢアｲｳｶﾞア");
+    }
+
+    #[test]
+    fn test_unfused_steps() {
+        let kana = ConvOption {
+            kana: true,
+            ..Default::default()
+        };
+        let long_vowel = ConvOption {
+            long_vowel: true,
+            ..Default::default()
+        };
+        let pipeline = Pipeline::new().z2h(kana).h2z(kana).kata2hira(long_vowel);
+        assert_eq!(pipeline.stages.len(), 3);
+        assert_eq!(pipeline.run("カーﾄﾞ"), "かあど");
+    }
+}
@@ -0,0 +1,624 @@
+//! Romanization of kana.
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+
+use crate::conv_option::IgnoreSet;
+use crate::conv_table::{
+    FULL_KANA, HIRAGANA, ROMAN_HEPBURN, ROMAN_INPUT, ROMAN_KUNREI, ROMAN_NIHON,
+};
+use crate::ConvOption;
+
+/// Romanization systems of `kana2roman`
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum RomanizationSystem {
+    /// Hepburn(e.g. `し` to `shi`, `ちゃ` to `cha`)
+    #[default]
+    Hepburn,
+    /// Kunrei-shiki(e.g. `し` to `si`, `ちゃ` to `tya`)
+    Kunrei,
+    /// Nihon-shiki(e.g. `ぢ` to `di`, `を` to `wo`)
+    Nihon,
+}
+
+impl RomanizationSystem {
+    /// Tables of this system in order of precedence
+    fn tables(&self) -> &'static [&'static [(&'static str, &'static str)]] {
+        match self {
+            RomanizationSystem::Hepburn => &[&ROMAN_HEPBURN],
+            RomanizationSystem::Kunrei => &[&ROMAN_KUNREI, &ROMAN_HEPBURN],
+            RomanizationSystem::Nihon => &[&ROMAN_NIHON, &ROMAN_KUNREI, &ROMAN_HEPBURN],
+        }
+    }
+
+    /// Kana of romaji which this system reads differently from `ROMAN_INPUT`
+    fn input(&self, roman: &[char]) -> Option<&'static str> {
+        let tables = self.tables();
+        tables[..tables.len() - 1].iter().find_map(|table| {
+            table
+                .iter()
+                .find(|(_, r)| r.chars().eq(roman.iter().copied()))
+                .map(|(kana, _)| *kana)
+        })
+    }
+}
+
+/// Convert from hiragana and full-width katakana to romaji
+///
+/// The romanization system is selected by `option.roman`(Hepburn by default).
+/// - A kana and a following small kana(e.g. `きゃ`) become one syllable(`kya`).
+/// - `っ` doubles the consonant of the next syllable(`ch` becomes `tch` in Hepburn),
+///   and is kept as it is when no consonant follows.
+/// - `ん` becomes `n'` before a vowel or `y` to avoid ambiguity.
+/// - `ー` repeats the previous vowel.
+///
+/// Other characters are kept as they are.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::kana2roman;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = kana2roman("きょうはまっちゃラーメン", option);
+/// assert_eq!("kyouhamatcharaamen", converted);
+///
+/// let option = ConvOption {
+///     ignore: "ン",
+///     ..Default::default()
+/// };
+/// let converted = kana2roman("ホンヤ", option);
+/// assert_eq!("hoンya", converted);
+/// ```
+pub fn kana2roman<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    kana_to_romaji(text, option, false)
+}
+
+/// Convert from hiragana to alphabets(romaji) like `kana2alphabet` of jaconv
+///
+/// This differs from `kana2roman` to keep the behavior of jaconv.
+/// - Katakana is kept as it is.
+/// - `っ` repeats the first letter of the next syllable(`っち` becomes `cchi`),
+///   and becomes `xtsu` at the beginning or the end of the text.
+/// - `ん` becomes `n` even before a vowel.
+/// - `ー` becomes `-`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::kana2alphabet;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = kana2alphabet("まみさん、まっちゃぱーてぃー、カナ", option);
+/// assert_eq!("mamisan、macchapa-ti-、カナ", converted);
+/// ```
+pub fn kana2alphabet<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    kana_to_romaji(text, option, true)
+}
+
+/// Convert from kana to romaji, following jaconv if `jaconv` is true
+fn kana_to_romaji(text: &str, option: &ConvOption, jaconv: bool) -> String {
+    let ignore = option.ignore_set();
+    let chars = text.chars().collect::<Vec<_>>();
+    let hira = if jaconv {
+        chars.clone()
+    } else {
+        chars.iter().map(|c| to_hiragana(*c)).collect::<Vec<_>>()
+    };
+    let mut output = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if option.is_ignored_in(&ignore, c) {
+            output.push(c);
+            i += 1;
+            continue;
+        }
+
+        let next = syllable(&hira[i + 1..], &chars[i + 1..], option, &ignore);
+        match hira[i] {
+            'っ' if jaconv => match next.and_then(|(r, _)| r.chars().next()) {
+                _ if i == 0 || i + 1 == chars.len() => output.push_str("xtsu"),
+                Some(n) => output.push(n),
+                None => output.push(chars[i + 1]),
+            },
+            'ー' if jaconv => output.push('-'),
+            'ん' if jaconv => output.push('n'),
+            'っ' => match next.and_then(|(r, _)| r.chars().next()) {
+                Some('c') => output.push('t'),
+                Some(n) if !is_vowel(n) && n != 'n' => output.push(n),
+                _ => output.push(c),
+            },
+            'ー' => match output.chars().last() {
+                Some(v) if is_vowel(v) => output.push(v),
+                _ => output.push(c),
+            },
+            'ん' => {
+                output.push('n');
+                if let Some(n) = next.and_then(|(r, _)| r.chars().next()) {
+                    if is_vowel(n) || n == 'y' {
+                        output.push('\'');
+                    }
+                }
+            }
+            _ => match syllable(&hira[i..], &chars[i..], option, &ignore) {
+                Some((roman, len)) => {
+                    output.push_str(roman);
+                    i += len;
+                    continue;
+                }
+                None => output.push(c),
+            },
+        }
+        i += 1;
+    }
+    output
+}
+
+/// Convert from romaji to hiragana
+///
+/// Romaji is matched with the longest syllable(up to 4 letters) case-insensitively.
+///
+/// - A doubled consonant(e.g. `tt`) and `t` before `ch` become `っ`.
+/// - `nn` and `n'` become `ん`, and `n` becomes `ん` when no vowel or `y` follows.
+///   `n` before `n` and a vowel(e.g. `konnichiha`) becomes `ん` alone.
+/// - `-` becomes `ー`.
+/// - `ti`, `tu`, `di` and `du` become loanword kana(`てぃ`, `とぅ`, `でぃ` and `どぅ`),
+///   which Hepburn romaji has no other spelling for, although Kunrei spellings like `si`,
+///   `zi`, `hu` and `tya` are accepted. Use `RomanizationSystem::Kunrei` to read them as `ち`.
+/// - `ぢ` and `づ` are spelled `dji` and `dzu`, and `ぢゃ` is spelled `dya`.
+///
+/// With `RomanizationSystem::Kunrei` or `RomanizationSystem::Nihon`, spellings of the system
+/// (e.g. `ti`, `tu`, `di`) take precedence.
+///
+/// Characters which are not romaji are kept as they are.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::roman2kana;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = roman2kana("kitte to konnichiha, shinbun", option);
+/// assert_eq!("きって と こんにちは, しんぶん", converted);
+/// ```
+pub fn roman2kana<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    romaji_to_kana(text, option, |c| c)
+}
+
+/// Convert from alphabets(romaji) to hiragana like `alphabet2kana` of jaconv
+///
+/// This is the same as `roman2kana`, e.g. `mamisan` becomes `まみさん` and
+/// `kitte` becomes `きって`.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::alphabet2kana;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = alphabet2kana("mamisan, ja-konn", option);
+/// assert_eq!("まみさん, じゃーこん", converted);
+/// ```
+pub fn alphabet2kana<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    roman2kana(text, option)
+}
+
+/// Convert from romaji to full-width katakana
+///
+/// Romaji is matched in the same way as `roman2kana`, including extended syllables for
+/// loanwords(e.g. `fa` to `ファ`, `ti` to `ティ`, `va` to `ヴァ`, `we` to `ウェ`).
+///
+/// Characters which are not romaji are kept as they are.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::ConvOption;
+/// use kelp::roman2kata;
+///
+/// let option = ConvOption {
+///     ..Default::default()
+/// };
+/// let converted = roman2kata("faito, pa-ti-, vaiorin", option);
+/// assert_eq!("ファイト, パーティー, ヴァイオリン", converted);
+/// ```
+pub fn roman2kata<'a>(text: impl AsRef<str>, option: impl Borrow<ConvOption<'a>>) -> String {
+    let text = text.as_ref();
+    let option = option.borrow();
+    romaji_to_kana(text, option, to_katakana)
+}
+
+/// Convert from romaji to kana, mapping each converted hiragana with `kana`
+fn romaji_to_kana(text: &str, option: &ConvOption, kana: fn(char) -> char) -> String {
+    let ignore = option.ignore_set();
+    let chars = text.chars().collect::<Vec<_>>();
+    let lower = chars
+        .iter()
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+    let is_ignored = |i: usize| {
+        chars
+            .get(i)
+            .is_some_and(|c| option.is_ignored_in(&ignore, *c))
+    };
+    let is_vowel_at = |i: usize| lower.get(i).is_some_and(|c| is_vowel(*c) || *c == 'y');
+    let mut output = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if is_ignored(i) {
+            output.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        let c = lower[i];
+        let next = lower.get(i + 1).copied().filter(|_| !is_ignored(i + 1));
+        if c == 'n' && !is_vowel_at(i + 1) {
+            output.push(kana('ん'));
+            i += match next {
+                Some('\'') => 2,
+                Some('n') if !is_vowel_at(i + 2) => 2,
+                _ => 1,
+            };
+            continue;
+        }
+        if c.is_ascii_alphabetic()
+            && !is_vowel(c)
+            && (next == Some(c) || (c == 't' && next == Some('c')))
+        {
+            output.push(kana('っ'));
+            i += 1;
+            continue;
+        }
+
+        let len = (1..=4)
+            .take_while(|n| i + n <= chars.len() && !is_ignored(i + n - 1))
+            .last()
+            .unwrap_or(0);
+        let syllable = (1..=len).rev().find_map(|n| {
+            let roman = &lower[i..i + n];
+            option
+                .roman
+                .input(roman)
+                .or_else(|| {
+                    ROMAN_INPUT
+                        .iter()
+                        .find(|(r, _)| r.chars().eq(roman.iter().copied()))
+                        .map(|(_, kana)| *kana)
+                })
+                .map(|hira| (hira, n))
+        });
+        match syllable {
+            Some((hira, n)) => {
+                output.extend(hira.chars().map(kana));
+                i += n;
+            }
+            None => {
+                output.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    output
+}
+
+/// Romaji of the syllable at the beginning and the number of its characters
+fn syllable(
+    hira: &[char],
+    chars: &[char],
+    option: &ConvOption,
+    ignore: &IgnoreSet,
+) -> Option<(&'static str, usize)> {
+    if chars
+        .first()
+        .is_none_or(|c| option.is_ignored_in(ignore, *c))
+    {
+        return None;
+    }
+    let len = if chars.len() > 1 && !option.is_ignored_in(ignore, chars[1]) {
+        2
+    } else {
+        1
+    };
+    (1..=len).rev().find_map(|n| {
+        option.roman.tables().iter().find_map(|table| {
+            table
+                .iter()
+                .find(|(kana, _)| kana.chars().eq(hira[..n].iter().copied()))
+                .map(|(_, roman)| (*roman, n))
+        })
+    })
+}
+
+/// Hiragana of a full-width katakana, or the character itself
+fn to_hiragana(c: char) -> char {
+    FULL_KANA
+        .iter()
+        .position(|k| k.chars().eq(Some(c)))
+        .and_then(|i| HIRAGANA[i].chars().next())
+        .unwrap_or(c)
+}
+
+/// Full-width katakana of a hiragana, or the character itself
+fn to_katakana(c: char) -> char {
+    HIRAGANA
+        .iter()
+        .position(|h| h.chars().eq(Some(c)))
+        .and_then(|i| FULL_KANA[i].chars().next())
+        .unwrap_or(c)
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kana2roman_vowels() {
+        let option = ConvOption::default();
+        assert_eq!(kana2roman("あいうえお", option), "aiueo");
+        assert_eq!(kana2roman("アイウエオ", option), "aiueo");
+    }
+
+    #[test]
+    fn test_kana2roman_hepburn() {
+        let option = ConvOption::default();
+        assert_eq!(kana2roman("しちつふじ", option), "shichitsufuji");
+        assert_eq!(kana2roman("ぢづを", option), "jizuo");
+    }
+
+    #[test]
+    fn test_kana2roman_youon() {
+        let option = ConvOption::default();
+        assert_eq!(kana2roman("きゃしゅちょ", option), "kyashucho");
+        assert_eq!(kana2roman("じゃにゅりょ", option), "janyuryo");
+        assert_eq!(kana2roman("ファヴィティ", option), "faviti");
+        assert_eq!(kana2roman("ゃ", option), "ya");
+    }
+
+    #[test]
+    fn test_kana2roman_sokuon() {
+        let option = ConvOption::default();
+        assert_eq!(kana2roman("きって", option), "kitte");
+        assert_eq!(kana2roman("まっちゃ", option), "matcha");
+        assert_eq!(kana2roman("ざっし", option), "zasshi");
+        assert_eq!(kana2roman("あっ", option), "aっ");
+        assert_eq!(kana2roman("っあ", option), "っa");
+    }
+
+    #[test]
+    fn test_kana2roman_n() {
+        let option = ConvOption::default();
+        assert_eq!(kana2roman("しんぶん", option), "shinbun");
+        assert_eq!(kana2roman("きんえん", option), "kin'en");
+        assert_eq!(kana2roman("こんや", option), "kon'ya");
+        assert_eq!(kana2roman("ほんの", option), "honno");
+    }
+
+    #[test]
+    fn test_kana2roman_long_vowel() {
+        let option = ConvOption::default();
+        assert_eq!(kana2roman("ラーメン", option), "raamen");
+        assert_eq!(kana2roman("ー", option), "ー");
+    }
+
+    #[test]
+    fn test_kana2roman_others() {
+        let option = ConvOption::default();
+        assert_eq!(kana2roman("漢字とabc、ｱ", option), "漢字toabc、ｱ");
+    }
+
+    #[test]
+    fn test_kana2roman_ignore() {
+        let option = ConvOption {
+            ignore: "ゃっ",
+            ..Default::default()
+        };
+        assert_eq!(kana2roman("きゃきって", option), "kiゃkiっte");
+    }
+
+    #[test]
+    fn test_roman2kana() {
+        let option = ConvOption::default();
+        assert_eq!(roman2kana("aiueo", option), "あいうえお");
+        assert_eq!(roman2kana("sushi", option), "すし");
+        assert_eq!(roman2kana("toukyou", option), "とうきょう");
+        assert_eq!(roman2kana("SaKuRa", option), "さくら");
+    }
+
+    #[test]
+    fn test_roman2kana_sokuon() {
+        let option = ConvOption::default();
+        assert_eq!(roman2kana("kitte", option), "きって");
+        assert_eq!(roman2kana("zasshi", option), "ざっし");
+        assert_eq!(roman2kana("matcha", option), "まっちゃ");
+        assert_eq!(roman2kana("xtsu", option), "っ");
+    }
+
+    #[test]
+    fn test_roman2kana_n() {
+        let option = ConvOption::default();
+        assert_eq!(roman2kana("nn", option), "ん");
+        assert_eq!(roman2kana("kin'en", option), "きんえん");
+        assert_eq!(roman2kana("kinen", option), "きねん");
+        assert_eq!(roman2kana("konnichiha", option), "こんにちは");
+        assert_eq!(roman2kana("konnnichiha", option), "こんにちは");
+        assert_eq!(roman2kana("kon'ya", option), "こんや");
+        assert_eq!(roman2kana("shinbun", option), "しんぶん");
+        assert_eq!(roman2kana("hon", option), "ほん");
+    }
+
+    #[test]
+    fn test_roman2kana_others() {
+        let option = ConvOption::default();
+        assert_eq!(roman2kana("ra-men 123!", option), "らーめん 123!");
+        assert_eq!(roman2kana("xqz", option), "xqz");
+        assert_eq!(roman2kana("漢字desu", option), "漢字です");
+    }
+
+    #[test]
+    fn test_roman2kana_d_and_t() {
+        let option = ConvOption::default();
+        assert_eq!(roman2kana("pa-ti-", option), "ぱーてぃー");
+        assert_eq!(roman2kana("tudi", option), "とぅでぃ");
+        assert_eq!(roman2kana("hanadji", option), "はなぢ");
+        assert_eq!(roman2kana("tsudzuku", option), "つづく");
+        assert_eq!(roman2kana("dyadyudyo", option), "ぢゃぢゅぢょ");
+        assert_eq!(roman2kana("chijimu", option), "ちじむ");
+    }
+
+    #[test]
+    fn test_roman2kana_ignore() {
+        let option = ConvOption {
+            ignore: "K",
+            ..Default::default()
+        };
+        assert_eq!(roman2kana("kaKa", option), "かKあ");
+        assert_eq!(roman2kana("tKta", option), "tKた");
+    }
+
+    #[test]
+    fn test_roman2kana_round_trip() {
+        let option = ConvOption::default();
+        for text in [
+            "きって",
+            "しんぶん",
+            "きんえん",
+            "まっちゃ",
+            "こんや",
+            "ふぁいる",
+        ]
+        .iter()
+        {
+            assert_eq!(roman2kana(kana2roman(text, option), option), *text);
+        }
+    }
+
+    #[test]
+    fn test_kana2roman_kunrei() {
+        let option = ConvOption {
+            roman: RomanizationSystem::Kunrei,
+            ..Default::default()
+        };
+        assert_eq!(kana2roman("しちつふじぢづを", option), "sitituhuzizizuo");
+        assert_eq!(kana2roman("しゃちゅじょ", option), "syatyuzyo");
+        assert_eq!(kana2roman("まっちゃ", option), "mattya");
+        assert_eq!(kana2roman("きゃ", option), "kya");
+    }
+
+    #[test]
+    fn test_roman2kana_kunrei() {
+        let option = ConvOption {
+            roman: RomanizationSystem::Kunrei,
+            ..Default::default()
+        };
+        let text = "しちつふじしゃちゅじょまっちゃ";
+        let roman = kana2roman(text, option);
+        assert_eq!(roman2kana(&roman, option), text);
+    }
+
+    #[test]
+    fn test_kana2roman_nihon() {
+        let option = ConvOption {
+            roman: RomanizationSystem::Nihon,
+            ..Default::default()
+        };
+        assert_eq!(kana2roman("ぢづを", option), "diduwo");
+        assert_eq!(kana2roman("しちつふじ", option), "sitituhuzi");
+        assert_eq!(kana2roman("ぢゃぢゅぢょ", option), "dyadyudyo");
+        assert_eq!(kana2roman("ゐゑ", option), "wiwe");
+    }
+
+    #[test]
+    fn test_roman2kana_nihon() {
+        let option = ConvOption {
+            roman: RomanizationSystem::Nihon,
+            ..Default::default()
+        };
+        let text = "ぢづをしちつふじぢゃゐゑ";
+        let roman = kana2roman(text, option);
+        assert_eq!(roman2kana(&roman, option), text);
+    }
+
+    #[test]
+    fn test_roman2kata() {
+        let option = ConvOption::default();
+        assert_eq!(roman2kata("fa ti va we", option), "ファ ティ ヴァ ウェ");
+        assert_eq!(roman2kata("kitte", option), "キッテ");
+        assert_eq!(roman2kata("konpyu-ta-", option), "コンピューター");
+        assert_eq!(roman2kata("ひらがな", option), "ひらがな");
+    }
+
+    #[test]
+    fn test_roman2kata_ignore() {
+        let option = ConvOption {
+            ignore: "K",
+            ..Default::default()
+        };
+        assert_eq!(roman2kata("kaKa", option), "カKア");
+    }
+
+    #[test]
+    fn test_alphabet2kana() {
+        let option = ConvOption::default();
+        assert_eq!(alphabet2kana("mamisan", option), "まみさん");
+        assert_eq!(alphabet2kana("kyoutohezaxtsu", option), "きょうとへざっ");
+        assert_eq!(
+            alphabet2kana("shinbun", option),
+            roman2kana("shinbun", option)
+        );
+    }
+
+    #[test]
+    fn test_kana2alphabet() {
+        let option = ConvOption::default();
+        assert_eq!(kana2alphabet("まみさん", option), "mamisan");
+        assert_eq!(kana2alphabet("きょうはまっちゃ", option), "kyouhamaccha");
+        assert_eq!(kana2alphabet("ぱーてぃー", option), "pa-ti-");
+        assert_eq!(kana2alphabet("こんや", option), "konya");
+        assert_eq!(kana2alphabet("カタカナ", option), "カタカナ");
+    }
+
+    #[test]
+    fn test_kana2alphabet_small_tsu() {
+        let option = ConvOption::default();
+        assert_eq!(kana2alphabet("っあ", option), "xtsua");
+        assert_eq!(kana2alphabet("あっ", option), "axtsu");
+        assert_eq!(kana2alphabet("あっあ", option), "aaa");
+        assert_eq!(kana2alphabet("あっカ", option), "aカカ");
+    }
+
+    #[test]
+    fn test_alphabet2kana_inverse() {
+        let option = ConvOption::default();
+        let text = "きょうはぱーてぃーでした";
+        let alphabet = kana2alphabet(text, option);
+        assert_eq!(alphabet2kana(&alphabet, option), text);
+    }
+}
@@ -0,0 +1,664 @@
+//! Adapters which convert strings written or read piece by piece.
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::conv_table::ZERO_WIDTH;
+use crate::Converter;
+
+/// Source strings which are held until strings after them are known
+///
+/// The last character(and zero-width characters after it) is held because it may be merged
+/// with a following voiced consonant mark. Emitted strings are dropped, and only the last
+/// emitted character is kept as the context of `long_vowel`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Pending {
+    /// Source strings which are not emitted yet
+    buf: String,
+    /// The last character of emitted strings
+    prev: Option<char>,
+}
+
+impl Pending {
+    /// Append strings, and emit converted strings which no following strings can change
+    pub(crate) fn feed<F: FnMut(&str)>(&mut self, converter: &Converter, text: &str, emit: F) {
+        self.buf.push_str(text);
+        let held = self
+            .buf
+            .char_indices()
+            .rev()
+            .find(|(_, c)| !ZERO_WIDTH.contains(c))
+            .map_or(0, |(i, _)| i);
+        self.convert(converter, held, emit);
+    }
+
+    /// Emit converted strings of all held strings
+    pub(crate) fn finish<F: FnMut(&str)>(&mut self, converter: &Converter, emit: F) {
+        self.convert(converter, self.buf.len(), emit);
+        self.buf.clear();
+        self.prev = None;
+    }
+
+    /// Emit converted strings of source characters which end by `end`
+    fn convert<F: FnMut(&str)>(&mut self, converter: &Converter, end: usize, mut emit: F) {
+        if end == 0 {
+            return;
+        }
+        let (output, spans) = converter.convert_spans_after(&self.buf, self.prev);
+        let mut emitted = 0;
+        for (source, converted) in spans.into_iter().take_while(|(s, _)| s.end <= end) {
+            let converted = &output[converted];
+            emit(converted);
+            self.prev = converted.chars().last().or(self.prev);
+            emitted = source.end;
+        }
+        self.buf.drain(..emitted);
+    }
+}
+
+/// An adapter of `fmt::Write` which converts strings written through it
+///
+/// Strings are converted as if they were written at once, so a voiced consonant mark
+/// written after its katakana(e.g. `ｶ` and then `ﾞ`) is merged. The last character is
+/// held until following strings are written, and written to the inner writer by `flush`,
+/// `into_inner` or dropping the adapter. `ignore_pattern` is matched in each held piece.
+///
+/// # Example
+///
+/// ```rust
+/// use core::fmt::{self, Write};
+/// use kelp::{ConvOption, Conversion, Converter, ConvertingFmtWriter};
+///
+/// struct Item(&'static str, u32);
+///
+/// impl fmt::Display for Item {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         let converter = Converter::new(Conversion::H2z, ConvOption::all());
+///         let mut writer = ConvertingFmtWriter::new(&converter, f);
+///         write!(writer, "{} {}", self.0, self.1)?;
+///         writer.flush()
+///     }
+/// }
+///
+/// assert_eq!("ガイド　１２", Item("ｶﾞｲﾄﾞ", 12).to_string());
+/// ```
+#[derive(Debug)]
+pub struct ConvertingFmtWriter<'c, 'a, W: fmt::Write> {
+    converter: &'c Converter<'a>,
+    inner: Option<W>,
+    pending: Pending,
+}
+
+impl<'c, 'a, W: fmt::Write> ConvertingFmtWriter<'c, 'a, W> {
+    /// Returns an adapter which writes converted strings to `inner`
+    pub fn new(converter: &'c Converter<'a>, inner: W) -> ConvertingFmtWriter<'c, 'a, W> {
+        ConvertingFmtWriter {
+            converter,
+            inner: Some(inner),
+            pending: Pending::default(),
+        }
+    }
+
+    /// Write all held strings to the inner writer
+    ///
+    /// Strings written after this are converted separately from ones before this.
+    pub fn flush(&mut self) -> fmt::Result {
+        let mut result = Ok(());
+        if let Some(inner) = self.inner.as_mut() {
+            self.pending.finish(self.converter, |s| {
+                if result.is_ok() {
+                    result = inner.write_str(s);
+                }
+            });
+        }
+        result
+    }
+
+    /// The inner writer
+    pub fn get_ref(&self) -> &W {
+        self.inner
+            .as_ref()
+            .expect("inner writer exists until into_inner")
+    }
+
+    /// Write all held strings and return the inner writer
+    pub fn into_inner(mut self) -> Result<W, fmt::Error> {
+        self.flush()?;
+        Ok(self
+            .inner
+            .take()
+            .expect("inner writer exists until into_inner"))
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for ConvertingFmtWriter<'_, '_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut result = Ok(());
+        if let Some(inner) = self.inner.as_mut() {
+            self.pending.feed(self.converter, s, |s| {
+                if result.is_ok() {
+                    result = inner.write_str(s);
+                }
+            });
+        }
+        result
+    }
+}
+
+impl<W: fmt::Write> Drop for ConvertingFmtWriter<'_, '_, W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// An adapter of `io::Write` which converts UTF-8 bytes written through it
+///
+/// Bytes are converted as if they were written at once, so a multi-byte character or a
+/// voiced consonant mark split across writes is handled. The last character is held until
+/// following bytes are written, so `flush` writes converted bytes except it, and `finish`,
+/// `into_inner` or dropping the adapter writes all of them. Writing invalid UTF-8 bytes
+/// fails with `io::ErrorKind::InvalidData`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Write;
+/// use kelp::{ConvOption, Conversion, Converter, ConvertingWriter};
+///
+/// let converter = Converter::new(Conversion::H2z, ConvOption::all());
+/// let mut writer = ConvertingWriter::new(&converter, Vec::new());
+/// let bytes = "ｶﾞｲﾄﾞ".as_bytes();
+/// writer.write_all(&bytes[..4]).unwrap();
+/// writer.write_all(&bytes[4..]).unwrap();
+/// let output = writer.into_inner().unwrap();
+/// assert_eq!("ガイド", String::from_utf8(output).unwrap());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ConvertingWriter<'c, 'a, W: std::io::Write> {
+    converter: &'c Converter<'a>,
+    inner: Option<W>,
+    pending: Pending,
+    /// Bytes of a character whose rest is not written yet
+    incomplete: Vec<u8>,
+    /// Converted bytes which are not written to the inner writer yet
+    output: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<'c, 'a, W: std::io::Write> ConvertingWriter<'c, 'a, W> {
+    /// Returns an adapter which writes converted bytes to `inner`
+    pub fn new(converter: &'c Converter<'a>, inner: W) -> ConvertingWriter<'c, 'a, W> {
+        ConvertingWriter {
+            converter,
+            inner: Some(inner),
+            pending: Pending::default(),
+            incomplete: Vec::new(),
+            output: Vec::new(),
+        }
+    }
+
+    /// Write all held bytes to the inner writer and flush it
+    ///
+    /// Bytes written after this are converted separately from ones before this.
+    /// Fails with `io::ErrorKind::InvalidData` if a character is incomplete.
+    pub fn finish(&mut self) -> std::io::Result<()> {
+        finish_bytes(
+            self.converter,
+            &mut self.pending,
+            &self.incomplete,
+            &mut self.output,
+        )?;
+        self.write_output()?;
+        self.inner_mut().flush()
+    }
+
+    /// The inner writer
+    pub fn get_ref(&self) -> &W {
+        self.inner
+            .as_ref()
+            .expect("inner writer exists until into_inner")
+    }
+
+    /// Write all held bytes and return the inner writer
+    pub fn into_inner(mut self) -> std::io::Result<W> {
+        self.finish()?;
+        Ok(self
+            .inner
+            .take()
+            .expect("inner writer exists until into_inner"))
+    }
+
+    fn inner_mut(&mut self) -> &mut W {
+        self.inner
+            .as_mut()
+            .expect("inner writer exists until into_inner")
+    }
+
+    /// Write converted bytes to the inner writer, keeping ones which are not written
+    fn write_output(&mut self) -> std::io::Result<()> {
+        let inner = self
+            .inner
+            .as_mut()
+            .expect("inner writer exists until into_inner");
+        while !self.output.is_empty() {
+            match inner.write(&self.output) {
+                Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+                Ok(n) => drop(self.output.drain(..n)),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for ConvertingWriter<'_, '_, W> {
+    /// Convert bytes, which are always consumed unless they are invalid
+    ///
+    /// Converted bytes which the inner writer fails to write are kept,
+    /// and the error is returned by the following call.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_output()?;
+        feed_bytes(
+            self.converter,
+            &mut self.pending,
+            &mut self.incomplete,
+            buf,
+            &mut self.output,
+        )?;
+        let _ = self.write_output();
+        Ok(buf.len())
+    }
+
+    /// Write converted bytes except held ones, and flush the inner writer
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_output()?;
+        self.inner_mut().flush()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Drop for ConvertingWriter<'_, '_, W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.finish();
+        }
+    }
+}
+
+/// Append converted bytes of UTF-8 bytes to `output`
+///
+/// Bytes of an incomplete character at the end are kept in `incomplete`.
+/// Invalid bytes are not consumed, and fail with `io::ErrorKind::InvalidData`.
+#[cfg(feature = "std")]
+fn feed_bytes(
+    converter: &Converter,
+    pending: &mut Pending,
+    incomplete: &mut Vec<u8>,
+    buf: &[u8],
+    output: &mut Vec<u8>,
+) -> std::io::Result<()> {
+    let mut bytes = core::mem::take(incomplete);
+    let kept = bytes.len();
+    bytes.extend_from_slice(buf);
+    let valid = match core::str::from_utf8(&bytes) {
+        Ok(s) => s,
+        Err(e) if e.error_len().is_none() => {
+            *incomplete = bytes[e.valid_up_to()..].to_vec();
+            core::str::from_utf8(&bytes[..e.valid_up_to()]).expect("valid bytes")
+        }
+        Err(e) => {
+            bytes.truncate(kept);
+            *incomplete = bytes;
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e));
+        }
+    };
+    pending.feed(converter, valid, |s| output.extend_from_slice(s.as_bytes()));
+    Ok(())
+}
+
+/// Append converted bytes of all held strings to `output`
+///
+/// Fails with `io::ErrorKind::InvalidData` if a character is incomplete.
+#[cfg(feature = "std")]
+fn finish_bytes(
+    converter: &Converter,
+    pending: &mut Pending,
+    incomplete: &[u8],
+    output: &mut Vec<u8>,
+) -> std::io::Result<()> {
+    pending.finish(converter, |s| output.extend_from_slice(s.as_bytes()));
+    if !incomplete.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "incomplete UTF-8 sequence",
+        ));
+    }
+    Ok(())
+}
+
+/// An adapter of `io::Read` which converts UTF-8 bytes read through it
+///
+/// Bytes are converted as if they were read at once, so a multi-byte character or a
+/// voiced consonant mark split across reads of the inner reader is handled. Reading
+/// invalid UTF-8 bytes fails with `io::ErrorKind::InvalidData`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Read;
+/// use kelp::{ConvOption, Conversion, Converter, ConvertingReader};
+///
+/// let converter = Converter::new(Conversion::Z2h, ConvOption::all());
+/// let mut reader = ConvertingReader::new(&converter, "ガイド　１２".as_bytes());
+/// let mut output = String::new();
+/// reader.read_to_string(&mut output).unwrap();
+/// assert_eq!("ｶﾞｲﾄﾞ 12", output);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ConvertingReader<'c, 'a, R: std::io::Read> {
+    converter: &'c Converter<'a>,
+    inner: R,
+    pending: Pending,
+    /// Bytes of a character whose rest is not read yet
+    incomplete: Vec<u8>,
+    /// Converted bytes, whose first `position` bytes were already read
+    output: Vec<u8>,
+    position: usize,
+    /// Whether the inner reader reached the end
+    eof: bool,
+}
+
+#[cfg(feature = "std")]
+impl<'c, 'a, R: std::io::Read> ConvertingReader<'c, 'a, R> {
+    /// Returns an adapter which reads converted bytes from `inner`
+    pub fn new(converter: &'c Converter<'a>, inner: R) -> ConvertingReader<'c, 'a, R> {
+        ConvertingReader {
+            converter,
+            inner,
+            pending: Pending::default(),
+            incomplete: Vec::new(),
+            output: Vec::new(),
+            position: 0,
+            eof: false,
+        }
+    }
+
+    /// The inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader, discarding bytes which were read from it but not converted
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for ConvertingReader<'_, '_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut source = [0; 4096];
+        while self.position == self.output.len() && !self.eof {
+            self.output.clear();
+            self.position = 0;
+            match self.inner.read(&mut source)? {
+                0 => {
+                    let finished = finish_bytes(
+                        self.converter,
+                        &mut self.pending,
+                        &self.incomplete,
+                        &mut self.output,
+                    );
+                    // An incomplete character fails after converted bytes before it are read
+                    if self.output.is_empty() {
+                        finished?;
+                        self.eof = true;
+                    }
+                }
+                n => feed_bytes(
+                    self.converter,
+                    &mut self.pending,
+                    &mut self.incomplete,
+                    &source[..n],
+                    &mut self.output,
+                )?,
+            }
+        }
+        let rest = &self.output[self.position..];
+        let n = rest.len().min(buf.len());
+        buf[..n].copy_from_slice(&rest[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Conversion of character iterators
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{ConvOption, Conversion, ConvertChars, Converter};
+///
+/// let converter = Converter::new(Conversion::H2z, ConvOption::all());
+/// let mut chars = "ｶﾞｲﾄﾞ 12".chars().convert(&converter);
+/// assert_eq!(Some('ガ'), chars.next());
+/// assert_eq!("イド", chars.by_ref().take(2).collect::<String>());
+/// assert_eq!("　１２", chars.collect::<String>());
+/// ```
+pub trait ConvertChars: Iterator<Item = char> + Sized {
+    /// Returns an iterator of converted characters, which are converted lazily
+    fn convert<'c, 'a>(self, converter: &'c Converter<'a>) -> ConvertedChars<'c, 'a, Self>;
+}
+
+impl<I: Iterator<Item = char>> ConvertChars for I {
+    fn convert<'c, 'a>(self, converter: &'c Converter<'a>) -> ConvertedChars<'c, 'a, Self> {
+        ConvertedChars {
+            converter,
+            chars: Some(self),
+            pending: Pending::default(),
+            output: String::new(),
+            position: 0,
+        }
+    }
+}
+
+/// An iterator of converted characters, returned by `ConvertChars::convert`
+///
+/// Source characters are pulled until the converted characters of them are settled,
+/// so the converted characters are the same as ones of the collected string.
+#[derive(Debug, Clone)]
+pub struct ConvertedChars<'c, 'a, I: Iterator<Item = char>> {
+    converter: &'c Converter<'a>,
+    /// Source characters, which are `None` after exhausted
+    chars: Option<I>,
+    pending: Pending,
+    /// Converted strings, whose first `position` bytes were already yielded
+    output: String,
+    position: usize,
+}
+
+impl<I: Iterator<Item = char>> Iterator for ConvertedChars<'_, '_, I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        while self.position == self.output.len() {
+            self.output.clear();
+            self.position = 0;
+            let chars = self.chars.as_mut()?;
+            let output = &mut self.output;
+            match chars.next() {
+                Some(c) => self
+                    .pending
+                    .feed(self.converter, c.encode_utf8(&mut [0; 4]), |s| {
+                        output.push_str(s)
+                    }),
+                None => {
+                    self.pending.finish(self.converter, |s| output.push_str(s));
+                    self.chars = None;
+                }
+            }
+        }
+        let c = self.output[self.position..].chars().next()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvOption, Conversion, MiddleDotPolicy};
+    use alloc::string::ToString;
+    use core::fmt::Write;
+
+    /// Convert strings written piece by piece
+    fn write_pieces(converter: &Converter, pieces: &[&str]) -> String {
+        let mut writer = ConvertingFmtWriter::new(converter, String::new());
+        for piece in pieces {
+            writer.write_str(piece).unwrap();
+        }
+        writer.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_merge_across_pieces() {
+        let converter = Converter::new(Conversion::H2z, ConvOption::all());
+        assert_eq!(
+            write_pieces(&converter, &["ｶ", "ﾞﾋ", "\u{200b}", "ﾟA"]),
+            "ガピＡ"
+        );
+        assert_eq!(write_pieces(&converter, &["", "ｶ", "", "ｷ"]), "カキ");
+        assert_eq!(write_pieces(&converter, &[]), "");
+    }
+
+    #[test]
+    fn test_long_vowel_across_pieces() {
+        let option = ConvOption {
+            long_vowel: true,
+            ..Default::default()
+        };
+        let converter = Converter::new(Conversion::Kata2Hira, option);
+        let text = "カーードー、ーアー";
+        let expected = converter.convert(text);
+        let pieces = text
+            .char_indices()
+            .map(|(i, c)| &text[i..i + c.len_utf8()])
+            .collect::<alloc::vec::Vec<_>>();
+        assert_eq!(write_pieces(&converter, &pieces), expected);
+        assert_eq!(write_pieces(&converter, &[text]), expected);
+    }
+
+    #[test]
+    fn test_long_runs() {
+        let option = ConvOption {
+            long_vowel: true,
+            ..Default::default()
+        };
+        let kata2hira = Converter::new(Conversion::Kata2Hira, option);
+        let z2h = Converter::new(Conversion::Z2h, ConvOption::all());
+        let strip = Converter::new(
+            Conversion::Z2h,
+            ConvOption {
+                middle_dot_policy: MiddleDotPolicy::Strip,
+                ..ConvOption::all()
+            },
+        );
+        let vowels = "コ".to_string() + &"ー".repeat(20000);
+        let marks = "ー".repeat(20000);
+        let dots = "ア".to_string() + &"・".repeat(20000) + "イ";
+        for (converter, text) in [(&kata2hira, &vowels), (&z2h, &marks), (&strip, &dots)] {
+            let expected = converter.convert(text);
+            assert_eq!(
+                text.chars().convert(converter).collect::<String>(),
+                expected
+            );
+            let mut writer = ConvertingFmtWriter::new(converter, String::new());
+            for c in text.chars() {
+                writer.write_char(c).unwrap();
+            }
+            assert_eq!(writer.into_inner().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_flush_on_drop() {
+        let converter = Converter::new(Conversion::Z2h, ConvOption::all());
+        let mut output = String::new();
+        {
+            let mut writer = ConvertingFmtWriter::new(&converter, &mut output);
+            write!(writer, "{}ア", 1).unwrap();
+            assert_eq!(writer.get_ref().as_str(), "1");
+        }
+        assert_eq!(output, "1ｱ");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_converting_writer() {
+        use std::io::Write;
+
+        let converter = Converter::new(Conversion::H2z, ConvOption::all());
+        let mut writer = ConvertingWriter::new(&converter, Vec::new());
+        for b in "ｶﾞｷA".bytes() {
+            assert_eq!(writer.write(&[b]).unwrap(), 1);
+        }
+        writer.flush().unwrap();
+        assert_eq!(writer.get_ref().as_slice(), "ガキ".as_bytes());
+
+        let error = writer.write(&[0xff]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        writer.write_all("ﾋﾟ".as_bytes()).unwrap();
+        let output = writer.into_inner().unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "ガキＡピ");
+
+        let mut writer = ConvertingWriter::new(&converter, Vec::new());
+        writer.write_all(&"ｱ".as_bytes()[..1]).unwrap();
+        let error = writer.finish().unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_converting_reader() {
+        use std::io::Read;
+
+        let converter = Converter::new(Conversion::H2z, ConvOption::all());
+        let text = "ｶﾞｷﾟA";
+        let bytes = text.as_bytes().chain(&b"\xef\xbe"[..]);
+        let mut reader = ConvertingReader::new(&converter, bytes);
+        let mut buf = [0; 1];
+        let mut output = Vec::new();
+        while output.len() < converter.convert(text).len() {
+            assert_eq!(reader.read(&mut buf).unwrap(), 1);
+            output.push(buf[0]);
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), converter.convert(text));
+        let error = reader.read(&mut buf).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        let mut reader = ConvertingReader::new(&converter, &b"\xff"[..]);
+        let error = reader.read(&mut buf).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        let mut reader = ConvertingReader::new(&converter, &b""[..]);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_convert_chars() {
+        let converter = Converter::new(Conversion::H2z, ConvOption::all());
+        let text = "ｶﾞｷ\u{200b}ﾟ ﾋﾟA";
+        assert_eq!(
+            text.chars().convert(&converter).collect::<String>(),
+            converter.convert(text)
+        );
+        assert_eq!("".chars().convert(&converter).next(), None);
+
+        let mut chars = "ｱｲｳ".chars().inspect(|c| assert_ne!(*c, 'ｳ'));
+        assert_eq!(chars.by_ref().convert(&converter).next(), Some('ア'));
+    }
+}
@@ -0,0 +1,397 @@
+//! Options which only have flags used by each kind of conversions.
+use core::ops::RangeInclusive;
+
+#[cfg(feature = "regex")]
+use crate::Pattern;
+use crate::{CharPredicate, ConvOption, Fallback, MiddleDotPolicy, Target, VoicedKanaPolicy};
+
+pub(crate) mod sealed {
+    use crate::ConvOption;
+
+    /// Options which have an inner `ConvOption`
+    pub trait Sealed<'a> {
+        /// The inner option
+        fn conv_option(&self) -> &ConvOption<'a>;
+    }
+
+    impl<'a, T: Sealed<'a> + ?Sized> Sealed<'a> for &T {
+        fn conv_option(&self) -> &ConvOption<'a> {
+            (**self).conv_option()
+        }
+    }
+
+    impl<'a> Sealed<'a> for ConvOption<'a> {
+        fn conv_option(&self) -> &ConvOption<'a> {
+            self
+        }
+    }
+}
+
+/// Options accepted by conversions between full-width and half-width(`h2z` and `z2h`)
+///
+/// This is implemented for `WidthOption` and references to it. `ConvOption` is accepted too
+/// for compatibility, but `WidthOption` is preferred because it cannot have flags which
+/// these conversions ignore. This cannot be implemented outside kelp.
+///
+/// `KanaOption` is rejected:
+///
+/// ```compile_fail
+/// use kelp::{z2h, KanaOption};
+///
+/// z2h("アイウ", KanaOption::new().long_vowel(true));
+/// ```
+pub trait AsWidthOption<'a>: sealed::Sealed<'a> {}
+
+impl<'a, T: AsWidthOption<'a> + ?Sized> AsWidthOption<'a> for &T {}
+
+impl<'a> AsWidthOption<'a> for ConvOption<'a> {}
+
+/// Options accepted by conversions between hiragana and katakana
+/// (`hira2kata`, `hira2hkata` and `kata2hira`)
+///
+/// This is implemented for `KanaOption` and references to it. `ConvOption` is accepted too
+/// for compatibility, but `KanaOption` is preferred because it cannot have flags which
+/// these conversions ignore. This cannot be implemented outside kelp.
+///
+/// `WidthOption` is rejected:
+///
+/// ```compile_fail
+/// use kelp::{kata2hira, WidthOption};
+///
+/// kata2hira("アイウ", WidthOption::new().digit(true));
+/// ```
+pub trait AsKanaOption<'a>: sealed::Sealed<'a> {}
+
+impl<'a, T: AsKanaOption<'a> + ?Sized> AsKanaOption<'a> for &T {}
+
+impl<'a> AsKanaOption<'a> for ConvOption<'a> {}
+
+/// Set flags of the inner `ConvOption`
+macro_rules! flags {
+    ($($(#[$doc:meta])* $name:ident),* $(,)?) => {
+        $(
+            $(#[$doc])*
+            pub const fn $name(mut self, $name: bool) -> Self {
+                self.0.$name = $name;
+                self
+            }
+        )*
+    };
+}
+
+/// Set ignore settings of the inner `ConvOption`
+macro_rules! ignore_settings {
+    () => {
+        /// Set ignore characters.
+        pub const fn ignore(mut self, ignore: &'a str) -> Self {
+            self.0.ignore = ignore;
+            self
+        }
+
+        /// Set ignore characters in addition to `ignore`.
+        pub const fn ignore_chars(mut self, chars: &'a [char]) -> Self {
+            self.0.ignore_chars = chars;
+            self
+        }
+
+        /// Set a predicate of ignore characters.
+        pub const fn ignore_if(mut self, predicate: &'a (dyn Fn(char) -> bool + Sync)) -> Self {
+            self.0.ignore_if = Some(CharPredicate(predicate));
+            self
+        }
+
+        /// Set a pattern of ignore regions.
+        #[cfg(feature = "regex")]
+        pub const fn ignore_pattern(mut self, pattern: &'a regex::Regex) -> Self {
+            self.0.ignore_pattern = Some(Pattern(pattern));
+            self
+        }
+
+        /// Set inclusive ranges of ignore characters.
+        pub const fn ignore_ranges(mut self, ranges: &'a [RangeInclusive<char>]) -> Self {
+            self.0.ignore_ranges = ranges;
+            self
+        }
+
+        /// Set characters which are only converted.
+        pub const fn only(mut self, only: &'a str) -> Self {
+            self.0.only = only;
+            self
+        }
+
+        /// Set character groups which are not converted.
+        pub const fn exclude(mut self, exclude: Target) -> Self {
+            self.0.exclude = exclude;
+            self
+        }
+
+        /// Set how hiragana and katakana which have no mapping are handled.
+        pub const fn fallback(mut self, fallback: Fallback) -> Self {
+            self.0.fallback = fallback;
+            self
+        }
+
+        /// Set how katakana middle dots(`・` and `･`) are handled.
+        pub const fn middle_dot_policy(mut self, policy: MiddleDotPolicy) -> Self {
+            self.0.middle_dot_policy = policy;
+            self
+        }
+
+        /// Set how voiced katakana are converted to half-width katakana.
+        pub const fn voiced_kana(mut self, policy: VoicedKanaPolicy) -> Self {
+            self.0.voiced_kana = policy;
+            self
+        }
+    };
+}
+
+/// An option of conversions between full-width and half-width(`h2z` and `z2h`)
+///
+/// Flags which are not used by `h2z` and `z2h`(e.g. `long_vowel`) cannot be set,
+/// and conversions between hiragana and katakana do not accept this.
+/// This can be built in const contexts(e.g. `static`).
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{h2z, z2h, WidthOption};
+///
+/// let option = WidthOption::new().ascii(true).kana(true).ignore("Ａ");
+/// assert_eq!("ＡBCｱｲｳ", z2h("ＡＢＣアイウ", &option));
+/// assert_eq!("ＡＢＣアイウ", h2z("ABCｱｲｳ", &option));
+///
+/// static DIGIT: WidthOption = WidthOption::new().digit(true);
+/// assert_eq!("123", z2h("１２３", &DIGIT));
+/// ```
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct WidthOption<'a>(ConvOption<'a>);
+
+impl<'a> WidthOption<'a> {
+    /// Returns an option which disables all flags
+    pub const fn new() -> WidthOption<'a> {
+        WidthOption(ConvOption::new())
+    }
+
+    /// Returns an option which enables only kana
+    pub const fn kana_only() -> WidthOption<'a> {
+        WidthOption::new().kana(true)
+    }
+
+    /// Returns an option which enables only ascii
+    pub const fn ascii_only() -> WidthOption<'a> {
+        WidthOption::new().ascii(true)
+    }
+
+    /// Returns an option which enables ascii and digit
+    pub const fn ascii_digits() -> WidthOption<'a> {
+        WidthOption::new().ascii(true).digit(true)
+    }
+
+    /// Returns an option which enables letters and digits, but keeps symbols and spaces
+    pub const fn alphanumeric() -> WidthOption<'a> {
+        WidthOption::new().letter(true).digit(true)
+    }
+
+    flags!(
+        /// Set a flag of small katakana of Ainu.
+        ainu_kana,
+        /// Set a flag of ascii.
+        ascii,
+        /// Set a flag of composing kana and voiced consonant marks.
+        compose_mark,
+        /// Set a flag of currency symbols.
+        currency,
+        /// Set a flag of digits.
+        digit,
+        /// Set a flag of enclosed numbers.
+        enclosed,
+        /// Set a flag of katakana.
+        kana,
+        /// Set a flag of approximate half-width katakana.
+        kana_fallback,
+        /// Set a flag of Japanese punctuation.
+        kana_punct,
+        /// Set a flag of latin letters.
+        letter,
+        /// Set a flag of folding middle dots.
+        middle_dot,
+        /// Set a flag of roman numerals.
+        numeral,
+        /// Set a flag of keeping spaces.
+        preserve_space,
+        /// Set a flag of prolonged sound marks.
+        prolonged_mark,
+        /// Set a flag of ascii symbols.
+        punct,
+        /// Set a flag of small katakana.
+        small_kana,
+        /// Set a flag of spaces.
+        space,
+        /// Set a flag of squared characters.
+        square,
+        /// Set a flag of folding wave dash.
+        wave_dash,
+        /// Set a flag of symbols which have another width form.
+        wide_symbol,
+    );
+
+    ignore_settings!();
+}
+
+impl<'a> sealed::Sealed<'a> for WidthOption<'a> {
+    fn conv_option(&self) -> &ConvOption<'a> {
+        &self.0
+    }
+}
+
+impl<'a> AsWidthOption<'a> for WidthOption<'a> {}
+
+impl<'a> From<WidthOption<'a>> for ConvOption<'a> {
+    fn from(option: WidthOption<'a>) -> ConvOption<'a> {
+        option.0
+    }
+}
+
+/// An option of conversions between hiragana and katakana
+/// (`hira2kata`, `hira2hkata` and `kata2hira`)
+///
+/// Flags which are not used by these conversions(e.g. `digit`) cannot be set,
+/// and `h2z` and `z2h` do not accept this.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::{kata2hira, KanaOption};
+///
+/// let option = KanaOption::new().long_vowel(true).keep_kana_punct(true);
+/// assert_eq!("「ああと」", kata2hira("「アート」", &option));
+/// assert_eq!("らあめん", kata2hira("ラーメン", option));
+/// ```
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct KanaOption<'a>(ConvOption<'a>);
+
+impl<'a> KanaOption<'a> {
+    /// Returns an option which disables all flags
+    pub const fn new() -> KanaOption<'a> {
+        KanaOption(ConvOption::new())
+    }
+
+    flags!(
+        /// Set a flag of small katakana of Ainu.
+        ainu_kana,
+        /// Set a flag of composing kana and voiced consonant marks.
+        compose_mark,
+        /// Set a flag of approximate half-width katakana by `hira2hkata`.
+        kana_fallback,
+        /// Set a flag of keeping Japanese punctuation.
+        keep_kana_punct,
+        /// Set a flag of replacing prolonged sound marks with vowels.
+        long_vowel,
+        /// Set a flag of folding middle dots.
+        middle_dot,
+        /// Set a flag of converting obsolete kana to modern ones.
+        modern_kana,
+        /// Set a flag of folding wave dash.
+        wave_dash,
+    );
+
+    ignore_settings!();
+}
+
+impl<'a> sealed::Sealed<'a> for KanaOption<'a> {
+    fn conv_option(&self) -> &ConvOption<'a> {
+        &self.0
+    }
+}
+
+impl<'a> AsKanaOption<'a> for KanaOption<'a> {}
+
+impl<'a> From<KanaOption<'a>> for ConvOption<'a> {
+    fn from(option: KanaOption<'a>) -> ConvOption<'a> {
+        option.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{h2z, hira2hkata, kata2hira_with_unknown, z2h};
+    use crate::{z2h_batch, z2h_in_place, z2h_spans, z2h_to};
+    use alloc::string::String;
+
+    #[test]
+    fn test_width_option() {
+        let option = WidthOption::new().ascii(true).digit(true).space(false);
+        let expected = ConvOption {
+            ascii: true,
+            digit: true,
+            ..Default::default()
+        };
+        assert_eq!(ConvOption::from(option), expected);
+        assert_eq!(z2h("ＡＢ１", option), z2h("ＡＢ１", expected));
+        assert_eq!(h2z("ab1", option), "ａｂ１");
+
+        let (converted, spans) = z2h_spans("ＡＢ", option);
+        assert_eq!((converted.as_str(), spans.len()), ("AB", 2));
+        assert_eq!(z2h_batch(["Ａ", "１"], option), ["A", "1"]);
+        let mut buf = String::from("Ａ１");
+        z2h_in_place(&mut buf, option);
+        assert_eq!(buf, "A1");
+        z2h_to(&mut buf, "Ｂ", option).unwrap();
+        assert_eq!(buf, "A1B");
+        let kana = KanaOption::new();
+        assert_eq!(kata2hira_with_unknown("アヷ", kana).1, ['ヷ']);
+    }
+
+    #[test]
+    fn test_presets_same_as_untyped() {
+        assert_eq!(
+            ConvOption::from(WidthOption::kana_only()),
+            ConvOption::kana_only()
+        );
+        assert_eq!(
+            ConvOption::from(WidthOption::ascii_only()),
+            ConvOption::ascii_only()
+        );
+        assert_eq!(
+            ConvOption::from(WidthOption::ascii_digits()),
+            ConvOption::ascii_digits()
+        );
+        assert_eq!(
+            ConvOption::from(WidthOption::alphanumeric()),
+            ConvOption::alphanumeric()
+        );
+    }
+
+    #[test]
+    fn test_ignore_settings() {
+        let ranges = ['ｳ'..='ｴ'];
+        let option = WidthOption::new()
+            .kana(true)
+            .ignore("ｱ")
+            .ignore_chars(&['ｲ'])
+            .ignore_ranges(&ranges)
+            .ignore_if(&|c| c == 'ｶ');
+        assert_eq!(h2z("ｱｲｳｴｵｶｷ", option), "ｱｲｳｴオｶキ");
+
+        let option = KanaOption::new().only("かき").ignore_chars(&['き']);
+        assert_eq!(hira2hkata("かきく", option), "ｶきく");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_ignore_pattern() {
+        use crate::kata2hira;
+
+        let brackets = regex::Regex::new(r"\[[^\]]*\]").unwrap();
+        let option = KanaOption::new().ignore_pattern(&brackets);
+        assert_eq!(kata2hira("ア[イ]ウ", option), "あ[イ]う");
+    }
+
+    #[test]
+    fn test_kana_option() {
+        let option = KanaOption::new().kana_fallback(true).ignore("ゐ");
+        assert_eq!(hira2hkata("ゎゐ", option), "ﾜゐ");
+        assert_eq!(ConvOption::from(option).ignore, "ゐ");
+    }
+}
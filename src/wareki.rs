@@ -0,0 +1,219 @@
+//! Conversion between Japanese era years(wareki) and Gregorian years.
+use alloc::format;
+use alloc::string::String;
+
+use crate::kanji2number;
+
+/// Japanese eras since Meiji
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum Era {
+    /// 明治(1868-10-23 to 1912-07-29)
+    Meiji,
+    /// 大正(1912-07-30 to 1926-12-24)
+    Taisho,
+    /// 昭和(1926-12-25 to 1989-01-07)
+    Showa,
+    /// 平成(1989-01-08 to 2019-04-30)
+    Heisei,
+    /// 令和(2019-05-01 to present)
+    Reiwa,
+}
+
+impl Era {
+    /// All eras in chronological order
+    pub const ALL: [Era; 5] = [Era::Meiji, Era::Taisho, Era::Showa, Era::Heisei, Era::Reiwa];
+
+    /// Name of the era(e.g. `令和`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Era::Meiji => "明治",
+            Era::Taisho => "大正",
+            Era::Showa => "昭和",
+            Era::Heisei => "平成",
+            Era::Reiwa => "令和",
+        }
+    }
+
+    /// Squared form of the name(e.g. `㋿`)
+    fn square(&self) -> char {
+        match self {
+            Era::Meiji => '㍾',
+            Era::Taisho => '㍽',
+            Era::Showa => '㍼',
+            Era::Heisei => '㍻',
+            Era::Reiwa => '㋿',
+        }
+    }
+
+    /// First day of the era as `(year, month, day)`
+    pub fn start(&self) -> (u32, u32, u32) {
+        match self {
+            Era::Meiji => (1868, 10, 23),
+            Era::Taisho => (1912, 7, 30),
+            Era::Showa => (1926, 12, 25),
+            Era::Heisei => (1989, 1, 8),
+            Era::Reiwa => (2019, 5, 1),
+        }
+    }
+
+    /// Last Gregorian year of the era, or `None` for the current era
+    pub fn last_year(&self) -> Option<u32> {
+        let next = Era::ALL.iter().find(|e| *e > self)?;
+        Some(next.start().0)
+    }
+
+    /// Gregorian year of a year of the era(`1` is the first year)
+    ///
+    /// Returns `None` if the era had already ended in the year.
+    pub fn to_year(&self, era_year: u32) -> Option<u32> {
+        let year = self.start().0.checked_add(era_year.checked_sub(1)?)?;
+        match self.last_year() {
+            Some(last) if year > last => None,
+            _ => Some(year),
+        }
+    }
+}
+
+/// Parse an era year(e.g. `令和6`, `平成３１年`, `令和元年`) into a Gregorian year
+///
+/// The era name can be a squared character(e.g. `㋿`), and the year can be written
+/// in half-width, full-width or kanji digits, or `元` for the first year.
+/// A trailing `年` is optional.
+///
+/// Returns `None` if `text` is not an era year, or the year is after the end of the era
+/// (e.g. `平成32`).
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::wareki2year;
+///
+/// assert_eq!(Some(2024), wareki2year("令和6"));
+/// assert_eq!(Some(2019), wareki2year("平成３１年"));
+/// assert_eq!(Some(2019), wareki2year("令和元年"));
+/// assert_eq!(Some(1989), wareki2year("昭和六十四年"));
+/// assert_eq!(None, wareki2year("平成32年"));
+/// ```
+pub fn wareki2year(text: &str) -> Option<u32> {
+    let text = text.trim();
+    let (era, rest) = Era::ALL.iter().find_map(|era| {
+        text.strip_prefix(era.name())
+            .or_else(|| text.strip_prefix(era.square()))
+            .map(|rest| (era, rest))
+    })?;
+    let rest = rest.strip_suffix('年').unwrap_or(rest);
+    let era_year = match rest {
+        "元" => 1,
+        _ => u32::try_from(kanji2number(rest)?).ok()?,
+    };
+    era.to_year(era_year)
+}
+
+/// Format a Gregorian year as an era year(e.g. `令和6年`)
+///
+/// In a year when an era changed(e.g. 2019), the new era is used.
+/// Use `date2wareki` to take the date into account.
+/// Returns `None` before Meiji.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::year2wareki;
+///
+/// assert_eq!(Some("令和6年".to_string()), year2wareki(2024));
+/// assert_eq!(Some("令和元年".to_string()), year2wareki(2019));
+/// assert_eq!(None, year2wareki(1800));
+/// ```
+pub fn year2wareki(year: u32) -> Option<String> {
+    date2wareki(year, 12, 31)
+}
+
+/// Format a Gregorian date as an era year(e.g. `平成31年`)
+///
+/// Returns `None` before Meiji.
+///
+/// # Example
+///
+/// ```rust
+/// use kelp::date2wareki;
+///
+/// assert_eq!(Some("平成31年".to_string()), date2wareki(2019, 4, 30));
+/// assert_eq!(Some("令和元年".to_string()), date2wareki(2019, 5, 1));
+/// ```
+pub fn date2wareki(year: u32, month: u32, day: u32) -> Option<String> {
+    let era = Era::ALL
+        .iter()
+        .rev()
+        .find(|era| era.start() <= (year, month, day))?;
+    Some(match year - era.start().0 + 1 {
+        1 => format!("{}元年", era.name()),
+        n => format!("{}{}年", era.name(), n),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wareki2year() {
+        assert_eq!(wareki2year("明治元年"), Some(1868));
+        assert_eq!(wareki2year("大正15年"), Some(1926));
+        assert_eq!(wareki2year("昭和64年"), Some(1989));
+        assert_eq!(wareki2year("平成1"), Some(1989));
+        assert_eq!(wareki2year("平成３１"), Some(2019));
+        assert_eq!(wareki2year("令和6"), Some(2024));
+        assert_eq!(wareki2year("令和十年"), Some(2028));
+        assert_eq!(wareki2year("㋿6年"), Some(2024));
+        assert_eq!(wareki2year("㍻31"), Some(2019));
+    }
+
+    #[test]
+    fn test_wareki2year_boundary() {
+        assert_eq!(wareki2year("明治45年"), Some(1912));
+        assert_eq!(wareki2year("明治46年"), None);
+        assert_eq!(wareki2year("昭和65年"), None);
+        assert_eq!(wareki2year("平成31年"), Some(2019));
+        assert_eq!(wareki2year("平成32年"), None);
+        assert_eq!(wareki2year("令和100年"), Some(2118));
+    }
+
+    #[test]
+    fn test_wareki2year_invalid() {
+        assert_eq!(wareki2year(""), None);
+        assert_eq!(wareki2year("令和"), None);
+        assert_eq!(wareki2year("令和0年"), None);
+        assert_eq!(wareki2year("2024年"), None);
+        assert_eq!(wareki2year("令和6年5月"), None);
+    }
+
+    #[test]
+    fn test_year2wareki() {
+        assert_eq!(year2wareki(1868).as_deref(), Some("明治元年"));
+        assert_eq!(year2wareki(1912).as_deref(), Some("大正元年"));
+        assert_eq!(year2wareki(1926).as_deref(), Some("昭和元年"));
+        assert_eq!(year2wareki(1989).as_deref(), Some("平成元年"));
+        assert_eq!(year2wareki(2018).as_deref(), Some("平成30年"));
+        assert_eq!(year2wareki(2019).as_deref(), Some("令和元年"));
+        assert_eq!(year2wareki(2024).as_deref(), Some("令和6年"));
+        assert_eq!(year2wareki(1867), None);
+    }
+
+    #[test]
+    fn test_date2wareki_boundary() {
+        assert_eq!(date2wareki(1868, 10, 22), None);
+        assert_eq!(date2wareki(1912, 7, 29).as_deref(), Some("明治45年"));
+        assert_eq!(date2wareki(1912, 7, 30).as_deref(), Some("大正元年"));
+        assert_eq!(date2wareki(1989, 1, 7).as_deref(), Some("昭和64年"));
+        assert_eq!(date2wareki(1989, 1, 8).as_deref(), Some("平成元年"));
+        assert_eq!(date2wareki(2019, 4, 30).as_deref(), Some("平成31年"));
+        assert_eq!(date2wareki(2019, 5, 1).as_deref(), Some("令和元年"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for year in 1868..2100 {
+            assert_eq!(year2wareki(year).and_then(|w| wareki2year(&w)), Some(year));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command, Output};
+
+fn kelp_cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_kelp-cli"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("kelp-cli-{}-{}", process::id(), name));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_text() {
+    let output = kelp_cli(&["-a", "-c", "h2z", "ABC"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "ＡＢＣ\n");
+}
+
+#[test]
+fn test_preserve_space() {
+    let output = kelp_cli(&["-a", "-s", "-c", "z2h", "Ａ　Ｂ"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "A　B\n");
+}
+
+#[test]
+fn test_unknown_conversion() {
+    let output = kelp_cli(&["-c", "x2y", "ABC"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("unknown conversion `x2y`"));
+}
+
+#[test]
+fn test_file() {
+    let path = temp_file("file.txt", "ＡＢＣ１２３\nアイウ\n");
+    let output = kelp_cli(&["-a", "-d", "-k", "-c", "z2h", "-f", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "ABC123\nｱｲｳ\n");
+}
+
+#[test]
+fn test_file_with_text() {
+    let path = temp_file("file_with_text.txt", "ＡＢＣ");
+    let output = kelp_cli(&["-c", "z2h", "-f", path.to_str().unwrap(), "ＡＢＣ"]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_file_not_found() {
+    let output = kelp_cli(&["-c", "z2h", "-f", "/nonexistent/kelp-cli.txt"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("failed to read"));
+}
+
+#[test]
+fn test_json() {
+    let output = kelp_cli(&["--json", "-a", "-c", "z2h", "ＡＢＣ１２３"]);
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "input": "ＡＢＣ１２３",
+            "output": "ABC１２３",
+            "conversion": "z2h",
+            "converted_chars": 3,
+        })
+    );
+}
+
+#[test]
+fn test_json_file() {
+    let path = temp_file("json.txt", "ｱｲｳ\n");
+    let output = kelp_cli(&["--json", "-c", "h2z", "-f", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["output"], "アイウ\n");
+    assert_eq!(json["conversion"], "h2z");
+    assert_eq!(json["converted_chars"], 3);
+}
+
+#[test]
+fn test_only() {
+    let output = kelp_cli(&["-d", "-o", "０１", "-c", "z2h", "０１２"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "01２\n");
+}
+
+#[test]
+fn test_ignore_range() {
+    let output = kelp_cli(&[
+        "--ignore-range",
+        "ア-オ",
+        "--ignore-range",
+        "ラ-ロ",
+        "-c",
+        "kata2hira",
+        "アカリ",
+    ]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "アかリ\n");
+
+    let output = kelp_cli(&["--ignore-range", "オ-ア", "-c", "kata2hira", "アカリ"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_voiced_kana() {
+    let output = kelp_cli(&["--voiced-base", "-c", "h2hk", "がか"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "ｶｶ\n");
+
+    let output = kelp_cli(&["--keep-voiced", "-c", "h2hk", "がか"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "がｶ\n");
+}
+
+#[test]
+fn test_exclude() {
+    let output = kelp_cli(&["-a", "--exclude", "letter", "-c", "z2h", "ＡＢ（）"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "ＡＢ()\n");
+
+    let output = kelp_cli(&["--exclude", "hiragana", "-c", "z2h", "ア"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_fallback() {
+    let output = kelp_cli(&["--fallback", "?", "-c", "k2h", "アヷイ"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "あ?い\n");
+
+    let output = kelp_cli(&["--fallback", "drop", "-c", "k2h", "アヷイ"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "あい\n");
+
+    let output = kelp_cli(&["--fallback", "error", "-c", "k2h", "アヷイ"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("`ヷ`"));
+}
+
+#[test]
+fn test_middle_dot_policy() {
+    let output = kelp_cli(&["-k", "--keep-middle-dot", "-c", "z2h", "ア・イ"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "ｱ・ｲ\n");
+
+    let output = kelp_cli(&["-k", "--strip-middle-dot", "-c", "z2h", "ア・イ"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "ｱｲ\n");
+
+    let output = kelp_cli(&["--keep-middle-dot", "--strip-middle-dot", "ア"]);
+    assert!(!output.status.success());
+}
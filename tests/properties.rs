@@ -0,0 +1,241 @@
+use kelp::*;
+use proptest::prelude::*;
+
+/// Strings which are likely to hit conversion tables
+fn text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        "[ -~　！-～ぁ-ゟ゠-ヿｦ-ﾟ〜−←-↓①-⒛㊿㌀-㍗㍱-㏟Ⅰ-ↈゐゑヰヱ＄￠-￦$¢£¥₩゛゜ー\u{3099}\u{fe0f}😀]{0,32}",
+    ]
+}
+
+/// A reader which returns bytes in chunks of the sizes in turn
+#[cfg(feature = "std")]
+struct ChunkedReader<'a> {
+    bytes: &'a [u8],
+    sizes: std::iter::Cycle<std::slice::Iter<'a, usize>>,
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for ChunkedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = self.sizes.next().map_or(0, |size| *size);
+        let n = size.min(buf.len()).min(self.bytes.len());
+        buf[..n].copy_from_slice(&self.bytes[..n]);
+        self.bytes = &self.bytes[n..];
+        Ok(n)
+    }
+}
+
+fn option<'a>(flags: [bool; 28], ignore: &'a str) -> ConvOption<'a> {
+    ConvOption {
+        ainu_kana: flags[26],
+        ascii: flags[0],
+        compose_mark: flags[1],
+        currency: flags[16],
+        digit: flags[2],
+        enclosed: flags[13],
+        fallback: if flags[27] {
+            Fallback::Replace('\u{fffd}')
+        } else {
+            Fallback::Keep
+        },
+        exclude: if flags[24] {
+            Target::LETTER | Target::SMALL_KANA
+        } else {
+            Target::NONE
+        } | if flags[25] {
+            Target::PROLONGED_MARK
+        } else {
+            Target::NONE
+        },
+        ignore,
+        kana: flags[3],
+        kana_fallback: flags[19],
+        kana_punct: flags[4],
+        keep_kana_punct: flags[21],
+        letter: flags[5],
+        long_vowel: flags[6],
+        middle_dot: flags[12],
+        preserve_space: flags[7],
+        prolonged_mark: flags[18],
+        small_kana: flags[23],
+        space: flags[20],
+        punct: flags[8],
+        square: flags[14],
+        voiced_kana: if flags[22] {
+            VoicedKanaPolicy::Base
+        } else {
+            VoicedKanaPolicy::Split
+        },
+        wave_dash: flags[9],
+        wide_symbol: flags[10],
+        modern_kana: flags[17],
+        numeral: flags[15],
+        only: if flags[11] { ignore } else { "" },
+        ..Default::default()
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn test_all_conversions(text in text(), flags: [bool; 28], ignore in "[ｱｶﾞＡ１ー゛]{0,3}") {
+        let option = option(flags, &ignore);
+        for conversion in Conversion::ALL.iter() {
+            let result = conversion.apply_counted(&text, option);
+            prop_assert!(result.converted <= text.chars().count());
+            prop_assert_eq!(&result.output, &conversion.apply(&text, option));
+
+            let (converted, spans) = conversion.apply_spans(&text, &option);
+            prop_assert_eq!(&converted, &result.output);
+            let mut source = 0;
+            let mut output = 0;
+            for (s, o) in spans.iter() {
+                prop_assert_eq!(s.start, source);
+                prop_assert_eq!(o.start, output);
+                prop_assert!(text.is_char_boundary(s.end));
+                prop_assert!(converted.is_char_boundary(o.end));
+                source = s.end;
+                output = o.end;
+            }
+            prop_assert_eq!(source, text.len());
+            prop_assert_eq!(output, converted.len());
+
+            let mut buf = String::new();
+            conversion.apply_to(&mut buf, &text, &option).unwrap();
+            prop_assert_eq!(&buf, &converted);
+
+            prop_assert_eq!(conversion.apply_cow(&text, option), converted.as_str());
+
+            let converter = Converter::new(*conversion, option);
+            prop_assert_eq!(&converter.convert(&text), &converted);
+            prop_assert_eq!(
+                conversion.apply_batch([text.as_str()], &option),
+                vec![converted.clone()]
+            );
+        }
+
+        let mut buf = text.clone();
+        z2h_in_place(&mut buf, option);
+        prop_assert_eq!(buf, z2h(&text, option));
+        prop_assert_eq!(kata2hira_with_unknown(&text, option).0, kata2hira(&text, option));
+        prop_assert_eq!(hira2kata_with_unknown(&text, option).0, hira2kata(&text, option));
+        small2large(&text, option);
+        prop_assert_eq!(analyze(&text).total(), text.chars().count());
+    }
+
+    #[test]
+    fn test_idempotent(text in text(), flags: [bool; 28], ignore in "[ｱｶﾞＡ１ー゛]{0,3}") {
+        // `ｶ゛` becomes `カ゛` by `h2z`, and then `ガ` with `compose_mark`
+        let mut option = option(flags, &ignore);
+        option.compose_mark = false;
+        for conversion in Conversion::ALL.iter() {
+            let once = conversion.apply(&text, option);
+            let twice = conversion.apply(&once, option);
+            prop_assert_eq!(once, twice, "{}", conversion);
+        }
+        let once = small2large(&text, option);
+        prop_assert_eq!(small2large(&once, option), once);
+    }
+
+    #[test]
+    fn test_streaming(
+        text in text(),
+        flags: [bool; 28],
+        ignore in "[ｱｶﾞＡ１ー゛]{0,3}",
+        sizes in prop::collection::vec(1usize..5, 1..8),
+    ) {
+        use std::fmt::Write;
+
+        let option = option(flags, &ignore);
+        let chars = text.chars().collect::<Vec<_>>();
+        for conversion in Conversion::ALL.iter() {
+            let converter = Converter::new(*conversion, option);
+            let mut writer = ConvertingFmtWriter::new(&converter, String::new());
+            let mut rest = chars.as_slice();
+            for size in sizes.iter().cycle() {
+                if rest.is_empty() {
+                    break;
+                }
+                let (piece, tail) = rest.split_at((*size).min(rest.len()));
+                writer.write_str(&piece.iter().collect::<String>()).unwrap();
+                rest = tail;
+            }
+            prop_assert_eq!(writer.into_inner().unwrap(), converter.convert(&text));
+
+            prop_assert_eq!(
+                text.chars().convert(&converter).collect::<String>(),
+                converter.convert(&text)
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_streaming_writer(
+        text in text(),
+        flags: [bool; 28],
+        ignore in "[ｱｶﾞＡ１ー゛]{0,3}",
+        sizes in prop::collection::vec(1usize..5, 1..8),
+    ) {
+        use std::io::Write;
+
+        let option = option(flags, &ignore);
+        for conversion in Conversion::ALL.iter() {
+            let converter = Converter::new(*conversion, option);
+            let mut writer = ConvertingWriter::new(&converter, Vec::new());
+            let mut rest = text.as_bytes();
+            for size in sizes.iter().cycle() {
+                if rest.is_empty() {
+                    break;
+                }
+                let (piece, tail) = rest.split_at((*size).min(rest.len()));
+                writer.write_all(piece).unwrap();
+                rest = tail;
+            }
+            prop_assert_eq!(
+                String::from_utf8(writer.into_inner().unwrap()).unwrap(),
+                converter.convert(&text)
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_streaming_reader(
+        text in text(),
+        flags: [bool; 28],
+        ignore in "[ｱｶﾞＡ１ー゛]{0,3}",
+        sizes in prop::collection::vec(1usize..5, 1..8),
+    ) {
+        use std::io::Read;
+
+        let option = option(flags, &ignore);
+        for conversion in Conversion::ALL.iter() {
+            let converter = Converter::new(*conversion, option);
+            let chunks = ChunkedReader {
+                bytes: text.as_bytes(),
+                sizes: sizes.iter().cycle(),
+            };
+            let mut output = String::new();
+            ConvertingReader::new(&converter, chunks)
+                .read_to_string(&mut output)
+                .unwrap();
+            prop_assert_eq!(output, converter.convert(&text));
+        }
+    }
+
+    #[test]
+    fn test_pipeline(text in text(), flags: [bool; 28], ignore in "[ｱｶﾞＡ１ー゛]{0,3}") {
+        let option = option(flags, &ignore);
+        let mut pipeline = Pipeline::new();
+        let mut expected = text.clone();
+        for conversion in Conversion::ALL.iter().chain(Conversion::ALL.iter().rev()) {
+            pipeline = pipeline.then(*conversion, option);
+            expected = conversion.apply(&expected, option);
+        }
+        prop_assert_eq!(pipeline.run(&text), expected);
+    }
+}